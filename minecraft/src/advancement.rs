@@ -0,0 +1,173 @@
+use crate::entity::namespaced;
+use crate::json::Json;
+use crate::text;
+use crate::text::{Color, TextComponent};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An advancement from `data/<namespace>/advancement/`, read from its JSON.
+#[derive(PartialEq, Clone, Debug)]
+pub struct AdvancementNode {
+    pub parent: Option<String>,
+    /// Criteria by name.
+    pub criteria: BTreeMap<String, Criterion>,
+    /// Lists of criterion names. The advancement is done once every list has one done.
+    pub requirements: Vec<Vec<String>>,
+    pub display: Option<AdvancementDisplay>,
+    /// The function run as the player when they complete the advancement.
+    pub reward: Option<String>,
+}
+
+/// Something a player does, and the conditions it is done under.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Criterion {
+    /// The namespaced trigger, like `minecraft:tick`.
+    pub trigger: String,
+    pub conditions: Json,
+}
+
+/// How the advancement shows in the advancements screen and in chat.
+#[derive(PartialEq, Clone, Debug)]
+pub struct AdvancementDisplay {
+    pub title: TextComponent,
+    pub frame: Frame,
+    pub announce_to_chat: bool,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Frame {
+    Task,
+    Goal,
+    Challenge,
+}
+
+impl AdvancementNode {
+    /// Reads an advancement, leaving out criteria without a trigger. Without `requirements`
+    /// every criterion is required.
+    pub fn from_json(json: &Json) -> AdvancementNode {
+        let criteria: BTreeMap<String, Criterion> = match json.get("criteria") {
+            Some(Json::Object(criteria)) => criteria
+                .iter()
+                .filter_map(|(name, criterion)| {
+                    let trigger = criterion.get("trigger").and_then(Json::as_str)?;
+                    Some((
+                        name.clone(),
+                        Criterion {
+                            trigger: namespaced(trigger),
+                            conditions: criterion
+                                .get("conditions")
+                                .cloned()
+                                .unwrap_or(Json::Object(vec![])),
+                        },
+                    ))
+                })
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+        let requirements = match json.get("requirements").and_then(Json::as_array) {
+            Some(requirements) => requirements
+                .iter()
+                .filter_map(Json::as_array)
+                .map(|names| {
+                    names
+                        .iter()
+                        .filter_map(Json::as_str)
+                        .map(String::from)
+                        .collect()
+                })
+                .collect(),
+            None => criteria.keys().map(|name| vec![name.clone()]).collect(),
+        };
+        let display = json.get("display").map(|display| AdvancementDisplay {
+            title: display
+                .get("title")
+                .and_then(|title| text::from_json(title).ok())
+                .unwrap_or_else(|| TextComponent::text("")),
+            frame: match display.get("frame").and_then(Json::as_str) {
+                Some("goal") => Frame::Goal,
+                Some("challenge") => Frame::Challenge,
+                _ => Frame::Task,
+            },
+            announce_to_chat: display
+                .get("announce_to_chat")
+                .and_then(Json::as_bool)
+                .unwrap_or(true),
+        });
+        AdvancementNode {
+            parent: json.get("parent").and_then(Json::as_str).map(namespaced),
+            criteria,
+            requirements,
+            display,
+            reward: json
+                .get("rewards")
+                .and_then(|rewards| rewards.get("function"))
+                .and_then(Json::as_str)
+                .map(String::from),
+        }
+    }
+
+    /// Whether the criteria in `done` meet every requirement. An advancement with no
+    /// requirements is never done.
+    pub fn is_done(&self, done: &BTreeSet<String>) -> bool {
+        !self.requirements.is_empty()
+            && self
+                .requirements
+                .iter()
+                .all(|names| names.iter().any(|name| done.contains(name)))
+    }
+
+    /// The advancement the way chat names it: its title in brackets, green or purple for
+    /// challenges, or its id when it has no display.
+    pub fn name(&self, id: &str) -> TextComponent {
+        match &self.display {
+            Some(display) => {
+                let mut name = TextComponent::text("[");
+                name.extra = vec![display.title.clone(), TextComponent::text("]")];
+                name.style.color = Some(match display.frame {
+                    Frame::Challenge => Color::DarkPurple,
+                    _ => Color::Green,
+                });
+                name
+            }
+            None => TextComponent::text(id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requirements() {
+        let json = crate::json::parse(
+            r#"{
+                "display": {"title": "Explorer", "frame": "goal"},
+                "criteria": {
+                    "north": {"trigger": "location"},
+                    "south": {"trigger": "minecraft:location"},
+                    "never": {"trigger": "impossible"}
+                },
+                "requirements": [["north", "south"], ["never"]],
+                "rewards": {"function": "ns:explored"}
+            }"#,
+        )
+        .unwrap();
+        let advancement = AdvancementNode::from_json(&json);
+        assert_eq!(advancement.criteria["north"].trigger, "minecraft:location");
+        assert_eq!(advancement.reward, Some(String::from("ns:explored")));
+        assert_eq!(advancement.name("ns:explorer").plain(), "[Explorer]");
+        let mut done: BTreeSet<String> = ["south".to_string()].iter().cloned().collect();
+        assert!(!advancement.is_done(&done));
+        done.insert("never".to_string());
+        assert!(advancement.is_done(&done));
+
+        let json = crate::json::parse(
+            r#"{"criteria": {"a": {"trigger": "tick"}, "b": {"trigger": "tick"}}}"#,
+        )
+        .unwrap();
+        let advancement = AdvancementNode::from_json(&json);
+        assert_eq!(advancement.name("ns:hidden").plain(), "ns:hidden");
+        assert!(!advancement.is_done(&["a".to_string()].iter().cloned().collect()));
+        assert!(!AdvancementNode::from_json(&Json::Object(vec![])).is_done(&done));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::text::{translation_parts, Color, Content, Style, TextComponent, TranslationPart};
+
+const RESET: &str = "\x1b[0m";
+const CUBE_LEVELS: [u32; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Renders a resolved component with terminal escape codes. With `colors` off the output is
+/// exactly the component's plain text, which keeps CI logs readable.
+pub fn render(component: &TextComponent, colors: bool) -> String {
+    if !colors {
+        return component.plain();
+    }
+    let mut output = String::new();
+    write_component(component, &Style::default(), &mut output);
+    if !output.is_empty() {
+        output.push_str(RESET);
+    }
+    output
+}
+
+fn write_component(component: &TextComponent, parent: &Style, output: &mut String) {
+    let style = component.style.inherit(parent);
+    match &component.content {
+        Content::Translatable {
+            key,
+            fallback,
+            with,
+        } => {
+            for part in translation_parts(fallback.as_ref().unwrap_or(key)) {
+                match part {
+                    TranslationPart::Literal(text) => write_text(&text, &style, output),
+                    TranslationPart::Argument(index) => {
+                        if let Some(argument) = with.get(index) {
+                            write_component(argument, &style, output)
+                        }
+                    }
+                }
+            }
+        }
+        content => write_text(&content.plain(), &style, output),
+    }
+    for child in &component.extra {
+        write_component(child, &style, output);
+    }
+}
+
+fn write_text(text: &str, style: &Style, output: &mut String) {
+    if text.is_empty() {
+        return;
+    }
+    output.push_str(&escape(style));
+    if style.obfuscated == Some(true) {
+        output.extend(
+            text.chars()
+                .map(|c| if c.is_whitespace() { c } else { '▒' }),
+        );
+    } else {
+        output.push_str(text);
+    }
+}
+
+/// The escape sequence that resets the terminal and then applies `style`.
+pub fn escape(style: &Style) -> String {
+    let mut codes = vec![String::from("0")];
+    if let Some(color) = style.color {
+        codes.push(color_code(color));
+    }
+    let flags = [
+        (style.bold, "1"),
+        (style.italic, "3"),
+        (style.underlined, "4"),
+        (style.strikethrough, "9"),
+    ];
+    for (flag, code) in flags.iter() {
+        if *flag == Some(true) {
+            codes.push(String::from(*code));
+        }
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// The foreground SGR parameter for a color: one of the 16 standard colors for named colors,
+/// and the closest 256-color palette entry for hex colors.
+pub fn color_code(color: Color) -> String {
+    match color {
+        Color::Black => String::from("30"),
+        Color::DarkRed => String::from("31"),
+        Color::DarkGreen => String::from("32"),
+        Color::Gold => String::from("33"),
+        Color::DarkBlue => String::from("34"),
+        Color::DarkPurple => String::from("35"),
+        Color::DarkAqua => String::from("36"),
+        Color::Gray => String::from("37"),
+        Color::DarkGray => String::from("90"),
+        Color::Red => String::from("91"),
+        Color::Green => String::from("92"),
+        Color::Yellow => String::from("93"),
+        Color::Blue => String::from("94"),
+        Color::LightPurple => String::from("95"),
+        Color::Aqua => String::from("96"),
+        Color::White => String::from("97"),
+        Color::Hex(rgb) => format!("38;5;{}", approximate_256(rgb)),
+    }
+}
+
+/// The xterm 256-color index nearest to `rgb`, searching both the 6x6x6 color cube and the
+/// grayscale ramp.
+pub fn approximate_256(rgb: u32) -> u8 {
+    let (r, g, b) = ((rgb >> 16) & 0xFF, (rgb >> 8) & 0xFF, rgb & 0xFF);
+    let nearest_level = |v: u32| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|i| CUBE_LEVELS[*i].abs_diff(v))
+            .unwrap()
+    };
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (r + g + b) / 3;
+    let gray_index = (average.saturating_sub(3) / 10).min(23);
+    let gray = 8 + 10 * gray_index;
+
+    let error = |(cr, cg, cb): (u32, u32, u32)| {
+        cr.abs_diff(r).pow(2) + cg.abs_diff(g).pow(2) + cb.abs_diff(b).pow(2)
+    };
+    if error((gray, gray, gray)) < error(cube) {
+        (232 + gray_index) as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn plain_when_colors_disabled() {
+        let component = text::parse("[{\"text\":\"a\",\"color\":\"red\"},\"b\"]").unwrap();
+        assert_eq!(render(&component, false), "ab");
+    }
+
+    #[test]
+    fn named_color_and_flags() {
+        let component = text::parse("{\"text\":\"hi\",\"color\":\"red\",\"bold\":true}").unwrap();
+        assert_eq!(render(&component, true), "\x1b[0;91;1mhi\x1b[0m");
+    }
+
+    #[test]
+    fn children_inherit_style() {
+        let component = text::parse(
+            "{\"text\":\"a\",\"color\":\"gold\",\"extra\":[{\"text\":\"b\",\"italic\":true},{\"text\":\"c\",\"color\":\"white\"}]}",
+        )
+        .unwrap();
+        assert_eq!(
+            render(&component, true),
+            "\x1b[0;33ma\x1b[0;33;3mb\x1b[0;97mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn underline_and_strikethrough() {
+        let component =
+            text::parse("{\"text\":\"x\",\"underlined\":true,\"strikethrough\":true}").unwrap();
+        assert_eq!(render(&component, true), "\x1b[0;4;9mx\x1b[0m");
+    }
+
+    #[test]
+    fn obfuscated_text_is_masked() {
+        let component = text::parse("{\"text\":\"ab c\",\"obfuscated\":true}").unwrap();
+        assert_eq!(render(&component, true), "\x1b[0m▒▒ ▒\x1b[0m");
+        assert_eq!(render(&component, false), "ab c");
+    }
+
+    #[test]
+    fn translation_arguments_keep_their_style() {
+        let component = text::parse(
+            "{\"translate\":\"<%s> hi\",\"with\":[{\"text\":\"Alice\",\"color\":\"aqua\"}]}",
+        )
+        .unwrap();
+        assert_eq!(
+            render(&component, true),
+            "\x1b[0m<\x1b[0;96mAlice\x1b[0m> hi\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(approximate_256(0xFF0000), 196);
+        assert_eq!(approximate_256(0x000000), 16);
+        assert_eq!(approximate_256(0xFFFFFF), 231);
+        assert_eq!(approximate_256(0x808080), 244);
+        assert_eq!(approximate_256(0x5FAF87), 72);
+        assert_eq!(color_code(Color::Hex(0xFF0000)), "38;5;196");
+    }
+}
@@ -0,0 +1,189 @@
+use crate::entity::namespaced;
+use crate::nbt::Nbt;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A block as written in commands, like `oak_stairs[facing=east]` or `chest{Lock:"key"}`: its id,
+/// the block states given and the data of its block entity.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Block {
+    pub id: String,
+    pub states: BTreeMap<String, String>,
+    pub nbt: Option<Nbt>,
+}
+
+impl Block {
+    pub fn new(id: &str) -> Block {
+        Block {
+            id: String::from(id),
+            states: BTreeMap::new(),
+            nbt: None,
+        }
+    }
+
+    pub fn air() -> Block {
+        Block::new("minecraft:air")
+    }
+
+    pub fn is_air(&self) -> bool {
+        matches!(
+            namespaced(&self.id).as_str(),
+            "minecraft:air" | "minecraft:cave_air" | "minecraft:void_air"
+        )
+    }
+
+    /// Whether the block fits `predicate`, as `execute if block` and fill filters test it: the
+    /// ids are equal, every state in the predicate has the same value here and the predicate's
+    /// NBT matches the block entity's. States the block was placed without never match, since
+    /// there is no registry of their defaults.
+    pub fn matches(&self, predicate: &Block) -> bool {
+        namespaced(&self.id) == namespaced(&predicate.id)
+            && predicate
+                .states
+                .iter()
+                .all(|(state, value)| self.states.get(state) == Some(value))
+            && match (&self.nbt, &predicate.nbt) {
+                (_, None) => true,
+                (Some(nbt), Some(pattern)) => nbt.matches(pattern),
+                (None, Some(_)) => false,
+            }
+    }
+}
+
+impl Display for Block {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if !self.states.is_empty() {
+            let states: Vec<String> = self
+                .states
+                .iter()
+                .map(|(state, value)| format!("{}={}", state, value))
+                .collect();
+            write!(f, "[{}]", states.join(","))?;
+        }
+        if let Some(nbt) = &self.nbt {
+            write!(f, "{}", nbt)?;
+        }
+        Ok(())
+    }
+}
+
+/// The blocks of every dimension. Only blocks that have been placed are stored; everything
+/// else is air.
+#[derive(PartialEq, Debug, Default)]
+pub struct World {
+    dimensions: HashMap<String, HashMap<[i32; 3], Block>>,
+}
+
+impl World {
+    pub fn new() -> World {
+        World::default()
+    }
+
+    pub fn get(&self, dimension: &str, position: [i32; 3]) -> Block {
+        self.dimensions
+            .get(dimension)
+            .and_then(|blocks| blocks.get(&position))
+            .cloned()
+            .unwrap_or_else(Block::air)
+    }
+
+    /// Places `block`, returning whether anything changed.
+    pub fn set(&mut self, dimension: &str, position: [i32; 3], block: &Block) -> bool {
+        let block = Block {
+            id: namespaced(&block.id),
+            ..block.clone()
+        };
+        if self.get(dimension, position) == block {
+            return false;
+        }
+        let blocks = self.dimensions.entry(String::from(dimension)).or_default();
+        if block == Block::air() {
+            blocks.remove(&position);
+        } else {
+            blocks.insert(position, block);
+        }
+        true
+    }
+
+    /// Whether `position` is inside the world border and build height of `dimension`.
+    pub fn contains(dimension: &str, position: [i32; 3]) -> bool {
+        let (bottom, top) = match dimension {
+            "minecraft:the_nether" | "minecraft:the_end" => (0, 256),
+            _ => (-64, 320),
+        };
+        let [x, y, z] = position;
+        x.abs() < 30_000_000 && z.abs() < 30_000_000 && bottom <= y && y < top
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stairs() -> Block {
+        let mut block = Block::new("oak_stairs");
+        block
+            .states
+            .insert("facing".to_string(), "east".to_string());
+        block.states.insert("half".to_string(), "top".to_string());
+        block
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(stairs().to_string(), "oak_stairs[facing=east,half=top]");
+        let mut chest = Block::new("minecraft:chest");
+        chest.nbt = Some(crate::nbt::parse("{Lock:\"key\"}").unwrap());
+        assert_eq!(chest.to_string(), "minecraft:chest{Lock:\"key\"}");
+    }
+
+    #[test]
+    fn predicates() {
+        let mut predicate = Block::new("minecraft:oak_stairs");
+        assert!(stairs().matches(&predicate));
+        predicate
+            .states
+            .insert("facing".to_string(), "east".to_string());
+        assert!(stairs().matches(&predicate));
+        predicate
+            .states
+            .insert("waterlogged".to_string(), "false".to_string());
+        assert!(!stairs().matches(&predicate));
+        assert!(!Block::new("stone").matches(&Block::new("dirt")));
+        let mut chest = Block::new("chest");
+        chest.nbt = Some(crate::nbt::parse("{Lock:\"key\",Items:[]}").unwrap());
+        let mut locked = Block::new("chest");
+        locked.nbt = Some(crate::nbt::parse("{Lock:\"key\"}").unwrap());
+        assert!(chest.matches(&locked));
+        assert!(!Block::new("chest").matches(&locked));
+    }
+
+    #[test]
+    fn sparse_world() {
+        let mut world = World::new();
+        let overworld = "minecraft:overworld";
+        assert_eq!(world.get(overworld, [0, 0, 0]), Block::air());
+        assert!(world.set(overworld, [0, 0, 0], &Block::new("stone")));
+        assert!(!world.set(overworld, [0, 0, 0], &Block::new("minecraft:stone")));
+        assert_eq!(world.get(overworld, [0, 0, 0]).id, "minecraft:stone");
+        assert_eq!(world.get("minecraft:the_nether", [0, 0, 0]), Block::air());
+        assert!(world.set(overworld, [0, 0, 0], &Block::new("air")));
+        assert_eq!(world, {
+            let mut empty = World::new();
+            empty
+                .dimensions
+                .insert(overworld.to_string(), HashMap::new());
+            empty
+        });
+    }
+
+    #[test]
+    fn build_limits() {
+        assert!(World::contains("minecraft:overworld", [0, -64, 0]));
+        assert!(!World::contains("minecraft:overworld", [0, 320, 0]));
+        assert!(!World::contains("minecraft:the_nether", [0, -1, 0]));
+        assert!(!World::contains("minecraft:overworld", [30_000_000, 0, 0]));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::text::TextComponent;
+use crate::{BossbarColor, BossbarStyle};
+use std::collections::BTreeSet;
+
+/// A boss bar made with `bossbar add`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct CustomBossbar {
+    pub name: TextComponent,
+    pub color: BossbarColor,
+    pub style: BossbarStyle,
+    pub value: i32,
+    pub max: i32,
+    pub visible: bool,
+    /// Names of the players shown the bar, whether or not they are online.
+    pub players: BTreeSet<String>,
+}
+
+impl CustomBossbar {
+    pub fn new(name: TextComponent) -> CustomBossbar {
+        CustomBossbar {
+            name,
+            color: BossbarColor::White,
+            style: BossbarStyle::Progress,
+            value: 0,
+            max: 100,
+            visible: true,
+            players: BTreeSet::new(),
+        }
+    }
+
+    /// How full the bar is drawn, from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        (f64::from(self.value) / f64::from(self.max)).clamp(0.0, 1.0)
+    }
+
+    /// The bar as one line of text, `width` cells wide: the name, the bar split into its notches
+    /// and the value, like `Boss [█████░░░░░] 50/100`.
+    pub fn progress_line(&self, width: usize) -> String {
+        let notches = self.style.notches();
+        let cells = (width / notches).max(1) * notches;
+        let filled = (self.progress() * cells as f64).round() as usize;
+        let mut bar = String::new();
+        for cell in 0..cells {
+            if cell > 0 && cell % (cells / notches) == 0 {
+                bar.push('|');
+            }
+            bar.push(if cell < filled { '█' } else { '░' });
+        }
+        format!(
+            "{} [{}] {}/{}",
+            self.name.plain(),
+            bar,
+            self.value,
+            self.max
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_lines() {
+        let mut bar = CustomBossbar::new(TextComponent::text("Boss"));
+        bar.value = 50;
+        assert_eq!(bar.progress_line(10), "Boss [█████░░░░░] 50/100");
+        bar.style = BossbarStyle::Notched6;
+        bar.value = 100;
+        bar.max = 3;
+        assert_eq!(bar.progress(), 1.0);
+        assert_eq!(bar.progress_line(12), "Boss [██|██|██|██|██|██] 100/3");
+        bar.value = 1;
+        assert_eq!(bar.progress_line(5), "Boss [█|█|░|░|░|░] 1/3");
+    }
+}
@@ -0,0 +1,347 @@
+use crate::item::Inventory;
+use crate::nbt::Nbt;
+use crate::text;
+use crate::text::TextComponent;
+use crate::{Criteria, StatType};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// An entity UUID, shown in the usual hyphenated form.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone, Debug)]
+pub struct Uuid(pub u128);
+
+impl Uuid {
+    /// A version 4 UUID derived from `seed`, so games hand out the same UUIDs on every run.
+    pub fn generate(seed: u64) -> Uuid {
+        let high = splitmix64(seed.wrapping_mul(2)) as u128;
+        let low = splitmix64(seed.wrapping_mul(2).wrapping_add(1)) as u128;
+        let bits = (high << 64) | low;
+        let bits = (bits & !(0xF << 76)) | (0x4 << 76);
+        let bits = (bits & !(0x3 << 62)) | (0x2 << 62);
+        Uuid(bits)
+    }
+
+    pub fn parse(text: &str) -> Option<Uuid> {
+        let groups: Vec<&str> = text.split('-').collect();
+        let lengths = [8, 4, 4, 4, 12];
+        if groups.len() != lengths.len()
+            || groups
+                .iter()
+                .zip(lengths.iter())
+                .any(|(group, length)| group.len() != *length)
+        {
+            return None;
+        }
+        u128::from_str_radix(&groups.concat(), 16).ok().map(Uuid)
+    }
+
+    /// The UUID as the four big-endian integers vanilla stores in NBT.
+    pub fn to_int_array(self) -> [i32; 4] {
+        [
+            (self.0 >> 96) as i32,
+            (self.0 >> 64) as i32,
+            (self.0 >> 32) as i32,
+            self.0 as i32,
+        ]
+    }
+}
+
+impl Display for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let hex = format!("{:032x}", self.0);
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )
+    }
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Whatever a score is stored against. Players are keyed by name so their scores survive
+/// logging out, other entities by UUID, and fake holders are names no player could have,
+/// like `#temp` or `$counter`.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Debug)]
+pub enum ScoreHolder {
+    Player(String),
+    Entity(Uuid),
+    Fake(String),
+}
+
+impl ScoreHolder {
+    /// The holder a name written in a command refers to.
+    pub fn from_name(name: &str) -> ScoreHolder {
+        if let Some(uuid) = Uuid::parse(name) {
+            ScoreHolder::Entity(uuid)
+        } else if is_player_name(name) {
+            ScoreHolder::Player(String::from(name))
+        } else {
+            ScoreHolder::Fake(String::from(name))
+        }
+    }
+}
+
+impl Display for ScoreHolder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreHolder::Player(name) | ScoreHolder::Fake(name) => write!(f, "{}", name),
+            ScoreHolder::Entity(uuid) => write!(f, "{}", uuid),
+        }
+    }
+}
+
+fn is_player_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= 16
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Entity {
+    pub uuid: Uuid,
+    /// The namespaced entity type, like `minecraft:marker`.
+    pub kind: String,
+    pub player_name: Option<String>,
+    pub custom_name: Option<TextComponent>,
+    pub tags: BTreeSet<String>,
+    pub position: [f64; 3],
+    /// Yaw and pitch, in degrees.
+    pub rotation: [f32; 2],
+    pub dimension: String,
+    pub alive: bool,
+    pub inventory: Inventory,
+    pub health: f32,
+    /// The hunger bar, from 0 to 20.
+    pub food: i32,
+    /// Breath left underwater, in ticks, up to 300.
+    pub air: i32,
+    pub xp_level: i32,
+    /// All the experience points collected since the last death.
+    pub xp_total: i32,
+    /// The armor bar, from 0 to 20.
+    pub armor: i32,
+    /// Statistics by type and namespaced id, like `(Used, "minecraft:carrot_on_a_stick")`.
+    pub stats: HashMap<(StatType, String), i32>,
+}
+
+impl Entity {
+    pub fn new(uuid: Uuid, kind: &str) -> Entity {
+        Entity {
+            uuid,
+            kind: namespaced(kind),
+            player_name: None,
+            custom_name: None,
+            tags: BTreeSet::new(),
+            position: [0.0, 0.0, 0.0],
+            rotation: [0.0, 0.0],
+            dimension: String::from("minecraft:overworld"),
+            alive: true,
+            inventory: Inventory::new(),
+            health: 20.0,
+            food: 20,
+            air: 300,
+            xp_level: 0,
+            xp_total: 0,
+            armor: 0,
+            stats: HashMap::new(),
+        }
+    }
+
+    pub fn player(uuid: Uuid, name: &str) -> Entity {
+        Entity {
+            player_name: Some(String::from(name)),
+            ..Entity::new(uuid, "player")
+        }
+    }
+
+    pub fn is_player(&self) -> bool {
+        self.player_name.is_some()
+    }
+
+    pub fn score_holder(&self) -> ScoreHolder {
+        match &self.player_name {
+            Some(name) => ScoreHolder::Player(name.clone()),
+            None => ScoreHolder::Entity(self.uuid),
+        }
+    }
+
+    /// Applies the data tags `summon` understands: `Tags`, `CustomName` and `Rotation`.
+    pub fn apply_nbt(&mut self, nbt: &Nbt) {
+        if let Some(Nbt::List(tags)) = nbt.get("Tags") {
+            self.tags = tags
+                .iter()
+                .filter_map(Nbt::as_str)
+                .map(String::from)
+                .collect();
+        }
+        if let Some(name) = nbt.get("CustomName").and_then(Nbt::as_str) {
+            self.custom_name =
+                Some(text::parse(name).unwrap_or_else(|_| TextComponent::text(name)));
+        }
+        if let Some(Nbt::List(rotation)) = nbt.get("Rotation") {
+            if let [yaw, pitch] = rotation.as_slice() {
+                if let (Some(yaw), Some(pitch)) = (yaw.as_f64(), pitch.as_f64()) {
+                    self.rotation = [yaw as f32, pitch as f32];
+                }
+            }
+        }
+    }
+
+    pub fn stat(&self, stat_type: StatType, id: &str) -> i32 {
+        self.stats
+            .get(&(stat_type, namespaced(id)))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The value a read-only criterion mirrors for this player, like hearts for `health`.
+    pub fn criteria_value(&self, criteria: &Criteria) -> Option<i32> {
+        match criteria {
+            Criteria::Health => Some(self.health.ceil() as i32),
+            Criteria::Xp => Some(self.xp_total),
+            Criteria::Level => Some(self.xp_level),
+            Criteria::Food => Some(self.food),
+            Criteria::Air => Some(self.air),
+            Criteria::Armor => Some(self.armor),
+            _ => None,
+        }
+    }
+
+    /// Whether an item slot exists on this entity. Only players have container slots.
+    pub fn has_slot(&self, slot: i32) -> bool {
+        self.is_player() || !(0..36).contains(&slot)
+    }
+
+    /// How far above its feet the entity's eyes are, for `facing` and the `eyes` anchor.
+    pub fn eye_height(&self) -> f64 {
+        match self.kind.as_str() {
+            "minecraft:player" => 1.62,
+            "minecraft:armor_stand" => 1.7775,
+            _ => 0.0,
+        }
+    }
+
+    /// The name shown in chat and command feedback: the custom name if there is one, otherwise
+    /// the player name or the type's name.
+    pub fn display_name(&self) -> TextComponent {
+        match (&self.custom_name, &self.player_name) {
+            (Some(name), _) => name.clone(),
+            (None, Some(name)) => TextComponent::text(name),
+            (None, None) => TextComponent::text(&type_name(&self.kind)),
+        }
+    }
+}
+
+/// `minecraft:armor_stand` reads as `Armor Stand`.
+pub(crate) fn type_name(kind: &str) -> String {
+    let path = kind.rsplit(':').next().unwrap_or(kind);
+    path.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub(crate) fn namespaced(id: &str) -> String {
+    match id.contains(':') {
+        true => String::from(id),
+        false => format!("minecraft:{}", id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_round_trip() {
+        let uuid = Uuid(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+        assert_eq!(uuid.to_string(), "01234567-89ab-cdef-0123-456789abcdef");
+        assert_eq!(Uuid::parse(&uuid.to_string()), Some(uuid));
+        assert_eq!(Uuid::parse("0123456789abcdef0123456789abcdef"), None);
+        assert_eq!(Uuid::parse("01234567-89ab-cdef-0123-456789abcdeg"), None);
+    }
+
+    #[test]
+    fn generated_uuids_are_version_4() {
+        let uuid = Uuid::generate(1).to_string();
+        assert_eq!(&uuid[14..15], "4");
+        assert!("89ab".contains(&uuid[19..20]));
+        assert_eq!(Uuid::generate(1), Uuid::generate(1));
+        assert_ne!(Uuid::generate(1), Uuid::generate(2));
+    }
+
+    #[test]
+    fn uuid_int_array() {
+        let uuid = Uuid(0x0000_0001_ffff_ffff_0000_0002_0000_0003);
+        assert_eq!(uuid.to_int_array(), [1, -1, 2, 3]);
+    }
+
+    #[test]
+    fn score_holder_from_name() {
+        assert_eq!(
+            ScoreHolder::from_name("Alice_99"),
+            ScoreHolder::Player("Alice_99".to_string())
+        );
+        assert_eq!(
+            ScoreHolder::from_name("#temp"),
+            ScoreHolder::Fake("#temp".to_string())
+        );
+        assert_eq!(
+            ScoreHolder::from_name("$counter"),
+            ScoreHolder::Fake("$counter".to_string())
+        );
+        assert_eq!(
+            ScoreHolder::from_name("01234567-89ab-cdef-0123-456789abcdef"),
+            ScoreHolder::Entity(Uuid(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef))
+        );
+    }
+
+    #[test]
+    fn summon_nbt() {
+        let mut entity = Entity::new(Uuid(1), "marker");
+        entity.apply_nbt(
+            &crate::nbt::parse(
+                "{Tags:[\"a\",\"b\"],CustomName:'{\"text\":\"Flag\"}',Rotation:[90f,-10f]}",
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            entity.tags.iter().collect::<Vec<_>>(),
+            vec![&"a".to_string(), &"b".to_string()]
+        );
+        assert_eq!(entity.display_name().plain(), "Flag");
+        assert_eq!(entity.rotation, [90.0, -10.0]);
+        entity.apply_nbt(&crate::nbt::parse("{CustomName:\"plain\"}").unwrap());
+        assert_eq!(entity.display_name().plain(), "plain");
+    }
+
+    #[test]
+    fn display_names() {
+        let mut stand = Entity::new(Uuid(1), "armor_stand");
+        assert_eq!(stand.kind, "minecraft:armor_stand");
+        assert_eq!(stand.display_name().plain(), "Armor Stand");
+        stand.custom_name = Some(TextComponent::text("Bob"));
+        assert_eq!(stand.display_name().plain(), "Bob");
+        assert_eq!(
+            Entity::player(Uuid(2), "Alice").display_name().plain(),
+            "Alice"
+        );
+    }
+}
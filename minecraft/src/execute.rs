@@ -1,22 +1,60 @@
+use crate::advancement::{AdvancementNode, Frame};
+use crate::block::{Block, World};
+use crate::bossbar::CustomBossbar;
+use crate::entity::{namespaced, Entity, ScoreHolder, Uuid};
+use crate::gamerule::{GameRule, GameRules};
+use crate::item;
+use crate::item::{ItemPredicate, ItemStack};
+use crate::json::Json;
+use crate::nbt::{Nbt, Path};
+use crate::random::RandomSource;
+use crate::scoreboard;
+use crate::scoreboard::{Line, Panel, ScoreboardView};
+use crate::structure;
+use crate::structure::Structure;
+use crate::team::PlayerTeam;
+use crate::text::{Color, Content, NbtSource, TextComponent};
 use crate::*;
+use std::cell::RefCell;
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
 
 #[derive(PartialEq, Debug)]
 struct Objective {
-    display_name: String,
+    criteria: Criteria,
+    display_name: TextComponent,
     render_type: RenderType,
-    data: HashMap<String, i32>,
+    data: HashMap<ScoreHolder, i32>,
+    /// Holders who may use `trigger` on this objective once. Everyone else is locked out.
+    unlocked: HashSet<ScoreHolder>,
+    number_format: Option<NumberFormat>,
+    display_auto_update: bool,
+    score_displays: HashMap<ScoreHolder, ScoreDisplay>,
 }
 
-#[derive(PartialEq, Debug)]
-struct Player {
-    name: String,
+#[derive(PartialEq, Default, Debug)]
+struct ScoreDisplay {
+    name: Option<TextComponent>,
+    number_format: Option<NumberFormat>,
 }
 
+/// Vanilla refuses to give an entity more tags than this.
+const MAX_TAGS: usize = 1024;
+
+#[derive(Default)]
 pub struct Datapack {
     pub name: String,
     pub functions: Vec<Function>,
+    pub structures: HashMap<String, Structure>,
+    pub item_modifiers: HashMap<String, Json>,
+    pub predicates: HashMap<String, Json>,
+    pub advancements: HashMap<String, AdvancementNode>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -27,31 +65,340 @@ pub enum Level {
 
 pub trait Log {
     fn log(&mut self, level: Level, message: &str);
+
+    /// Receives what commands run by players and command blocks report, for the server log,
+    /// while `logAdminCommands` is on. `sender` is who ran the command.
+    fn log_admin(&mut self, _sender: &str, _message: &str) {}
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Origin {
+    Console,
+    Player(String),
+    CommandBlock,
+}
+
+/// The game's logger, noting whether the command running has failed so `execute store` can
+/// tell, and holding back feedback its sender would not see. Everything else about the logger
+/// is reached through it.
+#[derive(PartialEq, Debug)]
+struct Feedback<'a, T: Log> {
+    logger: &'a mut T,
+    failed: bool,
+    verbose: bool,
+    show_info: bool,
+    show_failures: bool,
+    admin_sender: Option<String>,
+}
+
+impl<'a, T: Log> Feedback<'a, T> {
+    fn new(logger: &'a mut T) -> Feedback<'a, T> {
+        Feedback {
+            logger,
+            failed: false,
+            verbose: false,
+            show_info: true,
+            show_failures: true,
+            admin_sender: None,
+        }
+    }
+
+    fn log(&mut self, level: Level, message: &str) {
+        let shown = match level {
+            Level::Info => {
+                if let Some(sender) = &self.admin_sender {
+                    self.logger.log_admin(sender, message);
+                }
+                self.show_info
+            }
+            Level::Fail => {
+                self.failed = true;
+                self.show_failures
+            }
+        };
+        if shown || self.verbose {
+            self.logger.log(level, message);
+        }
+    }
+}
+
+impl<'a, T: Log> Deref for Feedback<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.logger
+    }
+}
+
+impl<'a, T: Log> DerefMut for Feedback<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.logger
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Message {
+    pub component: TextComponent,
+    pub text: String,
+}
+
+impl Message {
+    pub fn new(component: TextComponent) -> Message {
+        let text = component.plain();
+        Message { component, text }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum Channel {
+    Chat,
+    Title,
+    Subtitle,
+    Actionbar,
+}
+
+impl Display for Channel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Channel::Chat => "chat",
+                Channel::Title => "title",
+                Channel::Subtitle => "subtitle",
+                Channel::Actionbar => "actionbar",
+            }
+        )
+    }
 }
 
 pub trait Chat {
-    fn tell(&mut self, players: Vec<String>, message: &str);
+    fn tell(&mut self, players: Vec<String>, channel: Channel, message: &Message);
+}
+
+#[derive(Debug, Default)]
+pub struct Inbox {
+    histories: HashMap<(String, Channel), Vec<Message>>,
+}
+
+impl Inbox {
+    pub fn new() -> Inbox {
+        Inbox::default()
+    }
+
+    pub fn messages(&self, player: &str) -> &[Message] {
+        self.received(player, Channel::Chat)
+    }
+
+    pub fn received(&self, player: &str, channel: Channel) -> &[Message] {
+        self.histories
+            .get(&(String::from(player), channel))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn texts(&self, player: &str) -> Vec<&str> {
+        self.messages(player)
+            .iter()
+            .map(|message| message.text.as_str())
+            .collect()
+    }
+
+    pub fn last(&self, player: &str) -> Option<&Message> {
+        self.messages(player).last()
+    }
+
+    pub fn clear(&mut self) {
+        self.histories.clear();
+    }
+}
+
+impl Chat for Inbox {
+    fn tell(&mut self, players: Vec<String>, channel: Channel, message: &Message) {
+        for player in players {
+            self.histories
+                .entry((player, channel))
+                .or_default()
+                .push(message.clone());
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Source {
+    Server,
+    Entity(Uuid),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ExecutionContext {
+    pub source: Source,
+    pub position: [f64; 3],
+    /// Yaw and pitch, in degrees.
+    pub rotation: [f32; 2],
+    pub dimension: String,
+}
+
+impl ExecutionContext {
+    /// The server runs commands at the world origin, facing south.
+    pub fn server() -> ExecutionContext {
+        ExecutionContext {
+            source: Source::Server,
+            position: [0.0, 0.0, 0.0],
+            rotation: [0.0, 0.0],
+            dimension: String::from("minecraft:overworld"),
+        }
+    }
+
+    pub fn of(entity: &Entity) -> ExecutionContext {
+        ExecutionContext {
+            source: Source::Entity(entity.uuid),
+            position: entity.position,
+            rotation: entity.rotation,
+            dimension: entity.dimension.clone(),
+        }
+    }
+
+    /// Absolute x and z written without a decimal point are moved to the centre of their block,
+    /// as vanilla does for entity positions.
+    pub fn resolve(&self, position: &Vec3) -> [f64; 3] {
+        match (position.x, position.y, position.z) {
+            (Coordinate::Local(left), Coordinate::Local(up), Coordinate::Local(forwards)) => {
+                let offset = local_offset(self.rotation, left, up, forwards);
+                [
+                    self.position[0] + offset[0],
+                    self.position[1] + offset[1],
+                    self.position[2] + offset[2],
+                ]
+            }
+            (x, y, z) => [
+                centred(x, self.position[0]),
+                world_axis(y, self.position[1]),
+                centred(z, self.position[2]),
+            ],
+        }
+    }
+
+    pub fn resolve_block_pos(&self, position: &BlockPos) -> [i32; 3] {
+        let [x, y, z] = self.resolve(&Vec3 {
+            x: position.x,
+            y: position.y,
+            z: position.z,
+        });
+        [x.floor() as i32, y.floor() as i32, z.floor() as i32]
+    }
+
+    pub fn resolve_vec2(&self, position: &Vec2) -> [f64; 2] {
+        [
+            world_axis(position.x, self.position[0]),
+            world_axis(position.z, self.position[2]),
+        ]
+    }
+}
+
+fn world_axis(coordinate: Coordinate, origin: f64) -> f64 {
+    match coordinate {
+        Coordinate::Absolute(value, _) => value,
+        Coordinate::Relative(offset) | Coordinate::Local(offset) => origin + offset,
+    }
+}
+
+fn centred(coordinate: Coordinate, origin: f64) -> f64 {
+    match coordinate {
+        Coordinate::Absolute(value, false) => value + 0.5,
+        coordinate => world_axis(coordinate, origin),
+    }
+}
+
+/// The world-space offset of `^left ^up ^forwards` for something facing `rotation`, computed
+/// the way vanilla does: forwards follows the view direction, up is perpendicular to it and
+/// left completes the basis.
+fn local_offset(rotation: [f32; 2], left: f64, up: f64, forwards: f64) -> [f64; 3] {
+    let [yaw, pitch] = [rotation[0] as f64, rotation[1] as f64];
+    let (yaw_cos, yaw_sin) = (
+        (yaw + 90.0).to_radians().cos(),
+        (yaw + 90.0).to_radians().sin(),
+    );
+    let (pitch_cos, pitch_sin) = ((-pitch).to_radians().cos(), (-pitch).to_radians().sin());
+    let (up_cos, up_sin) = (
+        (-pitch + 90.0).to_radians().cos(),
+        (-pitch + 90.0).to_radians().sin(),
+    );
+    let forwards_axis = [yaw_cos * pitch_cos, pitch_sin, yaw_sin * pitch_cos];
+    let up_axis = [yaw_cos * up_cos, up_sin, yaw_sin * up_cos];
+    let left_axis = [
+        -(forwards_axis[1] * up_axis[2] - forwards_axis[2] * up_axis[1]),
+        -(forwards_axis[2] * up_axis[0] - forwards_axis[0] * up_axis[2]),
+        -(forwards_axis[0] * up_axis[1] - forwards_axis[1] * up_axis[0]),
+    ];
+    let mut offset = [0.0; 3];
+    for (i, axis) in offset.iter_mut().enumerate() {
+        *axis = forwards_axis[i] * forwards + up_axis[i] * up + left_axis[i] * left;
+    }
+    offset
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct TitleTimes {
+    pub fade_in: i32,
+    pub stay: i32,
+    pub fade_out: i32,
+}
+
+impl Default for TitleTimes {
+    fn default() -> TitleTimes {
+        TitleTimes {
+            fade_in: 10,
+            stay: 70,
+            fade_out: 20,
+        }
+    }
 }
 
 pub struct Game<'a, T: Log, S: Chat> {
     objectives: HashMap<String, Objective>,
     displays: HashMap<DisplaySlot, Option<String>>,
-    players: HashMap<String, Player>,
+    teams: BTreeMap<String, PlayerTeam>,
+    entities: Vec<Entity>,
+    /// Players who logged out, by name, as they were when they left.
+    offline: HashMap<String, Entity>,
+    /// The values read-only criteria last saw for each player.
+    recorded: HashMap<(Uuid, Criteria), i32>,
+    uuid_seed: u64,
+    world: World,
+    storage: HashMap<String, Nbt>,
+    title_times: HashMap<String, TitleTimes>,
+    context: ExecutionContext,
+    structure_directory: Option<PathBuf>,
+    bossbars: BTreeMap<String, CustomBossbar>,
+    gamerules: GameRules,
+    game_time: i64,
+    day_time: i64,
+    weather: WeatherKind,
+    weather_duration: Option<i32>,
+    difficulty: Difficulty,
+    seed: i64,
+    /// The world's randomness. Selectors draw from it while only reading the game.
+    random: RefCell<RandomSource>,
+    sequences: HashMap<String, RandomSource>,
+    /// How `random reset *` said to seed sequences: a salt, and whether the world seed and the
+    /// sequence's id go in.
+    sequence_defaults: (i64, bool, bool),
+    advancements: HashMap<String, BTreeMap<String, BTreeSet<String>>>,
+    origin: Origin,
+    /// Commands run so far by the outermost function running, which `maxCommandChainLength`
+    /// limits.
+    chain_length: Option<i32>,
+    /// What the command running returned, for `execute store`, if it returns more than success.
+    result: Option<i32>,
     datapack: &'a Option<Datapack>,
-    logger: &'a mut T,
+    logger: Feedback<'a, T>,
     chat: &'a mut S,
 }
 
 impl<'a, T: Log, S: Chat> Game<'a, T, S> {
     pub fn new(logger: &'a mut T, chat: &'a mut S) -> Game<'a, T, S> {
-        Game {
-            objectives: HashMap::new(),
-            displays: HashMap::new(),
-            players: HashMap::new(),
-            datapack: &None,
-            logger,
-            chat,
-        }
+        Game::from(logger, chat, &None)
     }
 
     pub fn from(
@@ -62,29 +409,550 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
         Game {
             objectives: HashMap::new(),
             displays: HashMap::new(),
-            players: HashMap::new(),
+            teams: BTreeMap::new(),
+            entities: Vec::new(),
+            offline: HashMap::new(),
+            recorded: HashMap::new(),
+            uuid_seed: 0,
+            world: World::new(),
+            storage: HashMap::new(),
+            title_times: HashMap::new(),
+            context: ExecutionContext::server(),
+            structure_directory: None,
+            bossbars: BTreeMap::new(),
+            gamerules: GameRules::new(),
+            game_time: 0,
+            day_time: 0,
+            weather: WeatherKind::Clear,
+            weather_duration: None,
+            difficulty: Difficulty::Easy,
+            seed: 0,
+            random: RefCell::new(RandomSource::new(0)),
+            sequences: HashMap::new(),
+            sequence_defaults: (0, true, true),
+            advancements: HashMap::new(),
+            origin: Origin::Console,
+            chain_length: None,
+            result: None,
             datapack,
-            logger,
+            logger: Feedback::new(logger),
             chat,
         }
     }
 
-    pub fn add_player(&mut self, name: &str) {
-        self.players.insert(
-            String::from(name),
-            Player {
-                name: String::from(name),
-            },
-        );
+    /// Brings a player online, or returns the UUID they already have. Players who logged out
+    /// come back as they left.
+    pub fn add_player(&mut self, name: &str) -> Uuid {
+        if let Some(player) = self.find_player(name) {
+            return player.uuid;
+        }
+        let player = match self.offline.remove(name) {
+            Some(player) => player,
+            None => Entity::player(self.next_uuid(), name),
+        };
+        let uuid = player.uuid;
+        self.entities.push(player);
+        uuid
+    }
+
+    pub fn spawn(&mut self, kind: &str) -> Uuid {
+        let uuid = self.next_uuid();
+        self.entities.push(Entity::new(uuid, kind));
+        uuid
+    }
+
+    pub fn entity(&self, uuid: Uuid) -> Option<&Entity> {
+        self.entities.iter().find(|e| e.uuid == uuid && e.alive)
+    }
+
+    pub fn entity_mut(&mut self, uuid: Uuid) -> Option<&mut Entity> {
+        self.entities.iter_mut().find(|e| e.uuid == uuid && e.alive)
+    }
+
+    pub fn find_player(&self, name: &str) -> Option<&Entity> {
+        self.entities
+            .iter()
+            .find(|e| e.alive && e.player_name.as_deref() == Some(name))
+    }
+
+    pub fn score(&self, holder: &ScoreHolder, objective: &str) -> Option<i32> {
+        self.objectives
+            .get(objective)
+            .and_then(|o| o.data.get(holder))
+            .copied()
+    }
+
+    /// What a client sees of the scoreboard. A `viewer` on a colored team sees that color's
+    /// sidebar instead of the usual one, when there is one.
+    pub fn scoreboard_view(&self, viewer: Option<&str>) -> ScoreboardView {
+        let sidebar = viewer
+            .and_then(|name| self.team_color(&ScoreHolder::Player(String::from(name))))
+            .and_then(|color| self.displayed(DisplaySlot::SidebarTeam(color)))
+            .or_else(|| self.displayed(DisplaySlot::Sidebar));
+        ScoreboardView {
+            sidebar: sidebar.map(|objective| self.sidebar_panel(objective)),
+            list: self
+                .displayed(DisplaySlot::List)
+                .map(|objective| self.list_panel(objective)),
+            below_name: self
+                .displayed(DisplaySlot::BelowName)
+                .map(|objective| self.below_name_panel(objective)),
+        }
+    }
+
+    fn displayed(&self, slot: DisplaySlot) -> Option<&Objective> {
+        self.displays
+            .get(&slot)
+            .and_then(|name| name.as_ref())
+            .and_then(|name| self.objectives.get(name))
+    }
+
+    /// The highest scores, then names in order among equal ones. Holders whose names start with
+    /// `#` are kept off the sidebar.
+    fn sidebar_panel(&self, objective: &Objective) -> Panel {
+        let mut scores: Vec<(String, &ScoreHolder, i32)> = objective
+            .data
+            .iter()
+            .map(|(holder, score)| (holder.to_string(), holder, *score))
+            .filter(|(name, _, _)| !name.starts_with('#'))
+            .collect();
+        scores.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+        Panel {
+            title: objective.display_name.clone(),
+            lines: scores
+                .into_iter()
+                .take(scoreboard::SIDEBAR_LINES)
+                .map(|(_, holder, score)| {
+                    Line::new(
+                        self.score_name(objective, holder),
+                        self.formatted_score(objective, holder, score, Some(Color::Red)),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn list_panel(&self, objective: &Objective) -> Panel {
+        let lines = self
+            .online_players()
+            .into_iter()
+            .map(|(name, holder)| {
+                let score = match (objective.data.get(&holder), objective.render_type) {
+                    (Some(score), RenderType::Integer) => {
+                        self.formatted_score(objective, &holder, *score, Some(Color::Yellow))
+                    }
+                    (Some(score), RenderType::Hearts) => scoreboard::hearts(*score),
+                    (None, _) => TextComponent::text(""),
+                };
+                Line::new(
+                    self.formatted_holder_name(&holder, TextComponent::text(&name)),
+                    score,
+                )
+            })
+            .collect();
+        Panel {
+            title: TextComponent::text("Players"),
+            lines,
+        }
+    }
+
+    fn below_name_panel(&self, objective: &Objective) -> Panel {
+        let lines = self
+            .online_players()
+            .into_iter()
+            .filter_map(|(name, holder)| {
+                let score = objective.data.get(&holder)?;
+                let mut below = self.formatted_score(objective, &holder, *score, None);
+                below.extra.push(TextComponent::text(" "));
+                below.extra.push(objective.display_name.clone());
+                Some(Line::new(
+                    self.formatted_holder_name(&holder, TextComponent::text(&name)),
+                    below,
+                ))
+            })
+            .collect();
+        Panel {
+            title: objective.display_name.clone(),
+            lines,
+        }
+    }
+
+    /// The name a score is listed under: an entity's current name if the objective updates
+    /// them, else any name given with `scoreboard players display name`, else the holder's
+    /// scoreboard name.
+    fn score_name(&self, objective: &Objective, holder: &ScoreHolder) -> TextComponent {
+        let name = match holder {
+            ScoreHolder::Entity(uuid) if objective.display_auto_update => {
+                self.entity(*uuid).map(Entity::display_name)
+            }
+            _ => None,
+        }
+        .or_else(|| {
+            objective
+                .score_displays
+                .get(holder)
+                .and_then(|display| display.name.clone())
+        })
+        .unwrap_or_else(|| TextComponent::text(&holder.to_string()));
+        self.formatted_holder_name(holder, name)
+    }
+
+    fn formatted_score(
+        &self,
+        objective: &Objective,
+        holder: &ScoreHolder,
+        score: i32,
+        color: Option<Color>,
+    ) -> TextComponent {
+        let format = objective
+            .score_displays
+            .get(holder)
+            .and_then(|display| display.number_format.as_ref())
+            .or(objective.number_format.as_ref());
+        scoreboard::format_score(score, format, color)
+    }
+
+    fn online_players(&self) -> Vec<(String, ScoreHolder)> {
+        let mut players: Vec<(String, ScoreHolder)> = self
+            .entities
+            .iter()
+            .filter(|e| e.alive)
+            .filter_map(|e| e.player_name.clone())
+            .map(|name| (name.clone(), ScoreHolder::Player(name)))
+            .collect();
+        players.sort();
+        players
+    }
+
+    fn formatted_holder_name(&self, holder: &ScoreHolder, name: TextComponent) -> TextComponent {
+        match self.team_of(holder) {
+            Some(team) => self.teams[team].format_name(name),
+            None => name,
+        }
+    }
+
+    /// Looks for structure templates in `directory` too, laid out like a world's `generated`
+    /// folder: `<namespace>/structures/<path>.nbt`. These win over the datapack's.
+    pub fn set_structure_directory(&mut self, directory: PathBuf) {
+        self.structure_directory = Some(directory);
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    fn next_uuid(&mut self) -> Uuid {
+        self.uuid_seed += 1;
+        Uuid::generate(self.uuid_seed)
+    }
+
+    pub fn set_storage(&mut self, id: &str, data: Nbt) {
+        self.storage.insert(namespaced(id), data);
     }
 
     pub fn execute(&mut self, command: &Command) {
+        self.route_feedback();
+        self.record_player_values();
         match command {
             Command::Scoreboard(s) => self.execute_scoreboard(s),
             Command::Function(f) => self.execute_function(f),
             Command::Execute(e) => self.execute_execute(e),
             Command::Tellraw(t) => self.execute_tellraw(t),
+            Command::Say(s) => self.execute_say(s),
+            Command::Tell(t) => self.execute_tell(t),
+            Command::Me(m) => self.execute_me(m),
+            Command::Title(t) => self.execute_title(t),
+            Command::Summon(s) => self.execute_summon(s),
+            Command::Kill(k) => self.execute_kill(k),
+            Command::Teleport(t) => self.execute_teleport(t),
+            Command::SetBlock(s) => self.execute_setblock(s),
+            Command::Fill(f) => self.execute_fill(f),
+            Command::Clone(c) => self.execute_clone(c),
+            Command::Place(Place::Template(p)) => self.execute_place_template(p),
+            Command::Give(g) => self.execute_give(g),
+            Command::Trigger(t) => self.execute_trigger(t),
+            Command::Team(t) => self.execute_team(t),
+            Command::Bossbar(b) => self.execute_bossbar(b),
+            Command::Gamerule(g) => self.execute_gamerule(g),
+            Command::Time(t) => self.execute_time(t),
+            Command::Weather(w) => self.execute_weather(w),
+            Command::Difficulty(d) => self.execute_difficulty(*d),
+            Command::Random(r) => self.execute_random(r),
+            Command::Advancement(a) => self.execute_advancement(a),
+            Command::Clear(c) => self.execute_clear(c),
+            Command::Item(Item::Replace(r)) => self.execute_item_replace(r),
+            Command::Item(Item::Modify(m)) => self.execute_item_modify(m),
+            Command::Tag(t) => self.execute_tag(t),
+        }
+    }
+
+    /// Brings objectives with read-only criteria up to date with the players they mirror, the way
+    /// vanilla does every tick. Like vanilla, only values that changed since they were last
+    /// recorded are written, so a new objective stays empty until something changes.
+    fn record_player_values(&mut self) {
+        let mut changes = Vec::new();
+        for player in self.entities.iter().filter(|e| e.alive && e.is_player()) {
+            for criteria in &Criteria::READ_ONLY {
+                if let Some(value) = player.criteria_value(criteria) {
+                    let key = (player.uuid, criteria.clone());
+                    if self.recorded.insert(key, value) != Some(value) {
+                        changes.push((player.score_holder(), criteria, value));
+                    }
+                }
+            }
+        }
+        for (holder, criteria, value) in changes {
+            for objective in self.objectives.values_mut() {
+                if objective.criteria == *criteria {
+                    objective.data.insert(holder.clone(), value);
+                }
+            }
+        }
+    }
+
+    pub fn execute_as(&mut self, player: &str, command: &Command) {
+        match self.find_player(player).map(ExecutionContext::of) {
+            Some(context) => {
+                let previous = std::mem::replace(&mut self.origin, Origin::Player(player.into()));
+                self.execute_in(context, command);
+                self.origin = previous;
+            }
+            None => self.logger.log(Level::Fail, "No player was found"),
+        }
+    }
+
+    pub fn execute_from_command_block(&mut self, command: &Command) {
+        let previous = std::mem::replace(&mut self.origin, Origin::CommandBlock);
+        self.execute(command);
+        self.origin = previous;
+    }
+
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.logger.verbose = verbose;
+    }
+
+    /// Decides who sees feedback from here on. Functions run silently; players see successes
+    /// only with `sendCommandFeedback`; command blocks report only with `commandBlockOutput`.
+    /// What players and command blocks do is also logged with `logAdminCommands`.
+    fn route_feedback(&mut self) {
+        let rules = &self.gamerules;
+        let (show_info, show_failures, sender) = match &self.origin {
+            _ if self.chain_length.is_some() => (false, false, None),
+            Origin::Console => (true, true, None),
+            Origin::Player(name) => (
+                rules.bool(GameRule::SendCommandFeedback),
+                true,
+                Some(name.clone()),
+            ),
+            Origin::CommandBlock => {
+                let output = rules.bool(GameRule::CommandBlockOutput);
+                (output, output, Some(String::from("@")).filter(|_| output))
+            }
+        };
+        self.logger.show_info = show_info;
+        self.logger.show_failures = show_failures;
+        self.logger.admin_sender = sender.filter(|_| rules.bool(GameRule::LogAdminCommands));
+    }
+
+    pub fn execute_in(&mut self, context: ExecutionContext, command: &Command) {
+        let previous = std::mem::replace(&mut self.context, context);
+        self.execute(command);
+        self.context = previous;
+    }
+
+    pub fn player_chat(&mut self, player: &str, text: &str) {
+        let component = TextComponent::translatable(
+            "chat.type.text",
+            "<%s> %s",
+            vec![TextComponent::text(player), TextComponent::text(text)],
+        );
+        self.broadcast(Message::new(component));
+    }
+
+    pub fn title_times(&self, player: &str) -> TitleTimes {
+        self.title_times.get(player).copied().unwrap_or_default()
+    }
+
+    pub fn player_joins(&mut self, name: &str) -> Uuid {
+        if let Some(player) = self.find_player(name) {
+            return player.uuid;
+        }
+        let uuid = self.add_player(name);
+        self.announce("multiplayer.player.joined", "%s joined the game", name);
+        uuid
+    }
+
+    /// A player logs out. Their scores stay, since those are kept by name.
+    pub fn player_leaves(&mut self, name: &str) {
+        let uuid = match self.find_player(name) {
+            Some(player) => player.uuid,
+            None => return,
+        };
+        self.increment_stat(name, StatType::Custom, "leave_game", 1);
+        if let Some(index) = self.entities.iter().position(|e| e.uuid == uuid) {
+            let player = self.entities.remove(index);
+            self.offline.insert(String::from(name), player);
+        }
+        self.recorded.retain(|(recorded, _), _| *recorded != uuid);
+        self.announce("multiplayer.player.left", "%s left the game", name);
+    }
+
+    pub fn player_dies(&mut self, name: &str, killer: Option<Uuid>) {
+        let uuid = match self.find_player(name) {
+            Some(player) => player.uuid,
+            None => return,
+        };
+        let victim = TextComponent::text(name);
+        let message = match killer.and_then(|killer| self.entity(killer)) {
+            Some(killer) if killer.is_player() => TextComponent::translatable(
+                "death.attack.player",
+                "%s was slain by %s",
+                vec![victim, killer.display_name()],
+            ),
+            Some(killer) => TextComponent::translatable(
+                "death.attack.mob",
+                "%s was slain by %s",
+                vec![victim, killer.display_name()],
+            ),
+            None => TextComponent::translatable("death.attack.generic", "%s died", vec![victim]),
+        };
+        self.die(uuid, killer, message);
+    }
+
+    pub fn use_item(&mut self, name: &str, item: &str) {
+        self.increment_stat(name, StatType::Used, item, 1);
+        if let Some(player) = self.find_player(name).map(|player| player.uuid) {
+            self.trigger(player, "minecraft:using_item", |_, _, conditions| {
+                item_matches(conditions.get("item"), item)
+            });
+        }
+    }
+
+    pub fn kill_entity(&mut self, name: &str, victim: Uuid) {
+        let killer = match self.find_player(name) {
+            Some(player) => player.uuid,
+            None => return,
+        };
+        match self.entity(victim).map(|e| e.player_name.clone()) {
+            Some(Some(victim)) => self.player_dies(&victim, Some(killer)),
+            Some(None) => {
+                self.credit_kill(killer, victim);
+                self.remove_entity(victim);
+            }
+            None => {}
+        }
+    }
+
+    pub fn increment_stat(&mut self, name: &str, stat_type: StatType, id: &str, amount: i32) {
+        let player = match self
+            .entities
+            .iter_mut()
+            .find(|e| e.alive && e.player_name.as_deref() == Some(name))
+        {
+            Some(player) => player,
+            None => return,
+        };
+        let stat = player.stats.entry((stat_type, namespaced(id))).or_insert(0);
+        *stat = stat.wrapping_add(amount);
+        let holder = player.score_holder();
+        self.add_to_criteria(
+            &holder,
+            &Criteria::Statistic(stat_type, namespaced(id)),
+            amount,
+        );
+    }
+
+    fn add_to_criteria(&mut self, holder: &ScoreHolder, criteria: &Criteria, amount: i32) {
+        for objective in self.objectives.values_mut() {
+            if objective.criteria == *criteria {
+                let score = objective.data.entry(holder.clone()).or_insert(0);
+                *score = score.wrapping_add(amount);
+            }
+        }
+    }
+
+    fn announce(&mut self, key: &str, pattern: &str, name: &str) {
+        let mut component =
+            TextComponent::translatable(key, pattern, vec![TextComponent::text(name)]);
+        component.style.color = Some(Color::Yellow);
+        self.broadcast(Message::new(component));
+    }
+
+    /// Counts a death for the player `victim` and the kill for `killer`, tells everyone with
+    /// `message` and respawns the player at the world origin with nothing on them.
+    fn die(&mut self, victim: Uuid, killer: Option<Uuid>, message: TextComponent) {
+        let (name, holder) = match self.entity(victim) {
+            Some(Entity {
+                player_name: Some(name),
+                ..
+            }) => (name.clone(), ScoreHolder::Player(name.clone())),
+            _ => return,
+        };
+        if self.gamerules.bool(GameRule::ShowDeathMessages) {
+            self.broadcast(Message::new(message));
+        }
+        self.add_to_criteria(&holder, &Criteria::DeathCount, 1);
+        self.increment_stat(&name, StatType::Custom, "deaths", 1);
+        if let Some(killer) = killer.and_then(|killer| self.entity(killer)) {
+            let kind = killer.kind.clone();
+            let killer = killer.uuid;
+            self.increment_stat(&name, StatType::KilledBy, &kind, 1);
+            self.credit_kill(killer, victim);
+        }
+        let keep_inventory = self.gamerules.bool(GameRule::KeepInventory);
+        if let Some(player) = self.entity_mut(victim) {
+            let mut respawned = Entity {
+                stats: std::mem::take(&mut player.stats),
+                tags: std::mem::take(&mut player.tags),
+                ..Entity::player(player.uuid, &name)
+            };
+            if keep_inventory {
+                respawned.inventory = std::mem::take(&mut player.inventory);
+                respawned.xp_level = player.xp_level;
+                respawned.xp_total = player.xp_total;
+            }
+            *player = respawned;
+        }
+    }
+
+    fn credit_kill(&mut self, killer: Uuid, victim: Uuid) {
+        let (name, holder) = match self.entity(killer) {
+            Some(Entity {
+                player_name: Some(name),
+                ..
+            }) => (name.clone(), ScoreHolder::Player(name.clone())),
+            _ => return,
+        };
+        let victim = match self.entity(victim) {
+            Some(victim) => victim.clone(),
+            None => return,
+        };
+        let (victim_holder, player) = (victim.score_holder(), victim.is_player());
+        self.add_to_criteria(&holder, &Criteria::TotalKillCount, 1);
+        self.increment_stat(&name, StatType::Killed, &victim.kind, 1);
+        if let Some(color) = self.team_color(&victim_holder) {
+            self.add_to_criteria(&holder, &Criteria::TeamKill(color), 1);
+        }
+        if let Some(color) = self.team_color(&holder).filter(|_| player) {
+            self.add_to_criteria(&victim_holder, &Criteria::KilledByTeam(color), 1);
         }
+        if player {
+            self.add_to_criteria(&holder, &Criteria::PlayerKillCount, 1);
+            self.increment_stat(&name, StatType::Custom, "player_kills", 1);
+        } else {
+            self.increment_stat(&name, StatType::Custom, "mob_kills", 1);
+        }
+        self.trigger(
+            killer,
+            "minecraft:player_killed_entity",
+            |game, player, conditions| {
+                game.entity_condition(conditions.get("entity"), &victim, player.position)
+            },
+        );
     }
 
     fn execute_scoreboard(&mut self, scoreboard: &Scoreboard) {
@@ -118,24 +986,38 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
             None => {
                 let display_name = condense_display_name(
                     &objectives_add.objective,
-                    objectives_add.display_name.as_ref().map(String::as_ref),
+                    objectives_add.display_name.as_ref(),
                 );
-                self.add_objective(&objectives_add.objective, &display_name);
                 self.logger.log(
                     Level::Info,
-                    &format!("Created new objective [{}]", display_name),
-                )
+                    &format!("Created new objective [{}]", display_name.plain()),
+                );
+                self.add_objective(
+                    &objectives_add.objective,
+                    objectives_add.criteria.clone(),
+                    display_name,
+                );
             }
         }
     }
 
-    fn add_objective(&mut self, objective_name: &str, display_name: &str) {
+    fn add_objective(
+        &mut self,
+        objective_name: &str,
+        criteria: Criteria,
+        display_name: TextComponent,
+    ) {
         self.objectives.insert(
             String::from(objective_name),
             Objective {
-                display_name: String::from(display_name),
-                render_type: RenderType::Integer,
+                render_type: criteria.default_render_type(),
+                criteria,
+                display_name,
                 data: HashMap::new(),
+                unlocked: HashSet::new(),
+                number_format: None,
+                display_auto_update: false,
+                score_displays: HashMap::new(),
             },
         );
     }
@@ -148,7 +1030,7 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
                 &format!(
                     "There are {} objectives:{}",
                     n,
-                    space_separate(self.objectives.values().map(|o| &o.display_name))
+                    space_separate(self.objectives.values().map(|o| o.display_name.plain()))
                 ),
             ),
         }
@@ -159,31 +1041,40 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
             Modification::DisplayName(new_display_name) => self
                 .execute_objectives_modify_display_name(
                     &objective_modify.objective,
-                    &new_display_name,
+                    new_display_name,
                 ),
             Modification::RenderType(new_render_type) => self
                 .execute_objectives_modify_render_type(
                     &objective_modify.objective,
                     *new_render_type,
                 ),
+            Modification::DisplayAutoUpdate(update) => self
+                .execute_objectives_modify_display_auto_update(
+                    &objective_modify.objective,
+                    *update,
+                ),
+            Modification::NumberFormat(format) => {
+                self.execute_objectives_modify_number_format(&objective_modify.objective, format)
+            }
         }
     }
 
-    fn execute_objectives_modify_display_name(
+    fn execute_objectives_modify_display_auto_update(
         &mut self,
         objective_name: &str,
-        new_display_name: &str,
+        update: bool,
     ) {
-        match &mut self.objectives.get_mut(objective_name) {
+        match self.objectives.get_mut(objective_name) {
             Some(objective) => {
-                if objective.display_name != new_display_name {
-                    objective.display_name = String::from(new_display_name);
+                if objective.display_auto_update != update {
+                    objective.display_auto_update = update;
+                    let feedback = match update {
+                        true => "Enabled display auto-update for objective",
+                        false => "Disabled display auto-update for objective",
+                    };
                     self.logger.log(
                         Level::Info,
-                        &format!(
-                            "Changed objective {} display name to [{}]",
-                            objective_name, new_display_name
-                        ),
+                        &format!("{} [{}]", feedback, objective.display_name.plain()),
                     );
                 }
             }
@@ -194,21 +1085,71 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
         }
     }
 
-    fn execute_objectives_modify_render_type(
+    fn execute_objectives_modify_number_format(
         &mut self,
         objective_name: &str,
-        new_render_type: RenderType,
+        format: &Option<NumberFormat>,
     ) {
-        match &mut self.objectives.get_mut(objective_name) {
+        match self.objectives.get_mut(objective_name) {
             Some(objective) => {
-                if objective.render_type != new_render_type {
-                    objective.render_type = new_render_type;
-                    self.logger.log(
-                        Level::Info,
-                        &format!(
-                            "Changed objective [{}] render type",
-                            &objective.display_name
-                        ),
+                objective.number_format = format.clone();
+                let feedback = match format {
+                    Some(_) => "Changed default number format for objective",
+                    None => "Cleared default number format for objective",
+                };
+                self.logger.log(
+                    Level::Info,
+                    &format!("{} [{}]", feedback, objective.display_name.plain()),
+                );
+            }
+            None => self.logger.log(
+                Level::Fail,
+                &format!("Unknown scoreboard objective '{}'", objective_name),
+            ),
+        }
+    }
+
+    fn execute_objectives_modify_display_name(
+        &mut self,
+        objective_name: &str,
+        new_display_name: &TextComponent,
+    ) {
+        match &mut self.objectives.get_mut(objective_name) {
+            Some(objective) => {
+                if objective.display_name != *new_display_name {
+                    objective.display_name = new_display_name.clone();
+                    self.logger.log(
+                        Level::Info,
+                        &format!(
+                            "Changed objective {} display name to [{}]",
+                            objective_name,
+                            new_display_name.plain()
+                        ),
+                    );
+                }
+            }
+            None => self.logger.log(
+                Level::Fail,
+                &format!("Unknown scoreboard objective '{}'", objective_name),
+            ),
+        }
+    }
+
+    fn execute_objectives_modify_render_type(
+        &mut self,
+        objective_name: &str,
+        new_render_type: RenderType,
+    ) {
+        match &mut self.objectives.get_mut(objective_name) {
+            Some(objective) => {
+                if objective.render_type != new_render_type {
+                    objective.render_type = new_render_type;
+                    self.logger.log(
+                        Level::Info,
+                        &format!(
+                            "Changed objective [{}] render type",
+                            objective.display_name.plain()
+                        ),
                     );
                 }
             }
@@ -223,7 +1164,7 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
         match self.objectives.remove(&objectives_remove.objective) {
             Some(objective) => self.logger.log(
                 Level::Info,
-                &format!("Removed objective [{}]", &objective.display_name),
+                &format!("Removed objective [{}]", objective.display_name.plain()),
             ),
             None => self.logger.log(
                 Level::Fail,
@@ -255,7 +1196,8 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
                         Level::Info,
                         &format!(
                             "Set display slot {} to show objective {}",
-                            objectives_set_display.slot, &objective.display_name
+                            objectives_set_display.slot,
+                            objective.display_name.plain()
                         ),
                     )
                 }
@@ -276,40 +1218,62 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
             Players::Remove(r) => self.execute_players_remove(r),
             Players::Set(s) => self.execute_players_set(s),
             Players::Operation(o) => self.execute_players_operation(o),
+            Players::Display(d) => self.execute_players_display(d),
+            Players::Enable(e) => self.execute_players_enable(e),
+            Players::Get(g) => self.execute_players_get(g),
             _ => {}
         }
     }
 
+    fn reject_read_only(&mut self, objective_name: &str) -> bool {
+        let read_only = self
+            .objectives
+            .get(objective_name)
+            .is_some_and(|o| o.criteria.is_read_only());
+        if read_only {
+            self.logger.log(
+                Level::Fail,
+                &format!("Scoreboard objective '{}' is read-only", objective_name),
+            );
+        }
+        read_only
+    }
+
     fn execute_players_add(&mut self, players_add: &PlayersAdd) {
-        match &players_add.targets {
-            Target::Name(name) => {
-                self.execute_players_add_from_name(&name, &players_add.objective, players_add.score)
-            }
-            _ => {}
+        if self.reject_read_only(&players_add.objective) {
+            return;
+        }
+        let holders = match self.found_score_holders(&players_add.targets) {
+            Some(holders) => holders,
+            None => return,
+        };
+        for holder in holders {
+            self.execute_players_add_from_name(&holder, &players_add.objective, players_add.score)
         }
     }
 
     fn execute_players_add_from_name(
         &mut self,
-        player_name: &str,
+        holder: &ScoreHolder,
         objective_name: &str,
         score: i32,
     ) {
+        let holder_name = self.holder_name(holder);
         match &mut self.objectives.get_mut(objective_name) {
             Some(objective) => {
                 objective
                     .data
-                    .entry(String::from(player_name))
-                    .and_modify(|e| *e = (*e).overflowing_add(score as i32).0)
-                    .or_insert(score as i32);
+                    .entry(holder.clone())
+                    .and_modify(|e| *e = (*e).overflowing_add(score).0)
+                    .or_insert(score);
                 self.logger.log(
                     Level::Info,
                     &format!(
                         "Added {} to [{}] for {} (now {})",
                         score,
-                        objective.display_name,
-                        player_name,
-                        objective.data.get(player_name).unwrap()
+                        objective.display_name.plain(),
+                        holder_name,
+                        objective.data.get(holder).unwrap()
                     ),
                 );
             }
@@ -321,37 +1285,44 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
     }
 
     fn execute_players_remove(&mut self, players_remove: &PlayersRemove) {
-        match &players_remove.targets {
-            Target::Name(name) => self.execute_players_remove_from_name(
-                &name,
+        if self.reject_read_only(&players_remove.objective) {
+            return;
+        }
+        let holders = match self.found_score_holders(&players_remove.targets) {
+            Some(holders) => holders,
+            None => return,
+        };
+        for holder in holders {
+            self.execute_players_remove_from_name(
+                &holder,
                 &players_remove.objective,
                 players_remove.score,
-            ),
-            _ => {}
+            )
         }
     }
 
     fn execute_players_remove_from_name(
         &mut self,
-        player_name: &str,
+        holder: &ScoreHolder,
         objective_name: &str,
         score: i32,
     ) {
+        let holder_name = self.holder_name(holder);
         match &mut self.objectives.get_mut(objective_name) {
             Some(objective) => {
                 objective
                     .data
-                    .entry(String::from(player_name))
-                    .and_modify(|e| *e = (*e).overflowing_sub(score as i32).0)
-                    .or_insert(-(score as i32));
+                    .entry(holder.clone())
+                    .and_modify(|e| *e = (*e).overflowing_sub(score).0)
+                    .or_insert(-score);
                 self.logger.log(
                     Level::Info,
                     &format!(
                         "Removed {} from [{}] for {} (now {})",
                         score,
-                        objective.display_name,
-                        player_name,
-                        objective.data.get(player_name).unwrap()
+                        objective.display_name.plain(),
+                        holder_name,
+                        objective.data.get(holder).unwrap()
                     ),
                 )
             }
@@ -363,27 +1334,38 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
     }
 
     fn execute_players_set(&mut self, players_set: &PlayersSet) {
-        match &players_set.targets {
-            Target::Name(name) => {
-                self.set_player_score(&name, &players_set.objective, players_set.score)
-            }
-            _ => {}
+        if self.reject_read_only(&players_set.objective) {
+            return;
+        }
+        let holders = match self.found_score_holders(&players_set.targets) {
+            Some(holders) => holders,
+            None => return,
+        };
+        for holder in holders {
+            self.set_score(&holder, &players_set.objective, players_set.score)
         }
     }
 
     fn set_player_score(&mut self, player_name: &str, objective_name: &str, score: i32) {
+        self.set_score(&ScoreHolder::from_name(player_name), objective_name, score)
+    }
+
+    fn set_score(&mut self, holder: &ScoreHolder, objective_name: &str, score: i32) {
+        let holder_name = self.holder_name(holder);
         match &mut self.objectives.get_mut(objective_name) {
             Some(objective) => {
                 objective
                     .data
-                    .entry(String::from(player_name))
+                    .entry(holder.clone())
                     .and_modify(|e| *e = score)
                     .or_insert(score);
                 self.logger.log(
                     Level::Info,
                     &format!(
                         "Set [{}] for {} to {}",
-                        objective.display_name, player_name, score
+                        objective.display_name.plain(),
+                        holder_name,
+                        score
                     ),
                 )
             }
@@ -394,20 +1376,180 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
         }
     }
 
+    fn execute_players_display(&mut self, players_display: &PlayersDisplay) {
+        let holders = self.get_score_holders(&players_display.targets);
+        let subject = match holders.as_slice() {
+            [] => {
+                self.logger.log(Level::Fail, "No entity was found");
+                return;
+            }
+            [holder] => self.holder_name(holder),
+            _ => format!("{} entities", holders.len()),
+        };
+        let objective = match self.objectives.get_mut(&players_display.objective) {
+            Some(objective) => objective,
+            None => {
+                self.logger.log(
+                    Level::Fail,
+                    &format!(
+                        "Unknown scoreboard objective '{}'",
+                        players_display.objective
+                    ),
+                );
+                return;
+            }
+        };
+        for holder in holders {
+            objective.data.entry(holder.clone()).or_insert(0);
+            let display = objective.score_displays.entry(holder).or_default();
+            match &players_display.option {
+                ScoreDisplayOption::Name(name) => display.name = name.clone(),
+                ScoreDisplayOption::NumberFormat(format) => display.number_format = format.clone(),
+            }
+        }
+        let objective_name = objective.display_name.plain();
+        let feedback = match &players_display.option {
+            ScoreDisplayOption::Name(Some(name)) => format!(
+                "Changed display name to {} for {} in [{}]",
+                name.plain(),
+                subject,
+                objective_name
+            ),
+            ScoreDisplayOption::Name(None) => {
+                format!(
+                    "Cleared display name for {} in [{}]",
+                    subject, objective_name
+                )
+            }
+            ScoreDisplayOption::NumberFormat(Some(_)) => format!(
+                "Changed number format for {} in [{}]",
+                subject, objective_name
+            ),
+            ScoreDisplayOption::NumberFormat(None) => format!(
+                "Cleared number format for {} in [{}]",
+                subject, objective_name
+            ),
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn execute_players_get(&mut self, players_get: &PlayersGet) {
+        let holder = match self
+            .get_score_holders(&players_get.target)
+            .into_iter()
+            .next()
+        {
+            Some(holder) => holder,
+            None => {
+                self.logger.log(Level::Fail, "No entity was found");
+                return;
+            }
+        };
+        let holder_name = self.holder_name(&holder);
+        match self.objectives.get(&players_get.objective) {
+            Some(objective) => match objective.data.get(&holder) {
+                Some(score) => {
+                    self.result = Some(*score);
+                    self.logger.log(
+                        Level::Info,
+                        &format!(
+                            "{} has {} [{}]",
+                            holder_name,
+                            score,
+                            objective.display_name.plain()
+                        ),
+                    );
+                }
+                None => self.logger.log(
+                    Level::Fail,
+                    &format!(
+                        "Can't get value of {} for {}; none is set",
+                        players_get.objective, holder_name
+                    ),
+                ),
+            },
+            None => self.logger.log(
+                Level::Fail,
+                &format!("Unknown scoreboard objective '{}'", players_get.objective),
+            ),
+        }
+    }
+
+    fn execute_players_enable(&mut self, players_enable: &PlayersEnable) {
+        let holders = self.get_score_holders(&players_enable.targets);
+        let target_name = match holders.as_slice() {
+            [] => {
+                self.logger.log(Level::Fail, "No entity was found");
+                return;
+            }
+            [holder] => self.holder_name(holder),
+            _ => format!("{} entities", holders.len()),
+        };
+        let objective = match self.objectives.get_mut(&players_enable.objective) {
+            Some(objective) => objective,
+            None => {
+                self.logger.log(
+                    Level::Fail,
+                    &format!(
+                        "Unknown scoreboard objective '{}'",
+                        players_enable.objective
+                    ),
+                );
+                return;
+            }
+        };
+        if objective.criteria != Criteria::Trigger {
+            self.logger
+                .log(Level::Fail, "Enable only works on trigger-objectives");
+            return;
+        }
+        let mut enabled = 0;
+        for holder in holders {
+            if objective.unlocked.insert(holder.clone()) {
+                objective.data.entry(holder).or_insert(0);
+                enabled += 1;
+            }
+        }
+        match enabled {
+            0 => self.logger.log(
+                Level::Fail,
+                "Nothing changed. That trigger is already enabled",
+            ),
+            _ => self.logger.log(
+                Level::Info,
+                &format!(
+                    "Enabled trigger [{}] for {}",
+                    objective.display_name.plain(),
+                    target_name
+                ),
+            ),
+        }
+    }
+
     fn execute_players_operation(&mut self, players_operation: &PlayersOperation) {
-        let source = &self.get_player_names(&players_operation.source)[0];
+        if self.reject_read_only(&players_operation.target_objective) {
+            return;
+        }
+        let source = match self.found_score_holders(&players_operation.source) {
+            Some(holders) => holders.into_iter().next().unwrap(),
+            None => return,
+        };
+        let targets = match self.found_score_holders(&players_operation.targets) {
+            Some(holders) => holders,
+            None => return,
+        };
         let operation = get_operation(&players_operation.operation);
-        for target in self.get_player_names(&players_operation.targets) {
-            self.reify_operation(players_operation, source, &operation, &target);
+        for target in targets {
+            self.reify_operation(players_operation, &source, &operation, &target);
         }
     }
 
     fn reify_operation<F: Fn(i32, i32) -> (i32, i32)>(
         &mut self,
         players_operation: &PlayersOperation,
-        source: &String,
+        source: &ScoreHolder,
         operation: F,
-        target: &String,
+        target: &ScoreHolder,
     ) {
         let source_objective = self.objectives.get(&players_operation.source_objective);
         let target_objective = self.objectives.get(&players_operation.target_objective);
@@ -417,6 +1559,7 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
                 let source_score = source_objective.data.get(source).copied().unwrap_or(0);
 
                 let (a, b) = operation(target_score, source_score);
+                let target_name = self.holder_name(target);
                 if let Some(target_objective) =
                     self.objectives.get_mut(&players_operation.target_objective)
                 {
@@ -425,14 +1568,15 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
                         self.objectives.get_mut(&players_operation.source_objective)
                     {
                         source_objective.data.insert(source.clone(), b);
-                        let display_name =
-                            &self.objectives[&players_operation.target_objective].display_name;
+                        let display_name = self.objectives[&players_operation.target_objective]
+                            .display_name
+                            .plain();
                         self.logger.log(
                             Level::Info,
                             &format!(
                                 "Set [{}] for {} to {}",
                                 display_name,
-                                &target,
+                                target_name,
                                 *self.objectives[&players_operation.target_objective]
                                     .data
                                     .get(target)
@@ -461,1231 +1605,6372 @@ impl<'a, T: Log, S: Chat> Game<'a, T, S> {
         }
     }
 
-    fn get_player_names(&self, target: &Target) -> Vec<String> {
+    fn get_score_holders(&self, target: &Target) -> Vec<ScoreHolder> {
         match target {
-            Target::Name(name) => vec![String::from(name)],
-            Target::Selector(_) => unimplemented!(),
+            Target::Name(name) => vec![ScoreHolder::from_name(name)],
+            Target::Selector(selector) => self
+                .select(selector)
+                .into_iter()
+                .map(Entity::score_holder)
+                .collect(),
         }
     }
 
-    fn execute_function(&mut self, function: &FunctionIdentifier) {
-        let datapack = self.datapack.as_ref().unwrap();
-        match datapack.functions.iter().find(|f| {
-            let fi = &f.identifier;
-            fi.name == function.name && fi.namespace == function.namespace
-        }) {
-            Some(function) => {
-                for command in &function.commands {
-                    self.execute(command);
-                }
-            }
-            None => self.logger.log(
-                Level::Fail,
-                &format!(
-                    "Unknown function {}:{}",
-                    match &function.namespace {
-                        Some(namespace) => namespace,
-                        None => "minecraft",
-                    },
-                    function.name
-                ),
-            ),
+    fn found_score_holders(&mut self, target: &Target) -> Option<Vec<ScoreHolder>> {
+        let holders = self.get_score_holders(target);
+        if holders.is_empty() {
+            self.logger.log(Level::Fail, "No entity was found");
+            return None;
         }
+        Some(holders)
     }
 
-    fn execute_execute(&mut self, execute: &Execute) {
-        match execute {
-            Execute::If(i) => self.execute_execute_if(i),
+    fn holder_name(&self, holder: &ScoreHolder) -> String {
+        match holder {
+            ScoreHolder::Entity(uuid) => match self.entity(*uuid) {
+                Some(entity) => entity.display_name().plain(),
+                None => uuid.to_string(),
+            },
+            holder => holder.to_string(),
         }
     }
 
-    fn execute_execute_if(&mut self, i: &If) {
-        match i {
-            If::Score(s) => self.execute_execute_if_score(s),
+    /// The living entities a target refers to. A name matches an online player or, written as
+    /// a UUID, any entity.
+    fn get_entities(&self, target: &Target) -> Vec<&Entity> {
+        match target {
+            Target::Name(name) => match Uuid::parse(name) {
+                Some(uuid) => self.entity(uuid).into_iter().collect(),
+                None => self.find_player(name).into_iter().collect(),
+            },
+            Target::Selector(selector) => self.select(selector),
         }
     }
 
-    fn execute_execute_if_score(&mut self, score: &Score) {
-        match score {
-            Score::Matches(rng_cmp) => self.execute_execute_if_matches(rng_cmp),
-            _ => {}
+    fn select(&self, selector: &Selector) -> Vec<&Entity> {
+        let mut players: Vec<&Entity> = self
+            .entities
+            .iter()
+            .filter(|e| e.alive && e.is_player())
+            .collect();
+        players.sort_by(|a, b| a.player_name.cmp(&b.player_name));
+        let candidates = match selector.variable {
+            SelectorVariable::A | SelectorVariable::P | SelectorVariable::R => players,
+            SelectorVariable::E => self.entities.iter().filter(|e| e.alive).collect(),
+            SelectorVariable::S => match self.context.source {
+                Source::Entity(uuid) => self.entity(uuid).into_iter().collect(),
+                Source::Server => vec![],
+            },
+        };
+
+        let mut origin = self.context.position;
+        let mut volume: Option<[f64; 3]> = None;
+        let mut world_limited = false;
+        let mut sort = match selector.variable {
+            SelectorVariable::P => Sort::Nearest,
+            SelectorVariable::R => Sort::Random,
+            _ => Sort::Arbitrary,
+        };
+        let mut limit = match selector.variable {
+            SelectorVariable::P | SelectorVariable::R => Some(1),
+            _ => None,
+        };
+        for argument in &selector.arguments {
+            match argument {
+                SelectorArgument::X(x) => origin[0] = *x,
+                SelectorArgument::Y(y) => origin[1] = *y,
+                SelectorArgument::Z(z) => origin[2] = *z,
+                SelectorArgument::Dx(d) => volume.get_or_insert([0.0; 3])[0] = *d,
+                SelectorArgument::Dy(d) => volume.get_or_insert([0.0; 3])[1] = *d,
+                SelectorArgument::Dz(d) => volume.get_or_insert([0.0; 3])[2] = *d,
+                SelectorArgument::Distance(_) => world_limited = true,
+                SelectorArgument::Sort(s) => sort = *s,
+                SelectorArgument::Limit(l) => limit = Some(*l as usize),
+                _ => {}
+            }
+        }
+        world_limited |= volume.is_some();
+
+        let mut selected: Vec<&Entity> = candidates
+            .into_iter()
+            .filter(|e| !world_limited || e.dimension == self.context.dimension)
+            .filter(|e| {
+                volume
+                    .map(|size| in_volume(e.position, origin, size))
+                    .unwrap_or(true)
+            })
+            .filter(|e| {
+                selector
+                    .arguments
+                    .iter()
+                    .all(|argument| self.selector_matches(e, argument, origin))
+            })
+            .collect();
+        let by_distance = |a: &&Entity, b: &&Entity| {
+            distance(a.position, origin)
+                .partial_cmp(&distance(b.position, origin))
+                .unwrap_or(cmp::Ordering::Equal)
+        };
+        match sort {
+            Sort::Nearest => selected.sort_by(by_distance),
+            Sort::Furthest => selected.sort_by(|a, b| by_distance(b, a)),
+            Sort::Arbitrary => {}
+            Sort::Random => self.random.borrow_mut().shuffle(&mut selected),
         }
+        if let Some(limit) = limit {
+            selected.truncate(limit);
+        }
+        selected
     }
 
-    fn execute_execute_if_matches(&mut self, rng_cmp: &RangeComparison) {
-        match self.objectives.get(&rng_cmp.target_objective) {
-            Some(objective) => {
-                if let Some(score) = objective
-                    .data
-                    .get(&self.get_player_names(&rng_cmp.target)[0])
-                    .copied()
-                {
-                    if self.does_match(score, &rng_cmp.interval) {
-                        self.execute(&rng_cmp.command);
-                    }
+    fn selector_matches(
+        &self,
+        entity: &Entity,
+        argument: &SelectorArgument,
+        origin: [f64; 3],
+    ) -> bool {
+        match argument {
+            SelectorArgument::Type { kind, negated } => {
+                (entity.kind == namespaced(kind)) != *negated
+            }
+            SelectorArgument::Tag { tag, negated } if tag.is_empty() => {
+                entity.tags.is_empty() != *negated
+            }
+            SelectorArgument::Tag { tag, negated } => entity.tags.contains(tag) != *negated,
+            SelectorArgument::Name { name, negated } => {
+                (entity.display_name().plain() == *name) != *negated
+            }
+            SelectorArgument::Team { team, negated } => {
+                let current = self.team_of(&entity.score_holder());
+                match team.is_empty() {
+                    true => current.is_none() != *negated,
+                    false => (current == Some(team.as_str())) != *negated,
                 }
             }
-            None => self.logger.log(
-                Level::Fail,
-                &format!(
-                    "Unknown scoreboard objective '{}'",
-                    rng_cmp.target_objective
-                ),
-            ),
+            SelectorArgument::Scores(scores) => scores.iter().all(|(objective, interval)| {
+                self.score(&entity.score_holder(), objective)
+                    .map(|score| self.does_match(score, interval))
+                    .unwrap_or(false)
+            }),
+            SelectorArgument::Distance(range) => range.contains(distance(entity.position, origin)),
+            SelectorArgument::Limit(_)
+            | SelectorArgument::X(_)
+            | SelectorArgument::Y(_)
+            | SelectorArgument::Z(_)
+            | SelectorArgument::Dx(_)
+            | SelectorArgument::Dy(_)
+            | SelectorArgument::Dz(_)
+            | SelectorArgument::Sort(_) => true,
+            SelectorArgument::Predicate { predicate, negated } => {
+                let passed = self
+                    .predicate(&namespaced(predicate))
+                    .is_some_and(|predicate| {
+                        self.test_condition(predicate, Some(entity), entity.position, &mut vec![])
+                    });
+                passed != *negated
+            }
         }
     }
 
-    fn does_match(&self, value: i32, interval: &Interval) -> bool {
-        match interval {
-            Interval::Value(v) => value == *v,
-            Interval::Bounded(a, b) => *a <= value && value <= *b,
-            Interval::LeftUnbounded(b) => value <= *b,
-            Interval::RightUnbounded(a) => *a <= value,
+    fn execute_summon(&mut self, summon: &Summon) {
+        if namespaced(&summon.entity) == "minecraft:player" {
+            self.logger.log(Level::Fail, "Unable to summon entity");
+            return;
         }
+        let uuid = self.next_uuid();
+        let mut entity = Entity::new(uuid, &summon.entity);
+        entity.position = match &summon.position {
+            Some(position) => self.context.resolve(position),
+            None => self.context.position,
+        };
+        entity.dimension = self.context.dimension.clone();
+        if let Some(nbt) = &summon.nbt {
+            entity.apply_nbt(nbt);
+        }
+        self.logger.log(
+            Level::Info,
+            &format!("Summoned new {}", entity.display_name().plain()),
+        );
+        self.entities.push(entity);
     }
 
-    fn execute_tellraw(&mut self, tellraw: &Tellraw) {
-        self.chat.tell(vec![], &tellraw.message);
+    fn execute_kill(&mut self, kill: &Kill) {
+        let targets: Vec<&Entity> = match &kill.targets {
+            Some(targets) => self.get_entities(targets),
+            None => self.select(&Selector::new(SelectorVariable::S)),
+        };
+        let killed: Vec<(Uuid, String)> = targets
+            .into_iter()
+            .map(|e| (e.uuid, e.display_name().plain()))
+            .collect();
+        let feedback = match killed.as_slice() {
+            [] => {
+                self.logger.log(Level::Fail, "No entity was found");
+                return;
+            }
+            [(_, name)] => format!("Killed {}", name),
+            _ => format!("Killed {} entities", killed.len()),
+        };
+        for (uuid, name) in killed {
+            let message = TextComponent::translatable(
+                "death.attack.genericKill",
+                "%s was killed",
+                vec![TextComponent::text(&name)],
+            );
+            self.die(uuid, None, message);
+            self.remove_entity(uuid);
+        }
+        self.logger.log(Level::Info, &feedback);
     }
-}
 
-fn condense_display_name(objective_name: &str, display_name: Option<&str>) -> String {
-    match display_name {
-        Some(name) => String::from(name),
-        None => String::from(objective_name),
+    /// Removes a killed entity along with its scores. Players respawn instead, keeping theirs.
+    fn remove_entity(&mut self, uuid: Uuid) {
+        if self.entity(uuid).map(Entity::is_player).unwrap_or(true) {
+            return;
+        }
+        self.entities.retain(|e| e.uuid != uuid);
+        let holder = ScoreHolder::Entity(uuid);
+        for objective in self.objectives.values_mut() {
+            objective.data.remove(&holder);
+            objective.unlocked.remove(&holder);
+            objective.score_displays.remove(&holder);
+        }
+        for team in self.teams.values_mut() {
+            team.members.remove(&holder);
+        }
     }
-}
-
-fn space_separate<'a, Iter: Iterator<Item = &'a String>>(strings: Iter) -> String {
-    let mut output = String::new();
-    strings.for_each(|s| output.push_str(&format!(" [{}]", s)));
-    output
-}
 
-fn slot_contains(slot: Option<&Option<String>>, value: &str) -> bool {
-    match slot {
-        Some(wrapper) => match wrapper {
-            Some(x) => x == value,
-            None => false,
-        },
-        None => false,
+    fn execute_tag(&mut self, tag: &Tag) {
+        let targets: Vec<Uuid> = self
+            .get_entities(&tag.targets)
+            .into_iter()
+            .map(|e| e.uuid)
+            .collect();
+        let subject = match targets.as_slice() {
+            [] => {
+                self.logger.log(Level::Fail, "No entity was found");
+                return;
+            }
+            [single] => self.entity(*single).unwrap().display_name().plain(),
+            _ => format!("{} entities", targets.len()),
+        };
+        match &tag.action {
+            TagAction::Add(name) => {
+                let mut changed = 0;
+                for uuid in &targets {
+                    let entity = self.entity_mut(*uuid).unwrap();
+                    if entity.tags.len() < MAX_TAGS && entity.tags.insert(name.clone()) {
+                        changed += 1;
+                    }
+                }
+                match changed {
+                    0 => self.logger.log(
+                        Level::Fail,
+                        "Target either already has the tag or has too many tags",
+                    ),
+                    _ => self
+                        .logger
+                        .log(Level::Info, &format!("Added tag '{}' to {}", name, subject)),
+                }
+            }
+            TagAction::Remove(name) => {
+                let mut changed = 0;
+                for uuid in &targets {
+                    if self.entity_mut(*uuid).unwrap().tags.remove(name) {
+                        changed += 1;
+                    }
+                }
+                match changed {
+                    0 => self
+                        .logger
+                        .log(Level::Fail, "Target does not have this tag"),
+                    _ => self.logger.log(
+                        Level::Info,
+                        &format!("Removed tag '{}' from {}", name, subject),
+                    ),
+                }
+            }
+            TagAction::List => {
+                let mut tags: Vec<&String> = targets
+                    .iter()
+                    .flat_map(|uuid| self.entity(*uuid).unwrap().tags.iter())
+                    .collect();
+                tags.sort();
+                tags.dedup();
+                let list = tags
+                    .iter()
+                    .map(|tag| tag.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ");
+                let feedback = match (targets.len(), tags.len()) {
+                    (1, 0) => format!("{} has no tags", subject),
+                    (1, count) => format!("{} has {} tags: {}", subject, count, list),
+                    (_, 0) => format!("The {} have no tags", subject),
+                    (_, count) => format!("The {} have {} total tags: {}", subject, count, list),
+                };
+                self.logger.log(Level::Info, &feedback);
+            }
+        }
     }
-}
 
-fn get_operation(operation_type: &OperationType) -> Box<dyn Fn(i32, i32) -> (i32, i32)> {
-    match operation_type {
-        OperationType::Addition => Box::new(|a, b| (a + b, b)),
-        OperationType::Subtraction => Box::new(|a, b| (a - b, b)),
-        OperationType::Multiplication => Box::new(|a, b| (a * b, b)),
-        OperationType::Division => Box::new(|a, b| (a / b, b)),
-        OperationType::Modulus => Box::new(|a, b| (a % b, b)),
-        OperationType::Assign => Box::new(|_, b| (b, b)),
-        OperationType::Min => Box::new(|a, b| (cmp::min(a, b), b)),
-        OperationType::Max => Box::new(|a, b| (cmp::max(a, b), b)),
-        OperationType::Swap => Box::new(|a, b| (b, a)),
-    }
-}
+    fn execute_teleport(&mut self, teleport: &Teleport) {
+        let targets: Vec<Uuid> = match &teleport.targets {
+            Some(targets) => self.get_entities(targets),
+            None => self.select(&Selector::new(SelectorVariable::S)),
+        }
+        .into_iter()
+        .map(|e| e.uuid)
+        .collect();
+        if targets.is_empty() {
+            match (&teleport.targets, &self.context.source) {
+                (None, Source::Server) => self.logger.log(
+                    Level::Fail,
+                    "An entity is required to run this command here",
+                ),
+                _ => self.logger.log(Level::Fail, "No entity was found"),
+            }
+            return;
+        }
+        let subject = match targets.as_slice() {
+            [single] => self.entity(*single).unwrap().display_name().plain(),
+            _ => format!("{} entities", targets.len()),
+        };
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::Players::Operation;
-    use std::collections::HashMap;
-    use std::collections::VecDeque;
-    use std::hash::Hash;
+        let feedback = match &teleport.destination {
+            Destination::Entity(destination) => {
+                let destination = match self.get_entities(destination).as_slice() {
+                    [] => {
+                        self.logger.log(Level::Fail, "No entity was found");
+                        return;
+                    }
+                    [single] => (*single).clone(),
+                    _ => {
+                        self.logger.log(
+                            Level::Fail,
+                            "Only one entity is allowed, but the provided selector allows more than one",
+                        );
+                        return;
+                    }
+                };
+                for uuid in &targets {
+                    let entity = self.entity_mut(*uuid).unwrap();
+                    entity.position = destination.position;
+                    entity.rotation = destination.rotation;
+                    entity.dimension = destination.dimension.clone();
+                }
+                format!(
+                    "Teleported {} to {}",
+                    subject,
+                    destination.display_name().plain()
+                )
+            }
+            Destination::Location(location, orientation) => {
+                let position = self.context.resolve(location);
+                let facing = match orientation {
+                    Some(Orientation::FacingLocation(target)) => Some(self.context.resolve(target)),
+                    Some(Orientation::FacingEntity(target, anchor)) => {
+                        match self.get_entities(target).as_slice() {
+                            [entity] => {
+                                let mut point = entity.position;
+                                if *anchor == Anchor::Eyes {
+                                    point[1] += entity.eye_height();
+                                }
+                                Some(point)
+                            }
+                            _ => {
+                                self.logger.log(Level::Fail, "No entity was found");
+                                return;
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+                let rotation = match orientation {
+                    Some(Orientation::Rotation(rotation)) => Some([
+                        world_axis(rotation.yaw, self.context.rotation[0] as f64) as f32,
+                        world_axis(rotation.pitch, self.context.rotation[1] as f64) as f32,
+                    ]),
+                    _ => None,
+                };
+                let dimension = self.context.dimension.clone();
+                for uuid in &targets {
+                    let entity = self.entity_mut(*uuid).unwrap();
+                    entity.position = position;
+                    entity.dimension = dimension.clone();
+                    if let Some(rotation) = rotation {
+                        entity.rotation = rotation;
+                    }
+                    if let Some(point) = facing {
+                        let eyes = [position[0], position[1] + entity.eye_height(), position[2]];
+                        entity.rotation = facing_rotation(eyes, point);
+                    }
+                }
+                format!(
+                    "Teleported {} to {:.6}, {:.6}, {:.6}",
+                    subject, position[0], position[1], position[2]
+                )
+            }
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
 
-    struct LoggerSpy {
-        messages: VecDeque<(Level, String)>,
+    fn execute_setblock(&mut self, setblock: &SetBlock) {
+        let position = match self.loaded_block_pos(&setblock.position) {
+            Some(position) => position,
+            None => return,
+        };
+        let dimension = self.context.dimension.clone();
+        let existing = self.world.get(&dimension, position);
+        let changed = match setblock.mode {
+            SetBlockMode::Keep if !existing.is_air() => false,
+            SetBlockMode::Destroy => {
+                let destroyed = self.world.set(&dimension, position, &Block::air());
+                self.world.set(&dimension, position, &setblock.block) || destroyed
+            }
+            _ => self.world.set(&dimension, position, &setblock.block),
+        };
+        match changed {
+            true => self.logger.log(
+                Level::Info,
+                &format!(
+                    "Changed the block at {}, {}, {}",
+                    position[0], position[1], position[2]
+                ),
+            ),
+            false => self.logger.log(Level::Fail, "Could not set the block"),
+        }
     }
 
-    impl LoggerSpy {
-        fn new() -> LoggerSpy {
-            LoggerSpy {
-                messages: VecDeque::new(),
+    fn execute_fill(&mut self, fill: &Fill) {
+        let (from, to) = match (
+            self.loaded_block_pos(&fill.from),
+            self.loaded_block_pos(&fill.to),
+        ) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return,
+        };
+        let (low, high) = corners(from, to);
+        if !self.check_volume(low, high) {
+            return;
+        }
+        let dimension = self.context.dimension.clone();
+        let mut filled = 0;
+        for position in positions(low, high) {
+            let on_border =
+                (0..3).any(|axis| position[axis] == low[axis] || position[axis] == high[axis]);
+            let existing = self.world.get(&dimension, position);
+            let block = match &fill.mode {
+                FillMode::Replace(Some(filter)) if !existing.matches(filter) => continue,
+                FillMode::Keep if !existing.is_air() => continue,
+                FillMode::Outline if !on_border => continue,
+                FillMode::Hollow if !on_border => Block::air(),
+                _ => fill.block.clone(),
+            };
+            let destroyed = fill.mode == FillMode::Destroy
+                && self.world.set(&dimension, position, &Block::air());
+            if self.world.set(&dimension, position, &block) || destroyed {
+                filled += 1;
             }
         }
+        match filled {
+            0 => self.logger.log(Level::Fail, "No blocks were filled"),
+            _ => self.logger.log(
+                Level::Info,
+                &format!("Successfully filled {} block(s)", filled),
+            ),
+        }
+    }
 
-        fn assert_logged(&mut self, level: Level, message: &str) {
-            match self.messages.pop_front() {
-                Some(msg) => {
-                    assert_eq!(level, msg.0);
-                    assert_eq!(message, &msg.1);
+    fn execute_clone(&mut self, clone: &CloneBlocks) {
+        let (begin, end, destination) = match (
+            self.loaded_block_pos(&clone.begin),
+            self.loaded_block_pos(&clone.end),
+            self.loaded_block_pos(&clone.destination),
+        ) {
+            (Some(begin), Some(end), Some(destination)) => (begin, end, destination),
+            _ => return,
+        };
+        let (low, high) = corners(begin, end);
+        let destination_high = [
+            destination[0] + high[0] - low[0],
+            destination[1] + high[1] - low[1],
+            destination[2] + high[2] - low[2],
+        ];
+        if !World::contains(&self.context.dimension, destination_high) {
+            self.logger
+                .log(Level::Fail, "That position is out of this world!");
+            return;
+        }
+        if !self.check_volume(low, high) {
+            return;
+        }
+        let overlapping = (0..3)
+            .all(|axis| low[axis] <= destination_high[axis] && destination[axis] <= high[axis]);
+        if overlapping && clone.mode != CloneMode::Force {
+            self.logger
+                .log(Level::Fail, "The source and destination can not overlap");
+            return;
+        }
+
+        let dimension = self.context.dimension.clone();
+        let copied: Vec<([i32; 3], Block)> = positions(low, high)
+            .map(|position| (position, self.world.get(&dimension, position)))
+            .filter(|(_, block)| match &clone.mask {
+                CloneMask::Replace => true,
+                CloneMask::Masked => !block.is_air(),
+                CloneMask::Filtered(filter) => block.matches(filter),
+            })
+            .collect();
+        if copied.is_empty() {
+            self.logger.log(Level::Fail, "No blocks were cloned");
+            return;
+        }
+        if clone.mode == CloneMode::Move {
+            for (position, _) in &copied {
+                self.world.set(&dimension, *position, &Block::air());
+            }
+        }
+        for (position, block) in &copied {
+            let target = [
+                destination[0] + position[0] - low[0],
+                destination[1] + position[1] - low[1],
+                destination[2] + position[2] - low[2],
+            ];
+            self.world.set(&dimension, target, block);
+        }
+        self.logger.log(
+            Level::Info,
+            &format!("Successfully cloned {} block(s)", copied.len()),
+        );
+    }
+
+    pub fn gamerules(&self) -> &GameRules {
+        &self.gamerules
+    }
+
+    fn execute_gamerule(&mut self, gamerule: &Gamerule) {
+        match gamerule.value {
+            Some(value) => {
+                self.gamerules.set(gamerule.rule, value);
+                self.route_feedback();
+                self.result = Some(value.as_int());
+                self.logger.log(
+                    Level::Info,
+                    &format!("Gamerule {} is now set to: {}", gamerule.rule, value),
+                );
+            }
+            None => {
+                let value = self.gamerules.get(gamerule.rule);
+                self.result = Some(value.as_int());
+                self.logger.log(
+                    Level::Info,
+                    &format!("Gamerule {} is currently set to: {}", gamerule.rule, value),
+                );
+            }
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.game_time += 1;
+        if self.gamerules.bool(GameRule::DoDaylightCycle) {
+            self.day_time += 1;
+        }
+        if self.gamerules.bool(GameRule::DoWeatherCycle) {
+            if let Some(duration) = self.weather_duration {
+                self.weather_duration = Some(duration - 1).filter(|d| *d > 0);
+                if self.weather_duration.is_none() {
+                    self.weather = WeatherKind::Clear;
                 }
-                None => panic!("assertion failed: no messages in the log"),
             }
         }
+        let players: Vec<Uuid> = self
+            .entities
+            .iter()
+            .filter(|e| e.alive && e.is_player())
+            .map(|e| e.uuid)
+            .collect();
+        for player in players {
+            self.trigger(player, "minecraft:tick", |_, _, _| true);
+            if self.game_time % 20 == 0 {
+                self.trigger(player, "minecraft:location", |_, _, _| true);
+            }
+        }
+    }
 
-        fn assert_matches<F>(&mut self, condition: F)
-        where
-            F: Fn(Level, &str) -> bool,
-        {
-            match self.messages.pop_front() {
-                Some(msg) => assert!(condition(msg.0, &msg.1)),
-                None => assert!(false),
+    pub fn game_time(&self) -> i64 {
+        self.game_time
+    }
+
+    pub fn day_time(&self) -> i64 {
+        self.day_time
+    }
+
+    pub fn weather(&self) -> WeatherKind {
+        self.weather
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn execute_time(&mut self, time: &Time) {
+        let day_time = (self.day_time % 24000) as i32;
+        let (result, feedback) = match time {
+            Time::Set(time) => {
+                self.day_time = i64::from(*time);
+                (*time % 24000, format!("Set the time to {}", time))
+            }
+            Time::Add(time) => {
+                self.day_time += i64::from(*time);
+                let day_time = (self.day_time % 24000) as i32;
+                (day_time, format!("Set the time to {}", day_time))
+            }
+            Time::Query(query) => {
+                let time = match query {
+                    TimeQuery::Daytime => day_time,
+                    TimeQuery::Gametime => (self.game_time % i64::from(i32::MAX)) as i32,
+                    TimeQuery::Day => (self.day_time / 24000 % i64::from(i32::MAX)) as i32,
+                };
+                (time, format!("The time is {}", time))
+            }
+        };
+        self.result = Some(result);
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    /// Sets the weather, for `duration` ticks if given. Like vanilla the result is the duration,
+    /// or -1 when the game decides.
+    fn execute_weather(&mut self, weather: &Weather) {
+        self.weather = weather.kind;
+        self.weather_duration = weather.duration;
+        self.result = Some(weather.duration.unwrap_or(-1));
+        let feedback = match weather.kind {
+            WeatherKind::Clear => "Set the weather to clear",
+            WeatherKind::Rain => "Set the weather to rain",
+            WeatherKind::Thunder => "Set the weather to rain & thunder",
+        };
+        self.logger.log(Level::Info, feedback);
+    }
+
+    fn execute_difficulty(&mut self, difficulty: Option<Difficulty>) {
+        match difficulty {
+            Some(difficulty) if difficulty == self.difficulty => self.logger.log(
+                Level::Fail,
+                &format!(
+                    "The difficulty did not change; it is already set to {}",
+                    difficulty
+                ),
+            ),
+            Some(difficulty) => {
+                self.difficulty = difficulty;
+                self.result = Some(0);
+                self.logger.log(
+                    Level::Info,
+                    &format!("The difficulty has been set to {}", difficulty.label()),
+                );
+            }
+            None => {
+                self.result = Some(self.difficulty.id());
+                self.logger.log(
+                    Level::Info,
+                    &format!("The difficulty is {}", self.difficulty.label()),
+                );
             }
         }
+    }
 
-        fn assert_no_logs(&mut self) {
-            assert!(self.messages.is_empty());
+    pub fn has_advancement(&self, player: &str, id: &str) -> bool {
+        let done = self
+            .advancements
+            .get(player)
+            .and_then(|advancements| advancements.get(&namespaced(id)));
+        match (self.advancement(&namespaced(id)), done) {
+            (Some(advancement), Some(done)) => advancement.is_done(done),
+            _ => false,
         }
+    }
 
-        fn skip(&mut self) {
-            self.messages.pop_front();
+    fn advancement(&self, id: &str) -> Option<&'a AdvancementNode> {
+        let datapack: &'a Option<Datapack> = self.datapack;
+        datapack
+            .as_ref()
+            .and_then(|datapack| datapack.advancements.get(id))
+    }
+
+    fn advancement_nodes(&self) -> BTreeMap<String, &'a AdvancementNode> {
+        let datapack: &'a Option<Datapack> = self.datapack;
+        datapack
+            .as_ref()
+            .map(|datapack| {
+                datapack
+                    .advancements
+                    .iter()
+                    .map(|(id, node)| (id.clone(), node))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn execute_advancement(&mut self, command: &Advancement) {
+        let players = self.get_player_names(&command.targets);
+        if players.is_empty() {
+            self.logger.log(Level::Fail, "No player was found");
+            return;
+        }
+        let grant = command.action == AdvancementAction::Grant;
+        let nodes = self.advancement_nodes();
+        let subject = match players.as_slice() {
+            [player] => player.clone(),
+            _ => format!("{} players", players.len()),
+        };
+        if let AdvancementSelection::Only(id, Some(criterion)) = &command.selection {
+            let id = namespaced(id);
+            let node = match nodes.get(&id) {
+                Some(node) => node,
+                None => {
+                    self.logger
+                        .log(Level::Fail, &format!("Unknown advancement: {}", id));
+                    return;
+                }
+            };
+            let name = node.name(&id).plain();
+            if !node.criteria.contains_key(criterion) {
+                self.logger.log(
+                    Level::Fail,
+                    &format!(
+                        "The advancement {} does not contain the criterion '{}'",
+                        name, criterion
+                    ),
+                );
+                return;
+            }
+            let changed = players
+                .iter()
+                .filter(|player| match grant {
+                    true => self.award(player, &id, node, criterion),
+                    false => self.revoke(player, &id, criterion),
+                })
+                .count();
+            let feedback = match (grant, changed > 0) {
+                (true, true) => format!(
+                    "Granted criterion '{}' of advancement {} to {}",
+                    criterion, name, subject
+                ),
+                (false, true) => format!(
+                    "Revoked criterion '{}' of advancement {} from {}",
+                    criterion, name, subject
+                ),
+                (true, false) => format!(
+                    "Couldn't grant criterion '{}' of advancement {} to {} as they already have it",
+                    criterion, name, subject
+                ),
+                (false, false) => format!(
+                    "Couldn't revoke criterion '{}' of advancement {} from {} as they don't have it",
+                    criterion, name, subject
+                ),
+            };
+            self.report_advancements(changed, &feedback);
+            return;
+        }
+
+        let ids = match selected_advancements(&command.selection, &nodes) {
+            Ok(ids) => ids,
+            Err(id) => {
+                self.logger
+                    .log(Level::Fail, &format!("Unknown advancement: {}", id));
+                return;
+            }
+        };
+        let mut changed = 0;
+        for player in &players {
+            for id in &ids {
+                let node = nodes[id];
+                let done = self
+                    .advancements
+                    .get(player)
+                    .and_then(|advancements| advancements.get(id))
+                    .cloned()
+                    .unwrap_or_default();
+                let criteria: Vec<&String> = match grant {
+                    true if node.is_done(&done) => continue,
+                    true => node
+                        .criteria
+                        .keys()
+                        .filter(|criterion| !done.contains(*criterion))
+                        .collect(),
+                    false if done.is_empty() => continue,
+                    false => done.iter().collect(),
+                };
+                for criterion in criteria {
+                    match grant {
+                        true => self.award(player, id, node, criterion),
+                        false => self.revoke(player, id, criterion),
+                    };
+                }
+                changed += 1;
+            }
+        }
+        let feedback = match (grant, changed > 0, ids.as_slice()) {
+            (true, true, [id]) => format!(
+                "Granted the advancement {} to {}",
+                nodes[id].name(id).plain(),
+                subject
+            ),
+            (true, true, _) => format!("Granted {} advancements to {}", ids.len(), subject),
+            (false, true, [id]) => format!(
+                "Revoked the advancement {} from {}",
+                nodes[id].name(id).plain(),
+                subject
+            ),
+            (false, true, _) => format!("Revoked {} advancements from {}", ids.len(), subject),
+            (true, false, [id]) => format!(
+                "Couldn't grant advancement {} to {} as they already have it",
+                nodes[id].name(id).plain(),
+                subject
+            ),
+            (true, false, _) => format!(
+                "Couldn't grant {} advancements to {} as they already have them",
+                ids.len(),
+                subject
+            ),
+            (false, false, [id]) => format!(
+                "Couldn't revoke advancement {} from {} as they don't have it",
+                nodes[id].name(id).plain(),
+                subject
+            ),
+            (false, false, _) => format!(
+                "Couldn't revoke {} advancements from {} as they don't have them",
+                ids.len(),
+                subject
+            ),
+        };
+        self.report_advancements(changed, &feedback);
+    }
+
+    fn report_advancements(&mut self, changed: usize, feedback: &str) {
+        if changed == 0 {
+            self.logger.log(Level::Fail, feedback);
+        } else {
+            self.result = Some(changed as i32);
+            self.logger.log(Level::Info, feedback);
+        }
+    }
+
+    /// Marks a criterion of the advancement done for the player, if it wasn't. Completing the
+    /// advancement this way runs its reward function as the player and tells everyone, as
+    /// `announceAdvancements` allows.
+    fn award(&mut self, player: &str, id: &str, node: &AdvancementNode, criterion: &str) -> bool {
+        let done = self
+            .advancements
+            .entry(String::from(player))
+            .or_default()
+            .entry(String::from(id))
+            .or_default();
+        let was_done = node.is_done(done);
+        if !done.insert(String::from(criterion)) {
+            return false;
+        }
+        if was_done || !node.is_done(done) {
+            return true;
+        }
+        if let Some(reward) = &node.reward {
+            if let Some(context) = self.find_player(player).map(ExecutionContext::of) {
+                let function = match reward.split_once(':') {
+                    Some((namespace, name)) => FunctionIdentifier {
+                        namespace: Some(String::from(namespace)),
+                        name: String::from(name),
+                    },
+                    None => FunctionIdentifier {
+                        namespace: None,
+                        name: reward.clone(),
+                    },
+                };
+                self.execute_in(context, &Command::Function(function));
+            }
+        }
+        let display = match &node.display {
+            Some(display) if display.announce_to_chat => display,
+            _ => return true,
+        };
+        if self.gamerules.bool(GameRule::AnnounceAdvancements) {
+            let (key, pattern) = match display.frame {
+                Frame::Task => (
+                    "chat.type.advancement.task",
+                    "%s has made the advancement %s",
+                ),
+                Frame::Goal => ("chat.type.advancement.goal", "%s has reached the goal %s"),
+                Frame::Challenge => (
+                    "chat.type.advancement.challenge",
+                    "%s has completed the challenge %s",
+                ),
+            };
+            let name = self.formatted_holder_name(
+                &ScoreHolder::Player(String::from(player)),
+                TextComponent::text(player),
+            );
+            let message = TextComponent::translatable(key, pattern, vec![name, node.name(id)]);
+            self.broadcast(Message::new(message));
+        }
+        true
+    }
+
+    fn revoke(&mut self, player: &str, id: &str, criterion: &str) -> bool {
+        self.advancements
+            .get_mut(player)
+            .and_then(|advancements| advancements.get_mut(id))
+            .is_some_and(|done| done.remove(criterion))
+    }
+
+    /// Fires `trigger` for the player `uuid`: every criterion listening for it that isn't done
+    /// yet is done when the player fits its `player` condition and `matches` accepts the rest
+    /// of its conditions.
+    fn trigger<F>(&mut self, uuid: Uuid, trigger: &str, matches: F)
+    where
+        F: Fn(&Self, &Entity, &Json) -> bool,
+    {
+        let player = match self.entity(uuid) {
+            Some(player) if player.is_player() => player,
+            _ => return,
+        };
+        let name = player.player_name.clone().unwrap_or_default();
+        let mut awarded = vec![];
+        for (id, node) in self.advancement_nodes() {
+            let done = self
+                .advancements
+                .get(&name)
+                .and_then(|advancements| advancements.get(&id));
+            for (criterion, listener) in &node.criteria {
+                if listener.trigger == trigger
+                    && !done.is_some_and(|done| done.contains(criterion))
+                    && self.entity_condition(
+                        listener.conditions.get("player"),
+                        player,
+                        player.position,
+                    )
+                    && matches(self, player, &listener.conditions)
+                {
+                    awarded.push((id.clone(), node, criterion.clone()));
+                }
+            }
+        }
+        for (id, node, criterion) in awarded {
+            self.award(&name, &id, node, &criterion);
+        }
+    }
+
+    /// Whether `entity` fits a trigger's entity condition, tested from `origin`: an entity
+    /// predicate, or a list of predicate conditions about it. No condition fits everything.
+    fn entity_condition(
+        &self,
+        condition: Option<&Json>,
+        entity: &Entity,
+        origin: [f64; 3],
+    ) -> bool {
+        match condition {
+            None => true,
+            Some(conditions @ Json::Array(_)) => {
+                self.test_condition(conditions, Some(entity), origin, &mut vec![])
+            }
+            Some(predicate) => self.entity_matches(entity, predicate, origin),
+        }
+    }
+
+    pub fn set_seed(&mut self, seed: i64) {
+        self.seed = seed;
+        self.random = RefCell::new(RandomSource::new(seed));
+        self.sequences.clear();
+    }
+
+    fn random_chance(&self, chance: f32) -> bool {
+        self.random.borrow_mut().next_float() < chance
+    }
+
+    fn execute_random(&mut self, random: &Random) {
+        match random {
+            Random::Value(range, sequence) => {
+                if let Some(value) = self.random_value(range, sequence.as_deref()) {
+                    self.result = Some(value);
+                    self.logger
+                        .log(Level::Info, &format!("Randomized value: {}", value));
+                }
+            }
+            Random::Roll(range, sequence) => {
+                if let Some(value) = self.random_value(range, sequence.as_deref()) {
+                    self.result = Some(value);
+                    let (min, max) = bounds(range);
+                    let message = TextComponent::translatable(
+                        "commands.random.roll",
+                        "%s rolled %s (from %s to %s)",
+                        vec![
+                            self.source_name(),
+                            TextComponent::text(&value.to_string()),
+                            TextComponent::text(&min.to_string()),
+                            TextComponent::text(&max.to_string()),
+                        ],
+                    );
+                    self.broadcast(Message::new(message));
+                }
+            }
+            Random::Reset(reset) => {
+                let salt = reset.seed.unwrap_or(0);
+                let include_world_seed = reset.include_world_seed.unwrap_or(true);
+                let include_sequence_id = reset.include_sequence_id.unwrap_or(true);
+                match &reset.sequence {
+                    Some(id) => {
+                        let id = namespaced(id);
+                        let source = self
+                            .sequence_source(&id, (salt, include_world_seed, include_sequence_id));
+                        self.sequences.insert(id.clone(), source);
+                        self.result = Some(1);
+                        self.logger
+                            .log(Level::Info, &format!("Reset random sequence {}", id));
+                    }
+                    None => {
+                        let count = self.sequences.len();
+                        self.sequences.clear();
+                        self.sequence_defaults = (salt, include_world_seed, include_sequence_id);
+                        self.result = Some(count as i32);
+                        self.logger
+                            .log(Level::Info, &format!("Reset {} random sequence(s)", count));
+                    }
+                }
+            }
+        }
+    }
+
+    /// A number in `range` from the sequence, or from the world's randomness without one.
+    /// Fails the command unless the range holds from 2 to `i32::MAX` numbers.
+    fn random_value(&mut self, range: &Interval, sequence: Option<&str>) -> Option<i32> {
+        let (min, max) = bounds(range);
+        let size = i64::from(max) - i64::from(min);
+        if size < 1 {
+            self.logger.log(
+                Level::Fail,
+                "The range of the random value must be at least 2",
+            );
+            return None;
+        }
+        if size >= i64::from(i32::MAX) {
+            self.logger.log(
+                Level::Fail,
+                "The range of the random value must be at most 2147483646",
+            );
+            return None;
+        }
+        Some(match sequence {
+            Some(id) => {
+                let id = namespaced(id);
+                if !self.sequences.contains_key(&id) {
+                    let source = self.sequence_source(&id, self.sequence_defaults);
+                    self.sequences.insert(id.clone(), source);
+                }
+                self.sequences.get_mut(&id).unwrap().between(min, max)
+            }
+            None => self.random.borrow_mut().between(min, max),
+        })
+    }
+
+    fn sequence_source(
+        &self,
+        id: &str,
+        (salt, include_world_seed, include_sequence_id): (i64, bool, bool),
+    ) -> RandomSource {
+        let seed = if include_world_seed { self.seed } else { 0 } ^ salt;
+        RandomSource::for_sequence(seed, Some(id).filter(|_| include_sequence_id))
+    }
+
+    pub fn bossbars(&self) -> &BTreeMap<String, CustomBossbar> {
+        &self.bossbars
+    }
+
+    fn bossbar_exists(&mut self, id: &str) -> bool {
+        let exists = self.bossbars.contains_key(&namespaced(id));
+        if !exists {
+            self.logger.log(
+                Level::Fail,
+                &format!("No bossbar exists with the ID '{}'", namespaced(id)),
+            );
+        }
+        exists
+    }
+
+    fn execute_bossbar(&mut self, bossbar: &Bossbar) {
+        match bossbar {
+            Bossbar::Add(id, name) => {
+                if self.bossbars.contains_key(&namespaced(id)) {
+                    self.logger.log(
+                        Level::Fail,
+                        &format!("A bossbar already exists with the ID '{}'", namespaced(id)),
+                    );
+                    return;
+                }
+                self.bossbars
+                    .insert(namespaced(id), CustomBossbar::new(name.clone()));
+                self.result = Some(self.bossbars.len() as i32);
+                self.logger.log(
+                    Level::Info,
+                    &format!("Created custom bossbar [{}]", name.plain()),
+                );
+            }
+            Bossbar::Remove(id) => {
+                if self.bossbar_exists(id) {
+                    let removed = self.bossbars.remove(&namespaced(id)).unwrap();
+                    self.result = Some(self.bossbars.len() as i32);
+                    self.logger.log(
+                        Level::Info,
+                        &format!("Removed custom bossbar [{}]", removed.name.plain()),
+                    );
+                }
+            }
+            Bossbar::List => {
+                self.result = Some(self.bossbars.len() as i32);
+                let feedback = match self.bossbars.len() {
+                    0 => String::from("There are no custom bossbars active"),
+                    n => format!(
+                        "There are {} custom bossbar(s) active: {}",
+                        n,
+                        self.bossbars
+                            .values()
+                            .map(|bossbar| format!("[{}]", bossbar.name.plain()))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                };
+                self.logger.log(Level::Info, &feedback);
+            }
+            Bossbar::Get(id, value) => self.execute_bossbar_get(id, *value),
+            Bossbar::Set(id, setting) => self.execute_bossbar_set(id, setting),
+        }
+    }
+
+    fn execute_bossbar_get(&mut self, id: &str, value: BossbarGet) {
+        if !self.bossbar_exists(id) {
+            return;
+        }
+        let bossbar = &self.bossbars[&namespaced(id)];
+        let name = format!("[{}]", bossbar.name.plain());
+        let (result, feedback) = match value {
+            BossbarGet::Value => (
+                bossbar.value,
+                format!("Custom bossbar {} has a value of {}", name, bossbar.value),
+            ),
+            BossbarGet::Max => (
+                bossbar.max,
+                format!("Custom bossbar {} has a maximum of {}", name, bossbar.max),
+            ),
+            BossbarGet::Visible if bossbar.visible => {
+                (1, format!("Custom bossbar {} is currently shown", name))
+            }
+            BossbarGet::Visible => (0, format!("Custom bossbar {} is currently hidden", name)),
+            BossbarGet::Players => {
+                let online: Vec<&str> = bossbar
+                    .players
+                    .iter()
+                    .filter(|player| self.find_player(player).is_some())
+                    .map(String::as_str)
+                    .collect();
+                match online.len() {
+                    0 => (
+                        0,
+                        format!("Custom bossbar {} has no players currently online", name),
+                    ),
+                    n => (
+                        n as i32,
+                        format!(
+                            "Custom bossbar {} has {} player(s) currently online: {}",
+                            name,
+                            n,
+                            online.join(", ")
+                        ),
+                    ),
+                }
+            }
+        };
+        self.result = Some(result);
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn execute_bossbar_set(&mut self, id: &str, setting: &BossbarSet) {
+        if !self.bossbar_exists(id) {
+            return;
         }
+        let players = match setting {
+            BossbarSet::Players(Some(targets)) => self.get_player_names(targets),
+            _ => vec![],
+        };
+        let bossbar = self.bossbars.get_mut(&namespaced(id)).unwrap();
+        let name = format!("[{}]", bossbar.name.plain());
+        let outcome = match setting {
+            BossbarSet::Color(color) if *color == bossbar.color => {
+                Err("Nothing changed. That's already the color of this bossbar")
+            }
+            BossbarSet::Color(color) => {
+                bossbar.color = *color;
+                Ok((0, format!("Custom bossbar {} has changed color", name)))
+            }
+            BossbarSet::Max(max) if *max == bossbar.max => {
+                Err("Nothing changed. That's already the max of this bossbar")
+            }
+            BossbarSet::Max(max) => {
+                bossbar.max = *max;
+                Ok((
+                    *max,
+                    format!("Custom bossbar {} has changed maximum to {}", name, max),
+                ))
+            }
+            BossbarSet::Name(new_name) if *new_name == bossbar.name => {
+                Err("Nothing changed. That's already the name of this bossbar")
+            }
+            BossbarSet::Name(new_name) => {
+                bossbar.name = new_name.clone();
+                Ok((0, format!("Custom bossbar {} has been renamed", name)))
+            }
+            BossbarSet::Players(_) => {
+                let players: BTreeSet<String> = players.into_iter().collect();
+                if players == bossbar.players {
+                    Err("Nothing changed. Those players are already on the bossbar with nobody to add or remove")
+                } else {
+                    bossbar.players = players;
+                    let count = bossbar.players.len();
+                    Ok((
+                        count as i32,
+                        match count {
+                            0 => format!("Custom bossbar {} no longer has any players", name),
+                            n => format!(
+                                "Custom bossbar {} now has {} player(s): {}",
+                                name,
+                                n,
+                                bossbar
+                                    .players
+                                    .iter()
+                                    .map(String::as_str)
+                                    .collect::<Vec<&str>>()
+                                    .join(", ")
+                            ),
+                        },
+                    ))
+                }
+            }
+            BossbarSet::Style(style) if *style == bossbar.style => {
+                Err("Nothing changed. That's already the style of this bossbar")
+            }
+            BossbarSet::Style(style) => {
+                bossbar.style = *style;
+                Ok((0, format!("Custom bossbar {} has changed style", name)))
+            }
+            BossbarSet::Value(value) if *value == bossbar.value => {
+                Err("Nothing changed. That's already the value of this bossbar")
+            }
+            BossbarSet::Value(value) => {
+                bossbar.value = *value;
+                Ok((
+                    *value,
+                    format!("Custom bossbar {} has changed value to {}", name, value),
+                ))
+            }
+            BossbarSet::Visible(visible) if *visible == bossbar.visible => Err(match visible {
+                true => "Nothing changed. The bossbar is already visible",
+                false => "Nothing changed. The bossbar is already hidden",
+            }),
+            BossbarSet::Visible(visible) => {
+                bossbar.visible = *visible;
+                Ok((
+                    0,
+                    match visible {
+                        true => format!("Custom bossbar {} is now visible", name),
+                        false => format!("Custom bossbar {} is now hidden", name),
+                    },
+                ))
+            }
+        };
+        match outcome {
+            Ok((result, feedback)) => {
+                self.result = Some(result);
+                self.logger.log(Level::Info, &feedback);
+            }
+            Err(failure) => self.logger.log(Level::Fail, failure),
+        }
+    }
+
+    fn team_of(&self, holder: &ScoreHolder) -> Option<&str> {
+        self.teams
+            .iter()
+            .find(|(_, team)| team.members.contains(holder))
+            .map(|(name, _)| name.as_str())
+    }
+
+    fn team_color(&self, holder: &ScoreHolder) -> Option<Color> {
+        self.team_of(holder).and_then(|name| self.teams[name].color)
+    }
+
+    fn team_display_name(&self, name: &str) -> String {
+        format!("[{}]", self.teams[name].display_name.plain())
+    }
+
+    fn team_exists(&mut self, name: &str) -> bool {
+        let exists = self.teams.contains_key(name);
+        if !exists {
+            self.logger
+                .log(Level::Fail, &format!("Unknown team '{}'", name));
+        }
+        exists
+    }
+
+    fn execute_team(&mut self, team: &Team) {
+        match team {
+            Team::Add(add) => self.execute_team_add(add),
+            Team::Remove(name) => self.execute_team_remove(name),
+            Team::Empty(name) => self.execute_team_empty(name),
+            Team::Join(name, members) => self.execute_team_join(name, members.as_ref()),
+            Team::Leave(members) => self.execute_team_leave(members),
+            Team::List(name) => self.execute_team_list(name.as_deref()),
+            Team::Modify(modify) => self.execute_team_modify(modify),
+        }
+    }
+
+    fn execute_team_add(&mut self, add: &TeamAdd) {
+        if self.teams.contains_key(&add.team) {
+            self.logger
+                .log(Level::Fail, "A team already exists by that name");
+            return;
+        }
+        let display_name = match &add.display_name {
+            Some(display_name) => display_name.clone(),
+            None => TextComponent::text(&add.team),
+        };
+        self.teams
+            .insert(add.team.clone(), PlayerTeam::new(display_name));
+        let feedback = format!("Created team {}", self.team_display_name(&add.team));
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn execute_team_remove(&mut self, name: &str) {
+        if self.team_exists(name) {
+            let feedback = format!("Removed team {}", self.team_display_name(name));
+            self.teams.remove(name);
+            self.logger.log(Level::Info, &feedback);
+        }
+    }
+
+    fn execute_team_empty(&mut self, name: &str) {
+        if !self.team_exists(name) {
+            return;
+        }
+        let team = self.teams.get_mut(name).unwrap();
+        let removed = std::mem::take(&mut team.members).len();
+        let feedback = match removed {
+            0 => {
+                self.logger
+                    .log(Level::Fail, "Nothing changed. That team is already empty");
+                return;
+            }
+            n => format!(
+                "Removed {} member(s) from team {}",
+                n,
+                self.team_display_name(name)
+            ),
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn team_members(&mut self, members: Option<&Target>) -> Option<(Vec<ScoreHolder>, String)> {
+        let holders = match members {
+            Some(members) => self.get_score_holders(members),
+            None => self
+                .select(&Selector::new(SelectorVariable::S))
+                .into_iter()
+                .map(Entity::score_holder)
+                .collect(),
+        };
+        match holders.as_slice() {
+            [] => {
+                self.logger.log(Level::Fail, "No entity was found");
+                None
+            }
+            [holder] => {
+                let name = self.holder_name(holder);
+                Some((holders, name))
+            }
+            _ => {
+                let count = format!("{} members", holders.len());
+                Some((holders, count))
+            }
+        }
+    }
+
+    fn execute_team_join(&mut self, name: &str, members: Option<&Target>) {
+        if !self.team_exists(name) {
+            return;
+        }
+        let (holders, subject) = match self.team_members(members) {
+            Some(members) => members,
+            None => return,
+        };
+        for holder in holders {
+            for team in self.teams.values_mut() {
+                team.members.remove(&holder);
+            }
+            self.teams.get_mut(name).unwrap().members.insert(holder);
+        }
+        let feedback = format!("Added {} to team {}", subject, self.team_display_name(name));
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn execute_team_leave(&mut self, members: &Target) {
+        let (holders, subject) = match self.team_members(Some(members)) {
+            Some(members) => members,
+            None => return,
+        };
+        for holder in holders {
+            for team in self.teams.values_mut() {
+                team.members.remove(&holder);
+            }
+        }
+        self.logger
+            .log(Level::Info, &format!("Removed {} from any team", subject));
+    }
+
+    fn execute_team_list(&mut self, name: Option<&str>) {
+        let feedback = match name {
+            None if self.teams.is_empty() => String::from("There are no teams"),
+            None => format!(
+                "There are {} team(s): {}",
+                self.teams.len(),
+                self.teams
+                    .keys()
+                    .map(|name| self.team_display_name(name))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Some(name) => {
+                if !self.team_exists(name) {
+                    return;
+                }
+                let members: Vec<String> = self.teams[name]
+                    .members
+                    .iter()
+                    .map(|holder| self.holder_name(holder))
+                    .collect();
+                match members.len() {
+                    0 => format!(
+                        "There are no members on team {}",
+                        self.team_display_name(name)
+                    ),
+                    n => format!(
+                        "Team {} has {} member(s): {}",
+                        self.team_display_name(name),
+                        n,
+                        members.join(", ")
+                    ),
+                }
+            }
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn execute_team_modify(&mut self, modify: &TeamModify) {
+        if !self.team_exists(&modify.team) {
+            return;
+        }
+        let team = self.teams.get_mut(&modify.team).unwrap();
+        let display_name = format!("[{}]", team.display_name.plain());
+        let outcome = match &modify.option {
+            TeamOption::DisplayName(name) if *name == team.display_name => {
+                Err("Nothing changed. That team already has that name")
+            }
+            TeamOption::DisplayName(name) => {
+                team.display_name = name.clone();
+                Ok(format!("Updated the name of team [{}]", name.plain()))
+            }
+            TeamOption::Color(color) if *color == team.color => {
+                Err("Nothing changed. That team already has that color")
+            }
+            TeamOption::Color(color) => {
+                team.color = *color;
+                Ok(format!(
+                    "Updated the color for team {} to {}",
+                    display_name,
+                    color.map_or(String::from("reset"), |color| color.to_string())
+                ))
+            }
+            TeamOption::FriendlyFire(value) if *value == team.friendly_fire => Err(match value {
+                true => "Nothing changed. Friendly fire is already enabled for that team",
+                false => "Nothing changed. Friendly fire is already disabled for that team",
+            }),
+            TeamOption::FriendlyFire(value) => {
+                team.friendly_fire = *value;
+                Ok(match value {
+                    true => format!("Enabled friendly fire for team {}", display_name),
+                    false => format!("Disabled friendly fire for team {}", display_name),
+                })
+            }
+            TeamOption::SeeFriendlyInvisibles(value) if *value == team.see_friendly_invisibles => {
+                Err(match value {
+                    true => "Nothing changed. That team can already see invisible teammates",
+                    false => "Nothing changed. That team already can't see invisible teammates",
+                })
+            }
+            TeamOption::SeeFriendlyInvisibles(value) => {
+                team.see_friendly_invisibles = *value;
+                Ok(match value {
+                    true => format!("Team {} can now see invisible teammates", display_name),
+                    false => format!(
+                        "Team {} can no longer see invisible teammates",
+                        display_name
+                    ),
+                })
+            }
+            TeamOption::NametagVisibility(value) if *value == team.nametag_visibility => {
+                Err("Nothing changed. Nametag visibility is already that value")
+            }
+            TeamOption::NametagVisibility(value) => {
+                team.nametag_visibility = *value;
+                Ok(format!(
+                    "Nametag visibility for team {} is now \"{}\"",
+                    display_name,
+                    value.label()
+                ))
+            }
+            TeamOption::DeathMessageVisibility(value)
+                if *value == team.death_message_visibility =>
+            {
+                Err("Nothing changed. Death message visibility is already that value")
+            }
+            TeamOption::DeathMessageVisibility(value) => {
+                team.death_message_visibility = *value;
+                Ok(format!(
+                    "Death message visibility for team {} is now \"{}\"",
+                    display_name,
+                    value.label()
+                ))
+            }
+            TeamOption::CollisionRule(value) if *value == team.collision_rule => {
+                Err("Nothing changed. Collision rule is already that value")
+            }
+            TeamOption::CollisionRule(value) => {
+                team.collision_rule = *value;
+                Ok(format!(
+                    "Collision rule for team {} is now \"{}\"",
+                    display_name,
+                    value.label()
+                ))
+            }
+            TeamOption::Prefix(prefix) => {
+                team.prefix = prefix.clone();
+                Ok(format!("Team prefix set to {}", prefix.plain()))
+            }
+            TeamOption::Suffix(suffix) => {
+                team.suffix = suffix.clone();
+                Ok(format!("Team suffix set to {}", suffix.plain()))
+            }
+        };
+        match outcome {
+            Ok(feedback) => self.logger.log(Level::Info, &feedback),
+            Err(failure) => self.logger.log(Level::Fail, failure),
+        }
+    }
+
+    /// Lets the player running the command change a `trigger` objective they were enabled for,
+    /// locking it again until the next `scoreboard players enable`.
+    fn execute_trigger(&mut self, trigger: &Trigger) {
+        let holder = match &self.context.source {
+            Source::Entity(uuid) => self
+                .entity(*uuid)
+                .filter(|e| e.is_player())
+                .map(Entity::score_holder),
+            Source::Server => None,
+        };
+        let holder = match holder {
+            Some(holder) => holder,
+            None => {
+                self.logger
+                    .log(Level::Fail, "A player is required to run this command here");
+                return;
+            }
+        };
+        let objective = match self.objectives.get_mut(&trigger.objective) {
+            Some(objective) => objective,
+            None => {
+                self.logger.log(
+                    Level::Fail,
+                    &format!("Unknown scoreboard objective '{}'", trigger.objective),
+                );
+                return;
+            }
+        };
+        if objective.criteria != Criteria::Trigger {
+            self.logger.log(
+                Level::Fail,
+                "You can only trigger objectives that are 'trigger' type",
+            );
+            return;
+        }
+        if !objective.unlocked.remove(&holder) {
+            self.logger
+                .log(Level::Fail, "You cannot trigger this objective yet");
+            return;
+        }
+        let name = objective.display_name.plain();
+        let score = objective.data.entry(holder).or_insert(0);
+        let feedback = match trigger.action {
+            None => {
+                *score = score.wrapping_add(1);
+                format!("Triggered [{}]", name)
+            }
+            Some(TriggerAction::Add(value)) => {
+                *score = score.wrapping_add(value);
+                format!("Triggered [{}] (added {} to value)", name, value)
+            }
+            Some(TriggerAction::Set(value)) => {
+                *score = value;
+                format!("Triggered [{}] (set value to {})", name, value)
+            }
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn execute_give(&mut self, give: &Give) {
+        let players: Vec<(Uuid, String, [f64; 3])> = self
+            .get_entities(&give.targets)
+            .into_iter()
+            .filter_map(|e| Some((e.uuid, e.player_name.clone()?, e.position)))
+            .collect();
+        if players.is_empty() {
+            self.logger.log(Level::Fail, "No player was found");
+            return;
+        }
+        let count = give.count.unwrap_or(1);
+        let stack = ItemStack {
+            count,
+            ..give.item.namespaced()
+        };
+        let name = stack.display_name().plain();
+        let limit = stack.max_stack_size() * 100;
+        if count > limit {
+            self.logger.log(
+                Level::Fail,
+                &format!("Can't give more than {} of {}", limit, name),
+            );
+            return;
+        }
+        for (uuid, _, position) in &players {
+            let left_over = self.entity_mut(*uuid).unwrap().inventory.add(&stack);
+            if left_over > 0 {
+                let mut dropped = Entity::new(self.next_uuid(), "item");
+                dropped.position = *position;
+                dropped.dimension = self.entity(*uuid).unwrap().dimension.clone();
+                self.entities.push(dropped);
+            }
+        }
+        let feedback = match players.as_slice() {
+            [(_, player, _)] => format!("Gave {} {} to {}", count, name, player),
+            _ => format!("Gave {} {} to {} players", count, name, players.len()),
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn execute_clear(&mut self, clear: &Clear) {
+        let players: Vec<(Uuid, String)> = match &clear.targets {
+            Some(targets) => self.get_entities(targets),
+            None => self.select(&Selector::new(SelectorVariable::S)),
+        }
+        .into_iter()
+        .filter_map(|e| Some((e.uuid, e.player_name.clone()?)))
+        .collect();
+        if players.is_empty() {
+            let message = match clear.targets {
+                Some(_) => "No player was found",
+                None => "A player is required to run this command here",
+            };
+            self.logger.log(Level::Fail, message);
+            return;
+        }
+        let everything = ItemPredicate {
+            id: None,
+            tests: vec![],
+        };
+        let predicate = clear.item.as_ref().unwrap_or(&everything);
+        let mut total = 0;
+        for (uuid, _) in &players {
+            let inventory = &mut self.entity_mut(*uuid).unwrap().inventory;
+            total += match clear.max_count {
+                Some(0) => inventory
+                    .stacks()
+                    .into_iter()
+                    .filter(|(_, stack)| predicate.matches(stack))
+                    .map(|(_, stack)| stack.count)
+                    .sum(),
+                max => inventory.remove(predicate, max),
+            };
+        }
+        let subject = match players.as_slice() {
+            [(_, player)] => format!("player {}", player),
+            _ => format!("{} players", players.len()),
+        };
+        match (total, clear.max_count) {
+            (0, _) => self
+                .logger
+                .log(Level::Fail, &format!("No items were found on {}", subject)),
+            (_, Some(0)) => self.logger.log(
+                Level::Info,
+                &format!("Found {} matching item(s) on {}", total, subject),
+            ),
+            _ => self.logger.log(
+                Level::Info,
+                &format!("Removed {} item(s) from {}", total, subject),
+            ),
+        }
+    }
+
+    fn execute_item_replace(&mut self, replace: &ItemReplace) {
+        let stack = match &replace.source {
+            ItemSource::With(stack, count) => ItemStack {
+                count: count.unwrap_or(1),
+                ..stack.namespaced()
+            },
+            ItemSource::From(source, slot, modifier) => {
+                let source = match self.get_entities(source).as_slice() {
+                    [source] => (*source).clone(),
+                    [] => {
+                        self.logger.log(Level::Fail, "No entity was found");
+                        return;
+                    }
+                    _ => {
+                        self.logger.log(
+                            Level::Fail,
+                            "Only one entity is allowed, but the provided selector allows more than one",
+                        );
+                        return;
+                    }
+                };
+                let mut stack = item::slot(slot)
+                    .and_then(|slot| source.inventory.get(slot).cloned())
+                    .unwrap_or_else(|| ItemStack::new("minecraft:air", 0));
+                if let Some(modifier) = modifier {
+                    match self.item_modifier(modifier) {
+                        Some(modifier) => stack.modify(&modifier),
+                        None => return,
+                    }
+                }
+                stack
+            }
+        };
+        self.fill_slot(&replace.targets, &replace.slot, |_| Some(stack.clone()));
+    }
+
+    fn execute_item_modify(&mut self, modify: &ItemModify) {
+        let modifier = match self.item_modifier(&modify.modifier) {
+            Some(modifier) => modifier,
+            None => return,
+        };
+        self.fill_slot(&modify.targets, &modify.slot, |existing| {
+            let mut stack = existing?.clone();
+            stack.modify(&modifier);
+            Some(stack)
+        });
+    }
+
+    fn fill_slot<F>(&mut self, targets: &Target, slot: &str, stack: F)
+    where
+        F: Fn(Option<&ItemStack>) -> Option<ItemStack>,
+    {
+        let index = item::slot(slot).unwrap_or_default();
+        let targets: Vec<Uuid> = self
+            .get_entities(targets)
+            .into_iter()
+            .map(|e| e.uuid)
+            .collect();
+        if targets.is_empty() {
+            self.logger.log(Level::Fail, "No entity was found");
+            return;
+        }
+        let mut changed: Vec<(String, ItemStack)> = Vec::new();
+        for uuid in targets {
+            let entity = self.entity_mut(uuid).unwrap();
+            if !entity.has_slot(index) {
+                continue;
+            }
+            if let Some(stack) = stack(entity.inventory.get(index)) {
+                entity.inventory.set(index, stack.clone());
+                changed.push((entity.display_name().plain(), stack));
+            }
+        }
+        let feedback = match changed.as_slice() {
+            [] => {
+                self.logger.log(
+                    Level::Fail,
+                    &format!("No targets accepted item into slot {}", slot),
+                );
+                return;
+            }
+            [(name, stack)] => format!(
+                "Replaced a slot on {} with {}",
+                name,
+                stack.display_name().plain()
+            ),
+            [(_, stack), ..] => format!(
+                "Replaced a slot on {} entities with {}",
+                changed.len(),
+                stack.display_name().plain()
+            ),
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn item_modifier(&mut self, id: &str) -> Option<Json> {
+        let modifier = self
+            .datapack
+            .as_ref()
+            .and_then(|datapack| datapack.item_modifiers.get(&namespaced(id)).cloned());
+        if modifier.is_none() {
+            self.logger.log(
+                Level::Fail,
+                &format!("Unknown item modifier '{}'", namespaced(id)),
+            );
+        }
+        modifier
+    }
+
+    fn execute_place_template(&mut self, place: &PlaceTemplate) {
+        let id = namespaced(&place.template);
+        let template = match self.structure(&id) {
+            Ok(template) => template,
+            Err(message) => {
+                self.logger.log(Level::Fail, &message);
+                return;
+            }
+        };
+        let origin = match &place.position {
+            Some(position) => match self.loaded_block_pos(position) {
+                Some(origin) => origin,
+                None => return,
+            },
+            None => {
+                let [x, y, z] = self.context.position;
+                [x.floor() as i32, y.floor() as i32, z.floor() as i32]
+            }
+        };
+        let dimension = self.context.dimension.clone();
+        for (position, block) in &template.blocks {
+            let [x, y, z] = structure::transform(*position, place.mirror, place.rotation);
+            let target = [origin[0] + x, origin[1] + y, origin[2] + z];
+            if World::contains(&dimension, target) {
+                let block = structure::transform_block(block, place.mirror, place.rotation);
+                self.world.set(&dimension, target, &block);
+            }
+        }
+        for (position, data) in &template.entities {
+            let kind = match data.get("id").and_then(Nbt::as_str) {
+                Some(kind) if namespaced(kind) != "minecraft:player" => kind,
+                _ => continue,
+            };
+            let [x, y, z] = structure::transform_exact(*position, place.mirror, place.rotation);
+            let mut entity = Entity::new(self.next_uuid(), kind);
+            entity.apply_nbt(data);
+            entity.position = [
+                f64::from(origin[0]) + x,
+                f64::from(origin[1]) + y,
+                f64::from(origin[2]) + z,
+            ];
+            entity.dimension = dimension.clone();
+            self.entities.push(entity);
+        }
+        self.logger.log(
+            Level::Info,
+            &format!(
+                "Loaded template \"{}\" at {}, {}, {}",
+                id, origin[0], origin[1], origin[2]
+            ),
+        );
+    }
+
+    fn structure(&self, id: &str) -> Result<Structure, String> {
+        let missing = || format!("There is no template with id \"{}\"", id);
+        let (namespace, path) = id.split_once(':').ok_or_else(missing)?;
+        if let Some(directory) = &self.structure_directory {
+            let file = directory
+                .join(namespace)
+                .join("structures")
+                .join(format!("{}.nbt", path));
+            match fs::read(file) {
+                Ok(bytes) => {
+                    return Structure::load(&bytes)
+                        .map_err(|e| format!("Failed to load structure {}: {:?}", id, e))
+                }
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    return Err(format!("Failed to read structure {}: {}", id, e))
+                }
+                Err(_) => {}
+            }
+        }
+        self.datapack
+            .as_ref()
+            .and_then(|datapack| datapack.structures.get(id).cloned())
+            .ok_or_else(missing)
+    }
+
+    fn loaded_block_pos(&mut self, position: &BlockPos) -> Option<[i32; 3]> {
+        let position = self.context.resolve_block_pos(position);
+        if World::contains(&self.context.dimension, position) {
+            Some(position)
+        } else {
+            self.logger
+                .log(Level::Fail, "That position is out of this world!");
+            None
+        }
+    }
+
+    fn check_volume(&mut self, low: [i32; 3], high: [i32; 3]) -> bool {
+        let limit = i64::from(self.gamerules.int(GameRule::CommandModificationBlockLimit));
+        let volume: i64 = (0..3)
+            .map(|axis| i64::from(high[axis] - low[axis]) + 1)
+            .product();
+        if volume > limit {
+            self.logger.log(
+                Level::Fail,
+                &format!(
+                    "Too many blocks in the specified area (maximum {}, specified {})",
+                    limit, volume
+                ),
+            );
+        }
+        volume <= limit
+    }
+
+    fn get_player_names(&self, target: &Target) -> Vec<String> {
+        self.get_entities(target)
+            .into_iter()
+            .filter_map(|e| e.player_name.clone())
+            .collect()
+    }
+
+    fn source_name(&self) -> TextComponent {
+        match self.context.source {
+            Source::Entity(uuid) => self
+                .entity(uuid)
+                .map(Entity::display_name)
+                .unwrap_or_else(|| TextComponent::text(&uuid.to_string())),
+            Source::Server => TextComponent::text("Server"),
+        }
+    }
+
+    fn execute_function(&mut self, function: &FunctionIdentifier) {
+        let datapack = self.datapack.as_ref().unwrap();
+        match datapack.functions.iter().find(|f| {
+            let fi = &f.identifier;
+            fi.name == function.name && fi.namespace == function.namespace
+        }) {
+            Some(function) => {
+                let outermost = self.chain_length.is_none();
+                let limit = self.gamerules.int(GameRule::MaxCommandChainLength);
+                for command in &function.commands {
+                    let run = self.chain_length.unwrap_or(0);
+                    if run >= limit {
+                        break;
+                    }
+                    self.chain_length = Some(run + 1);
+                    self.execute(command);
+                }
+                if outermost {
+                    self.chain_length = None;
+                    self.route_feedback();
+                }
+            }
+            None => self.logger.log(
+                Level::Fail,
+                &format!(
+                    "Unknown function {}:{}",
+                    match &function.namespace {
+                        Some(namespace) => namespace,
+                        None => "minecraft",
+                    },
+                    function.name
+                ),
+            ),
+        }
+    }
+
+    fn execute_execute(&mut self, execute: &Execute) {
+        match execute {
+            Execute::If(i) => self.execute_execute_if(i, true),
+            Execute::Unless(i) => self.execute_execute_if(i, false),
+            Execute::Store(s) => self.execute_execute_store(s),
+        }
+    }
+
+    /// Runs `command`, returning its result: the value it set, or 1 if it succeeded without
+    /// setting one. Failed commands return `None`.
+    fn run_for_result(&mut self, command: &Command) -> Option<i32> {
+        let failed = std::mem::replace(&mut self.logger.failed, false);
+        let previous = self.result.take();
+        self.execute(command);
+        let result = match self.logger.failed {
+            true => None,
+            false => Some(self.result.unwrap_or(1)),
+        };
+        self.logger.failed |= failed;
+        self.result = previous;
+        result
+    }
+
+    fn execute_execute_store(&mut self, store: &Store) {
+        let holders = match &store.target {
+            StoreTarget::Bossbar(id, _) => {
+                if !self.bossbar_exists(id) {
+                    return;
+                }
+                vec![]
+            }
+            StoreTarget::Score(targets, objective) => {
+                if !self.objectives.contains_key(objective) {
+                    self.logger.log(
+                        Level::Fail,
+                        &format!("Unknown scoreboard objective '{}'", objective),
+                    );
+                    return;
+                }
+                if self.reject_read_only(objective) {
+                    return;
+                }
+                self.get_score_holders(targets)
+            }
+        };
+        let result = self.run_for_result(&store.command);
+        let value = match store.kind {
+            StoreKind::Result => result.unwrap_or(0),
+            StoreKind::Success => result.is_some() as i32,
+        };
+        match &store.target {
+            StoreTarget::Bossbar(id, field) => {
+                if let Some(bossbar) = self.bossbars.get_mut(&namespaced(id)) {
+                    match field {
+                        BossbarValue::Value => bossbar.value = value,
+                        BossbarValue::Max => bossbar.max = value.max(1),
+                    }
+                }
+            }
+            StoreTarget::Score(_, objective) => {
+                if let Some(objective) = self.objectives.get_mut(objective) {
+                    for holder in holders {
+                        objective.data.insert(holder, value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn execute_execute_if(&mut self, i: &If, expected: bool) {
+        match i {
+            If::Score(s) => self.execute_execute_if_score(s, expected),
+            If::Block(b) => self.execute_execute_if_block(b, expected),
+            If::Blocks(b) => self.execute_execute_if_blocks(b, expected),
+            If::Items(i) => self.execute_execute_if_items(i, expected),
+            If::Predicate(p) => self.execute_execute_if_predicate(p, expected),
+        }
+    }
+
+    fn execute_execute_if_score(&mut self, score: &Score, expected: bool) {
+        if let Score::Matches(rng_cmp) = score {
+            self.execute_execute_if_matches(rng_cmp, expected)
+        }
+    }
+
+    fn execute_execute_if_matches(&mut self, rng_cmp: &RangeComparison, expected: bool) {
+        let holder = match self.found_score_holders(&rng_cmp.target) {
+            Some(holders) => holders.into_iter().next().unwrap(),
+            None => return,
+        };
+        match self.objectives.get(&rng_cmp.target_objective) {
+            Some(objective) => {
+                let matched = objective
+                    .data
+                    .get(&holder)
+                    .map(|score| self.does_match(*score, &rng_cmp.interval))
+                    .unwrap_or(false);
+                if matched == expected {
+                    self.execute(&rng_cmp.command);
+                }
+            }
+            None => self.logger.log(
+                Level::Fail,
+                &format!(
+                    "Unknown scoreboard objective '{}'",
+                    rng_cmp.target_objective
+                ),
+            ),
+        }
+    }
+
+    fn execute_execute_if_block(&mut self, condition: &BlockCondition, expected: bool) {
+        let position = match self.loaded_block_pos(&condition.position) {
+            Some(position) => position,
+            None => return,
+        };
+        let block = self.world.get(&self.context.dimension, position);
+        if block.matches(&condition.block) == expected {
+            self.execute(&condition.command);
+        }
+    }
+
+    fn execute_execute_if_blocks(&mut self, condition: &BlocksCondition, expected: bool) {
+        let (start, end, destination) = match (
+            self.loaded_block_pos(&condition.start),
+            self.loaded_block_pos(&condition.end),
+            self.loaded_block_pos(&condition.destination),
+        ) {
+            (Some(start), Some(end), Some(destination)) => (start, end, destination),
+            _ => return,
+        };
+        let (low, high) = corners(start, end);
+        if !self.check_volume(low, high) {
+            return;
+        }
+        let dimension = &self.context.dimension;
+        let matched = positions(low, high).all(|position| {
+            let source = self.world.get(dimension, position);
+            if condition.mode == BlocksMode::Masked && source.is_air() {
+                return true;
+            }
+            let offset = [
+                destination[0] + position[0] - low[0],
+                destination[1] + position[1] - low[1],
+                destination[2] + position[2] - low[2],
+            ];
+            source == self.world.get(dimension, offset)
+        });
+        if matched == expected {
+            self.execute(&condition.command);
+        }
+    }
+
+    fn execute_execute_if_items(&mut self, condition: &ItemsCondition, expected: bool) {
+        let slots = item::slot_range(&condition.slots).unwrap_or_default();
+        let count: i32 = self
+            .get_entities(&condition.targets)
+            .iter()
+            .map(|entity| entity.inventory.count(&slots, &condition.predicate))
+            .sum();
+        if (count > 0) == expected {
+            self.execute(&condition.command);
+        }
+    }
+
+    fn execute_execute_if_predicate(&mut self, condition: &PredicateCondition, expected: bool) {
+        let id = namespaced(&condition.predicate);
+        let predicate = match self.predicate(&id) {
+            Some(predicate) => predicate,
+            None => {
+                self.logger
+                    .log(Level::Fail, &format!("Unknown predicate: {}", id));
+                return;
+            }
+        };
+        let this = match self.context.source {
+            Source::Entity(uuid) => self.entity(uuid),
+            Source::Server => None,
+        };
+        if self.test_condition(predicate, this, self.context.position, &mut vec![]) == expected {
+            self.execute(&condition.command);
+        }
+    }
+
+    fn predicate(&self, id: &str) -> Option<&'a Json> {
+        let datapack: &'a Option<Datapack> = self.datapack;
+        datapack
+            .as_ref()
+            .and_then(|datapack| datapack.predicates.get(id))
+    }
+
+    /// Whether a predicate's condition holds, with `this` the entity it is about and `origin`
+    /// where it is tested. A list of conditions holds when all of them do. Unknown conditions
+    /// never hold, and neither does a `reference` back to a predicate still being tested.
+    fn test_condition(
+        &self,
+        condition: &Json,
+        this: Option<&Entity>,
+        origin: [f64; 3],
+        references: &mut Vec<String>,
+    ) -> bool {
+        if let Some(conditions) = condition.as_array() {
+            return conditions
+                .iter()
+                .all(|condition| self.test_condition(condition, this, origin, references));
+        }
+        let terms = condition
+            .get("terms")
+            .and_then(Json::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let kind = condition
+            .get("condition")
+            .and_then(Json::as_str)
+            .map(namespaced)
+            .unwrap_or_default();
+        match kind.as_str() {
+            "minecraft:all_of" => terms
+                .iter()
+                .all(|term| self.test_condition(term, this, origin, references)),
+            "minecraft:any_of" => terms
+                .iter()
+                .any(|term| self.test_condition(term, this, origin, references)),
+            "minecraft:inverted" => condition
+                .get("term")
+                .is_some_and(|term| !self.test_condition(term, this, origin, references)),
+            "minecraft:reference" => {
+                let id = match condition.get("name").and_then(Json::as_str) {
+                    Some(name) => namespaced(name),
+                    None => return false,
+                };
+                let predicate = match self.predicate(&id) {
+                    Some(predicate) if !references.contains(&id) => predicate,
+                    _ => return false,
+                };
+                references.push(id);
+                let passed = self.test_condition(predicate, this, origin, references);
+                references.pop();
+                passed
+            }
+            "minecraft:random_chance" => {
+                let chance = condition
+                    .get("chance")
+                    .map_or(0.0, |chance| self.number(chance, this));
+                self.random_chance(chance)
+            }
+            "minecraft:value_check" => {
+                let value = condition
+                    .get("value")
+                    .map_or(0.0, |value| self.number(value, this));
+                self.in_int_range(value.round() as i32, condition.get("range"), this)
+            }
+            "minecraft:time_check" => {
+                let time = match condition.get("period").and_then(Json::as_f64) {
+                    Some(period) if period >= 1.0 => self.day_time % period as i64,
+                    _ => self.day_time,
+                };
+                self.in_int_range(time as i32, condition.get("value"), this)
+            }
+            "minecraft:weather_check" => {
+                let is = |key, actual| {
+                    condition
+                        .get(key)
+                        .and_then(Json::as_bool)
+                        .is_none_or(|expected| expected == actual)
+                };
+                is("raining", self.weather != WeatherKind::Clear)
+                    && is("thundering", self.weather == WeatherKind::Thunder)
+            }
+            "minecraft:entity_scores" => match condition_entity(condition, this) {
+                Some(entity) => match condition.get("scores") {
+                    Some(Json::Object(scores)) => scores.iter().all(|(objective, range)| {
+                        self.score(&entity.score_holder(), objective)
+                            .is_some_and(|score| self.in_int_range(score, Some(range), this))
+                    }),
+                    _ => true,
+                },
+                None => false,
+            },
+            "minecraft:entity_properties" => match condition_entity(condition, this) {
+                Some(entity) => condition
+                    .get("predicate")
+                    .is_none_or(|predicate| self.entity_matches(entity, predicate, origin)),
+                None => condition.get("predicate").is_none(),
+            },
+            _ => false,
+        }
+    }
+
+    fn number(&self, provider: &Json, this: Option<&Entity>) -> f32 {
+        if let Some(number) = provider.as_f64() {
+            return number as f32;
+        }
+        let field = |key| {
+            provider
+                .get(key)
+                .map_or(0.0, |value| self.number(value, this))
+        };
+        let kind = provider
+            .get("type")
+            .and_then(Json::as_str)
+            .map_or_else(|| String::from("minecraft:uniform"), namespaced);
+        match kind.as_str() {
+            "minecraft:constant" => field("value"),
+            "minecraft:uniform" => {
+                let (min, max) = (field("min"), field("max"));
+                min + self.random.borrow_mut().next_float() * (max - min)
+            }
+            "minecraft:binomial" => {
+                let (n, p) = (field("n").round() as i32, field("p"));
+                (0..n).filter(|_| self.random_chance(p)).count() as f32
+            }
+            "minecraft:score" => {
+                let holder = match provider.get("target") {
+                    Some(Json::String(target)) => context_holder(target, this),
+                    Some(target) => {
+                        let kind = target.get("type").and_then(Json::as_str).map(namespaced);
+                        match kind.as_deref() {
+                            Some("minecraft:fixed") => target
+                                .get("name")
+                                .and_then(Json::as_str)
+                                .map(ScoreHolder::from_name),
+                            Some("minecraft:context") => target
+                                .get("target")
+                                .and_then(Json::as_str)
+                                .and_then(|target| context_holder(target, this)),
+                            _ => None,
+                        }
+                    }
+                    None => None,
+                };
+                let objective = provider
+                    .get("score")
+                    .and_then(Json::as_str)
+                    .unwrap_or_default();
+                let scale = provider.get("scale").and_then(Json::as_f64).unwrap_or(1.0);
+                holder
+                    .and_then(|holder| self.score(&holder, objective))
+                    .map_or(0.0, |score| (f64::from(score) * scale) as f32)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Whether `value` is in an int range: a number it must equal, or an object whose `min` and
+    /// `max` number providers bound it. A missing range holds every value.
+    fn in_int_range(&self, value: i32, range: Option<&Json>, this: Option<&Entity>) -> bool {
+        match range {
+            None => true,
+            Some(Json::Number(number)) => value == *number as i32,
+            Some(range) => {
+                let bound = |key| {
+                    range
+                        .get(key)
+                        .map(|bound| self.number(bound, this).round() as i32)
+                };
+                bound("min").is_none_or(|min| min <= value)
+                    && bound("max").is_none_or(|max| value <= max)
+            }
+        }
+    }
+
+    /// Whether the entity fits the parts of an entity predicate that are known here: `type`,
+    /// `team`, `distance` from `origin`, and `location`'s `dimension` and `position`. Other
+    /// parts are ignored.
+    fn entity_matches(&self, entity: &Entity, predicate: &Json, origin: [f64; 3]) -> bool {
+        let kind = match predicate.get("type") {
+            Some(Json::String(kind)) => entity.kind == namespaced(kind),
+            Some(Json::Array(kinds)) => kinds
+                .iter()
+                .filter_map(Json::as_str)
+                .any(|kind| entity.kind == namespaced(kind)),
+            _ => true,
+        };
+        let team = predicate
+            .get("team")
+            .and_then(Json::as_str)
+            .is_none_or(|team| self.team_of(&entity.score_holder()) == Some(team));
+        let offset = [
+            entity.position[0] - origin[0],
+            entity.position[1] - origin[1],
+            entity.position[2] - origin[2],
+        ];
+        let near = predicate.get("distance").is_none_or(|range| {
+            let horizontal = (offset[0] * offset[0] + offset[2] * offset[2]).sqrt();
+            in_float_range(distance(entity.position, origin), range.get("absolute"))
+                && in_float_range(horizontal, range.get("horizontal"))
+                && in_float_range(offset[0].abs(), range.get("x"))
+                && in_float_range(offset[1].abs(), range.get("y"))
+                && in_float_range(offset[2].abs(), range.get("z"))
+        });
+        let located = predicate.get("location").is_none_or(|location| {
+            let dimension = location
+                .get("dimension")
+                .and_then(Json::as_str)
+                .is_none_or(|dimension| entity.dimension == namespaced(dimension));
+            let position = location.get("position");
+            let axis = |key| position.and_then(|position| position.get(key));
+            dimension
+                && in_float_range(entity.position[0], axis("x"))
+                && in_float_range(entity.position[1], axis("y"))
+                && in_float_range(entity.position[2], axis("z"))
+        });
+        kind && team && near && located
+    }
+
+    fn does_match(&self, value: i32, interval: &Interval) -> bool {
+        match interval {
+            Interval::Value(v) => value == *v,
+            Interval::Bounded(a, b) => *a <= value && value <= *b,
+            Interval::LeftUnbounded(b) => value <= *b,
+            Interval::RightUnbounded(a) => *a <= value,
+        }
+    }
+
+    fn execute_tellraw(&mut self, tellraw: &Tellraw) {
+        let recipients = self.get_player_names(&tellraw.target);
+        if recipients.is_empty() {
+            self.logger.log(Level::Fail, "No player was found");
+            return;
+        }
+        self.deliver(recipients, Channel::Chat, &tellraw.message);
+    }
+
+    /// Resolves `component` for each recipient and hands it to the chat. Score components
+    /// naming `*` resolve differently for each recipient, so recipients are grouped by the
+    /// message they end up seeing.
+    fn deliver(&mut self, recipients: Vec<String>, channel: Channel, component: &TextComponent) {
+        let mut deliveries: Vec<(Message, Vec<String>)> = Vec::new();
+        for recipient in recipients {
+            let message = Message::new(self.resolve_component(component, Some(&recipient)));
+            match deliveries.iter_mut().find(|(m, _)| *m == message) {
+                Some((_, players)) => players.push(recipient),
+                None => deliveries.push((message, vec![recipient])),
+            }
+        }
+        for (message, players) in deliveries {
+            self.chat.tell(players, channel, &message);
+        }
+    }
+
+    fn broadcast(&mut self, message: Message) {
+        let everyone = self.get_player_names(&Target::Selector(Selector::new(SelectorVariable::A)));
+        self.chat.tell(everyone, Channel::Chat, &message);
+    }
+
+    fn execute_say(&mut self, say: &Say) {
+        let component = TextComponent::translatable(
+            "chat.type.announcement",
+            "[%s] %s",
+            vec![self.source_name(), self.expand_message(&say.message)],
+        );
+        self.broadcast(Message::new(component));
+    }
+
+    fn execute_me(&mut self, me: &Me) {
+        let component = TextComponent::translatable(
+            "chat.type.emote",
+            "* %s %s",
+            vec![self.source_name(), self.expand_message(&me.action)],
+        );
+        self.broadcast(Message::new(component));
+    }
+
+    fn execute_tell(&mut self, tell: &Tell) {
+        let recipients = self.get_player_names(&tell.targets);
+        if recipients.is_empty() {
+            self.logger.log(Level::Fail, "No player was found");
+            return;
+        }
+        let message = self.expand_message(&tell.message);
+        for recipient in recipients {
+            let incoming = whisper(
+                "commands.message.display.incoming",
+                "%s whispers to you: %s",
+                self.source_name(),
+                message.clone(),
+            );
+            self.chat.tell(
+                vec![recipient.clone()],
+                Channel::Chat,
+                &Message::new(incoming),
+            );
+            let outgoing = Message::new(whisper(
+                "commands.message.display.outgoing",
+                "You whisper to %s: %s",
+                TextComponent::text(&recipient),
+                message.clone(),
+            ));
+            match self.context.source {
+                Source::Server => self.logger.log(Level::Info, &outgoing.text),
+                Source::Entity(uuid) => {
+                    if let Some(sender) = self.entity(uuid).and_then(|e| e.player_name.clone()) {
+                        self.chat.tell(vec![sender], Channel::Chat, &outgoing)
+                    }
+                }
+            }
+        }
+    }
+
+    fn execute_title(&mut self, title: &Title) {
+        let recipients = self.get_player_names(&title.targets);
+        if recipients.is_empty() {
+            self.logger.log(Level::Fail, "No player was found");
+            return;
+        }
+        let subject = match recipients.as_slice() {
+            [single] => single.clone(),
+            _ => format!("{} players", recipients.len()),
+        };
+        let feedback = match &title.action {
+            TitleAction::Title(component) => {
+                self.deliver(recipients, Channel::Title, component);
+                format!("Showing new title for {}", subject)
+            }
+            TitleAction::Subtitle(component) => {
+                self.deliver(recipients, Channel::Subtitle, component);
+                format!("Showing new subtitle for {}", subject)
+            }
+            TitleAction::Actionbar(component) => {
+                self.deliver(recipients, Channel::Actionbar, component);
+                format!("Showing new actionbar title for {}", subject)
+            }
+            TitleAction::Times {
+                fade_in,
+                stay,
+                fade_out,
+            } => {
+                let times = TitleTimes {
+                    fade_in: *fade_in,
+                    stay: *stay,
+                    fade_out: *fade_out,
+                };
+                for recipient in recipients {
+                    self.title_times.insert(recipient, times);
+                }
+                format!("Changing title display times for {}", subject)
+            }
+            TitleAction::Clear => format!("Cleared titles for {}", subject),
+            TitleAction::Reset => {
+                for recipient in &recipients {
+                    self.title_times.remove(recipient);
+                }
+                format!("Reset title options for {}", subject)
+            }
+        };
+        self.logger.log(Level::Info, &feedback);
+    }
+
+    fn expand_message(&self, message: &str) -> TextComponent {
+        let mut expanded = TextComponent::text("");
+        let mut literal = String::new();
+        for (i, word) in message.split(' ').enumerate() {
+            if i > 0 {
+                literal.push(' ');
+            }
+            match word.starts_with('@').then(|| parse::parse_target(word)) {
+                Some(Ok(target)) => {
+                    expanded.extra.push(TextComponent::text(&literal));
+                    literal.clear();
+                    let names = self.get_entity_names(&target);
+                    expanded.extra.push(TextComponent::text(&names.join(", ")));
+                }
+                _ => literal.push_str(word),
+            }
+        }
+        if expanded.extra.is_empty() {
+            return TextComponent::text(&literal);
+        }
+        expanded.extra.push(TextComponent::text(&literal));
+        expanded.extra.retain(|part| !part.plain().is_empty());
+        expanded
+    }
+
+    fn resolve_component(
+        &self,
+        component: &TextComponent,
+        recipient: Option<&str>,
+    ) -> TextComponent {
+        let (content, mut extra) = match &component.content {
+            Content::Score {
+                name,
+                objective,
+                value,
+            } => (
+                Content::Text(match value {
+                    Some(value) => value.clone(),
+                    None => self.resolve_score(name, objective, recipient),
+                }),
+                vec![],
+            ),
+            Content::Selector {
+                selector,
+                separator,
+            } => (
+                Content::Text(String::new()),
+                self.join(
+                    self.get_entities(selector)
+                        .into_iter()
+                        .map(Entity::display_name)
+                        .collect(),
+                    separator,
+                    selector_separator(),
+                    recipient,
+                ),
+            ),
+            Content::Nbt {
+                path,
+                source,
+                interpret,
+                separator,
+            } => (
+                Content::Text(String::new()),
+                self.join(
+                    self.resolve_nbt(path, source, *interpret, recipient),
+                    separator,
+                    TextComponent::text(", "),
+                    recipient,
+                ),
+            ),
+            Content::Translatable {
+                key,
+                fallback,
+                with,
+            } => (
+                Content::Translatable {
+                    key: key.clone(),
+                    fallback: fallback.clone(),
+                    with: with
+                        .iter()
+                        .map(|arg| self.resolve_component(arg, recipient))
+                        .collect(),
+                },
+                vec![],
+            ),
+            other => (other.clone(), vec![]),
+        };
+        extra.extend(
+            component
+                .extra
+                .iter()
+                .map(|child| self.resolve_component(child, recipient)),
+        );
+        TextComponent {
+            content,
+            style: component.style.clone(),
+            extra,
+        }
+    }
+
+    fn resolve_score(&self, name: &Target, objective: &str, recipient: Option<&str>) -> String {
+        let holder = match name {
+            Target::Name(name) if name == "*" => recipient.map(ScoreHolder::from_name),
+            Target::Name(name) => Some(ScoreHolder::from_name(name)),
+            Target::Selector(_) => match self.get_entities(name).as_slice() {
+                [single] => Some(single.score_holder()),
+                _ => None,
+            },
+        };
+        holder
+            .and_then(|holder| {
+                self.objectives
+                    .get(objective)
+                    .and_then(|o| o.data.get(&holder))
+            })
+            .map(i32::to_string)
+            .unwrap_or_default()
+    }
+
+    fn resolve_nbt(
+        &self,
+        path: &str,
+        source: &NbtSource,
+        interpret: bool,
+        recipient: Option<&str>,
+    ) -> Vec<TextComponent> {
+        let root = match source {
+            NbtSource::Storage(id) => self.storage.get(&namespaced(id)),
+            NbtSource::Block(_) | NbtSource::Entity(_) => None,
+        };
+        let path = match Path::parse(path) {
+            Ok(path) => path,
+            Err(_) => return vec![],
+        };
+        root.map(|root| path.get(root))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tag| match interpret {
+                true => text::parse(&tag.as_text())
+                    .map(|c| self.resolve_component(&c, recipient))
+                    .unwrap_or_else(|_| TextComponent::text(&tag.as_text())),
+                false => TextComponent::text(&tag.as_text()),
+            })
+            .collect()
+    }
+
+    fn join(
+        &self,
+        parts: Vec<TextComponent>,
+        separator: &Option<Box<TextComponent>>,
+        default: TextComponent,
+        recipient: Option<&str>,
+    ) -> Vec<TextComponent> {
+        let separator = match separator {
+            Some(separator) => self.resolve_component(separator, recipient),
+            None => default,
+        };
+        let mut joined = Vec::new();
+        for (i, part) in parts.into_iter().enumerate() {
+            if i > 0 {
+                joined.push(separator.clone());
+            }
+            joined.push(part);
+        }
+        joined
+    }
+
+    fn get_entity_names(&self, target: &Target) -> Vec<String> {
+        self.get_entities(target)
+            .into_iter()
+            .map(|e| e.display_name().plain())
+            .collect()
+    }
+}
+
+fn corners(a: [i32; 3], b: [i32; 3]) -> ([i32; 3], [i32; 3]) {
+    (
+        [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])],
+        [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])],
+    )
+}
+
+/// Every block position in a box, in x, then z, then y order like vanilla's iteration.
+fn positions(low: [i32; 3], high: [i32; 3]) -> impl Iterator<Item = [i32; 3]> {
+    (low[1]..=high[1]).flat_map(move |y| {
+        (low[2]..=high[2]).flat_map(move |z| (low[0]..=high[0]).map(move |x| [x, y, z]))
+    })
+}
+
+fn facing_rotation(from: [f64; 3], to: [f64; 3]) -> [f32; 2] {
+    let [dx, dy, dz] = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+    let horizontal = (dx * dx + dz * dz).sqrt();
+    [
+        wrap_degrees(dz.atan2(dx).to_degrees() - 90.0) as f32,
+        wrap_degrees(-dy.atan2(horizontal).to_degrees()) as f32,
+    ]
+}
+
+fn wrap_degrees(degrees: f64) -> f64 {
+    (degrees + 180.0).rem_euclid(360.0) - 180.0
+}
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// Whether `position` lies in the block-aligned box spanned from `origin` by `size`, which like
+/// vanilla's `dx`/`dy`/`dz` reaches one block past the far corner.
+fn in_volume(position: [f64; 3], origin: [f64; 3], size: [f64; 3]) -> bool {
+    (0..3).all(|axis| {
+        let (low, high) = if size[axis] < 0.0 {
+            (origin[axis] + size[axis], origin[axis] + 1.0)
+        } else {
+            (origin[axis], origin[axis] + size[axis] + 1.0)
+        };
+        low <= position[axis] && position[axis] <= high
+    })
+}
+
+fn whisper(key: &str, pattern: &str, name: TextComponent, message: TextComponent) -> TextComponent {
+    let mut component = TextComponent::translatable(key, pattern, vec![name, message]);
+    component.style.color = Some(text::Color::Gray);
+    component.style.italic = Some(true);
+    component
+}
+
+fn selector_separator() -> TextComponent {
+    let mut separator = TextComponent::text(", ");
+    separator.style.color = Some(text::Color::Gray);
+    separator
+}
+
+fn condense_display_name(
+    objective_name: &str,
+    display_name: Option<&TextComponent>,
+) -> TextComponent {
+    match display_name {
+        Some(name) => name.clone(),
+        None => TextComponent::text(objective_name),
+    }
+}
+
+fn space_separate<Iter: Iterator<Item = String>>(strings: Iter) -> String {
+    let mut output = String::new();
+    strings.for_each(|s| output.push_str(&format!(" [{}]", s)));
+    output
+}
+
+/// The ids of the advancements a selection names, each after its parent, or the id of an
+/// advancement it names that doesn't exist.
+fn selected_advancements(
+    selection: &AdvancementSelection,
+    nodes: &BTreeMap<String, &AdvancementNode>,
+) -> Result<Vec<String>, String> {
+    let (id, before, after) = match selection {
+        AdvancementSelection::Everything => return Ok(nodes.keys().cloned().collect()),
+        AdvancementSelection::Only(id, _) => (id, false, false),
+        AdvancementSelection::From(id) => (id, false, true),
+        AdvancementSelection::Until(id) => (id, true, false),
+        AdvancementSelection::Through(id) => (id, true, true),
+    };
+    let id = namespaced(id);
+    if !nodes.contains_key(&id) {
+        return Err(id);
+    }
+    let mut ids = vec![id.clone()];
+    if before {
+        let mut parent = nodes[&id].parent.as_ref();
+        while let Some(id) = parent.filter(|id| nodes.contains_key(*id) && !ids.contains(id)) {
+            ids.insert(0, id.clone());
+            parent = nodes[id].parent.as_ref();
+        }
+    }
+    if after {
+        let mut index = ids.len() - 1;
+        while index < ids.len() {
+            let children: Vec<String> = nodes
+                .iter()
+                .filter(|(child, node)| {
+                    node.parent.as_ref() == Some(&ids[index]) && !ids.contains(child)
+                })
+                .map(|(child, _)| child.clone())
+                .collect();
+            ids.extend(children);
+            index += 1;
+        }
+    }
+    Ok(ids)
+}
+
+fn item_matches(predicate: Option<&Json>, item: &str) -> bool {
+    match predicate.and_then(|predicate| predicate.get("items")) {
+        Some(Json::String(id)) => namespaced(id) == namespaced(item),
+        Some(Json::Array(ids)) => ids
+            .iter()
+            .filter_map(Json::as_str)
+            .any(|id| namespaced(id) == namespaced(item)),
+        _ => true,
+    }
+}
+
+fn condition_entity<'e>(condition: &Json, this: Option<&'e Entity>) -> Option<&'e Entity> {
+    match condition.get("entity").and_then(Json::as_str) {
+        Some("this") => this,
+        _ => None,
+    }
+}
+
+fn context_holder(target: &str, this: Option<&Entity>) -> Option<ScoreHolder> {
+    match target {
+        "this" => this.map(Entity::score_holder),
+        _ => None,
+    }
+}
+
+fn in_float_range(value: f64, range: Option<&Json>) -> bool {
+    match range {
+        None => true,
+        Some(Json::Number(number)) => value == *number,
+        Some(range) => {
+            range
+                .get("min")
+                .and_then(Json::as_f64)
+                .is_none_or(|min| min <= value)
+                && range
+                    .get("max")
+                    .and_then(Json::as_f64)
+                    .is_none_or(|max| value <= max)
+        }
+    }
+}
+
+fn bounds(range: &Interval) -> (i32, i32) {
+    match *range {
+        Interval::Value(value) => (value, value),
+        Interval::Bounded(min, max) => (min, max),
+        Interval::LeftUnbounded(max) => (i32::MIN, max),
+        Interval::RightUnbounded(min) => (min, i32::MAX),
+    }
+}
+
+fn slot_contains(slot: Option<&Option<String>>, value: &str) -> bool {
+    match slot {
+        Some(Some(x)) => x == value,
+        _ => false,
+    }
+}
+
+fn get_operation(operation_type: &OperationType) -> Box<dyn Fn(i32, i32) -> (i32, i32)> {
+    match operation_type {
+        OperationType::Addition => Box::new(|a, b| (a + b, b)),
+        OperationType::Subtraction => Box::new(|a, b| (a - b, b)),
+        OperationType::Multiplication => Box::new(|a, b| (a * b, b)),
+        OperationType::Division => Box::new(|a, b| (a / b, b)),
+        OperationType::Modulus => Box::new(|a, b| (a % b, b)),
+        OperationType::Assign => Box::new(|_, b| (b, b)),
+        OperationType::Min => Box::new(|a, b| (cmp::min(a, b), b)),
+        OperationType::Max => Box::new(|a, b| (cmp::max(a, b), b)),
+        OperationType::Swap => Box::new(|a, b| (b, a)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::collections::VecDeque;
+    use std::hash::Hash;
+
+    struct LoggerSpy {
+        messages: VecDeque<(Level, String)>,
+        admin: Vec<String>,
+    }
+
+    impl LoggerSpy {
+        fn new() -> LoggerSpy {
+            LoggerSpy {
+                messages: VecDeque::new(),
+                admin: vec![],
+            }
+        }
+
+        fn assert_logged(&mut self, level: Level, message: &str) {
+            match self.messages.pop_front() {
+                Some(msg) => {
+                    assert_eq!(level, msg.0);
+                    assert_eq!(message, &msg.1);
+                }
+                None => panic!("assertion failed: no messages in the log"),
+            }
+        }
+
+        fn assert_matches<F>(&mut self, condition: F)
+        where
+            F: Fn(Level, &str) -> bool,
+        {
+            match self.messages.pop_front() {
+                Some(msg) => assert!(condition(msg.0, &msg.1)),
+                None => panic!("assertion failed: no messages in the log"),
+            }
+        }
+
+        fn assert_no_logs(&mut self) {
+            assert!(self.messages.is_empty());
+        }
+
+        fn skip(&mut self) {
+            self.messages.pop_front();
+        }
+    }
+
+    impl Log for LoggerSpy {
+        fn log(&mut self, level: Level, message: &str) {
+            self.messages.push_back((level, String::from(message)));
+        }
+
+        fn log_admin(&mut self, sender: &str, message: &str) {
+            self.admin.push(format!("[{}: {}]", sender, message));
+        }
+    }
+
+    fn holder(name: &str) -> ScoreHolder {
+        ScoreHolder::from_name(name)
+    }
+
+    struct NullChat {}
+
+    impl Chat for NullChat {
+        fn tell(&mut self, _players: Vec<String>, _channel: Channel, _message: &Message) {}
+    }
+
+    fn is_anagram<T>(a: Vec<T>, b: Vec<T>) -> bool
+    where
+        T: Hash + Eq,
+    {
+        let mut counts: HashMap<&T, i32> = HashMap::new();
+        for item in &a {
+            counts.entry(item).and_modify(|e| *e += 1).or_insert(1);
+        }
+        for item in &b {
+            counts.entry(item).and_modify(|e| *e -= 1).or_insert(-1);
+        }
+        counts.values().all(|v| *v == 0)
+    }
+
+    #[test]
+    fn scoreboard_objectives_add_no_display() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: None,
+        })));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&command);
+        assert!(game.objectives.contains_key("obj"));
+        logger.assert_logged(Level::Info, "Created new objective [obj]");
+    }
+
+    #[test]
+    fn scoreboard_objectives_add_with_display() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("obj name")),
+        })));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&command);
+        assert!(game.objectives.contains_key("obj"));
+        logger.assert_logged(Level::Info, "Created new objective [obj name]");
+    }
+
+    #[test]
+    fn scoreboard_objectives_add_twice() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("obj name")),
+        })));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&command);
+        game.execute(&command);
+        assert!(game.objectives.contains_key("obj"));
+        logger.assert_logged(Level::Info, "Created new objective [obj name]");
+        logger.assert_logged(Level::Fail, "An objective already exists by that name");
+    }
+
+    #[test]
+    fn scoreboard_objectives_add_same_display() {
+        let command1 =
+            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj1"),
+                criteria: Criteria::Dummy,
+                display_name: Some(TextComponent::text("display name")),
+            })));
+        let command2 =
+            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj2"),
+                criteria: Criteria::Dummy,
+                display_name: Some(TextComponent::text("display name")),
+            })));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&command1);
+        game.execute(&command2);
+        assert!(game.objectives.contains_key("obj1"));
+        assert!(game.objectives.contains_key("obj2"));
+        logger.assert_logged(Level::Info, "Created new objective [display name]");
+        logger.assert_logged(Level::Info, "Created new objective [display name]");
+    }
+
+    #[test]
+    fn scoreboard_objectives_list_0_objectives() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::List));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&command);
+        logger.assert_logged(Level::Info, "There are no objectives");
+    }
+
+    #[test]
+    fn scoreboard_objectives_list_1_objective() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::List));
+        let add_command =
+            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj"),
+                criteria: Criteria::Dummy,
+                display_name: Some(TextComponent::text("obj name")),
+            })));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add_command);
+        game.execute(&command);
+        logger.skip();
+        logger.assert_logged(Level::Info, "There are 1 objectives: [obj name]");
+    }
+
+    #[test]
+    fn scoreboard_objectives_list_many_objectives() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::List));
+        let add_first =
+            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj1"),
+                criteria: Criteria::Dummy,
+                display_name: None,
+            })));
+        let add_second =
+            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj2"),
+                criteria: Criteria::Dummy,
+                display_name: None,
+            })));
+        let add_third =
+            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj3"),
+                criteria: Criteria::Dummy,
+                display_name: None,
+            })));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add_first);
+        game.execute(&add_second);
+        game.execute(&add_third);
+        game.execute(&command);
+        logger.skip();
+        logger.skip();
+        logger.skip();
+        logger.assert_matches(|level, message: &str| {
+            level == Level::Info
+                && message.starts_with("There are 3 objectives: ")
+                && is_anagram(
+                    message[(message.find(":").unwrap() + 2)..]
+                        .split(" ")
+                        .collect(),
+                    vec!["[obj1]", "[obj2]", "[obj3]"],
+                )
+        });
+    }
+
+    #[test]
+    fn scoreboard_objectives_modify_display_name() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("prev display name")),
+        })));
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
+            ObjectivesModify {
+                objective: String::from("obj"),
+                modification: Modification::DisplayName(TextComponent::text("new display name")),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        game.execute(&command);
+        assert_eq!(
+            game.objectives.get("obj").unwrap().display_name,
+            TextComponent::text("new display name")
+        );
+        logger.skip();
+        logger.assert_logged(
+            Level::Info,
+            "Changed objective obj display name to [new display name]",
+        );
+    }
+
+    #[test]
+    fn scoreboard_objectives_modify_display_name_no_objective() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
+            ObjectivesModify {
+                objective: String::from("obj"),
+                modification: Modification::DisplayName(TextComponent::text("new display name")),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&command);
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn scoreboard_objectives_modify_display_name_no_change() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("display name")),
+        })));
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
+            ObjectivesModify {
+                objective: String::from("obj"),
+                modification: Modification::DisplayName(TextComponent::text("display name")),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        game.execute(&command);
+        logger.skip();
+        logger.assert_no_logs();
+    }
+
+    #[test]
+    fn scoreboard_objectives_remove_existing() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("display name")),
+        })));
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Remove(
+            ObjectivesRemove {
+                objective: String::from("obj"),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        game.execute(&command);
+        assert!(!game.objectives.contains_key("obj"));
+        logger.skip();
+        logger.assert_logged(Level::Info, "Removed objective [display name]");
+    }
+
+    #[test]
+    fn scoreboard_objectives_remove_nothing() {
+        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Remove(
+            ObjectivesRemove {
+                objective: String::from("obj"),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&command);
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn initial_render_type() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: None,
+        })));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        assert_eq!(
+            game.objectives.get("obj").unwrap().render_type,
+            RenderType::Integer
+        );
+    }
+
+    #[test]
+    fn scoreboard_objectives_modify_render_type() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("display name")),
+        })));
+        let modify = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
+            ObjectivesModify {
+                objective: String::from("obj"),
+                modification: Modification::RenderType(RenderType::Hearts),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        game.execute(&modify);
+        assert_eq!(
+            game.objectives.get("obj").unwrap().render_type,
+            RenderType::Hearts
+        );
+        logger.skip();
+        logger.assert_logged(Level::Info, "Changed objective [display name] render type");
+    }
+
+    #[test]
+    fn scoreboard_objectives_modify_render_type_no_objective() {
+        let modify = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
+            ObjectivesModify {
+                objective: String::from("obj"),
+                modification: Modification::RenderType(RenderType::Integer),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&modify);
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn scoreboard_objectives_modify_render_type_no_change() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("display name")),
+        })));
+        let modify = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
+            ObjectivesModify {
+                objective: String::from("obj"),
+                modification: Modification::RenderType(RenderType::Integer),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        game.execute(&modify);
+        logger.skip();
+        logger.assert_no_logs();
+    }
+
+    #[test]
+    fn scoreboard_objectives_set_display() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("display name")),
+        })));
+        let set_display = Command::Scoreboard(Scoreboard::Objectives(Objectives::SetDisplay(
+            ObjectivesSetDisplay {
+                slot: DisplaySlot::Sidebar,
+                objective: String::from("obj"),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        game.execute(&set_display);
+        assert_eq!(
+            game.displays.get(&DisplaySlot::Sidebar).unwrap(),
+            &Some(String::from("obj"))
+        );
+        logger.skip();
+        logger.assert_logged(
+            Level::Info,
+            "Set display slot sidebar to show objective display name",
+        );
+    }
+
+    #[test]
+    fn scoreboard_objectives_set_display_no_objective() {
+        let set_display = Command::Scoreboard(Scoreboard::Objectives(Objectives::SetDisplay(
+            ObjectivesSetDisplay {
+                slot: DisplaySlot::BelowName,
+                objective: String::from("obj"),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&set_display);
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn scoreboard_objectives_set_display_twice() {
+        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+            objective: String::from("obj"),
+            criteria: Criteria::Dummy,
+            display_name: Some(TextComponent::text("display name")),
+        })));
+        let set_display = Command::Scoreboard(Scoreboard::Objectives(Objectives::SetDisplay(
+            ObjectivesSetDisplay {
+                slot: DisplaySlot::Sidebar,
+                objective: String::from("obj"),
+            },
+        )));
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&add);
+        game.execute(&set_display);
+        game.execute(&set_display);
+        logger.skip();
+        logger.skip();
+        logger.assert_logged(
+            Level::Fail,
+            "Nothing changed. That display slot is already showing that objective",
+        );
+    }
+
+    #[test]
+    fn add_player_to_game() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("player1");
+        let uuid = game.add_player("player1");
+        assert_eq!(game.entities.len(), 1);
+        let player = game.entity(uuid).unwrap();
+        assert_eq!(player.player_name, Some(String::from("player1")));
+        assert_eq!(player.kind, "minecraft:player");
+        assert_eq!(player.score_holder(), holder("player1"));
+    }
+
+    #[test]
+    fn scoreboard_players_add_player_not_ingame() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("player1");
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj"),
+                criteria: Criteria::Dummy,
+                display_name: Some(TextComponent::text("display name")),
+            }),
+        )));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Add(
+            PlayersAdd {
+                targets: Target::Name(String::from("player1")),
+                objective: String::from("obj"),
+                score: 11,
+            },
+        ))));
+        assert_eq!(
+            game.objectives
+                .get("obj")
+                .unwrap()
+                .data
+                .get(&holder("player1"))
+                .unwrap(),
+            &11
+        );
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Add(
+            PlayersAdd {
+                targets: Target::Name(String::from("player1")),
+                objective: String::from("obj"),
+                score: 4,
+            },
+        ))));
+        assert_eq!(
+            game.objectives
+                .get("obj")
+                .unwrap()
+                .data
+                .get(&holder("player1"))
+                .unwrap(),
+            &15
+        );
+        logger.skip();
+        logger.assert_logged(
+            Level::Info,
+            "Added 11 to [display name] for player1 (now 11)",
+        );
+        logger.assert_logged(
+            Level::Info,
+            "Added 4 to [display name] for player1 (now 15)",
+        );
+    }
+
+    #[test]
+    fn scoreboard_players_add_nonexistant_objective() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Add(
+            PlayersAdd {
+                targets: Target::Name(String::from("player")),
+                objective: String::from("obj"),
+                score: 16,
+            },
+        ))));
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn scoreboard_players_remove() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("player1");
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj"),
+                criteria: Criteria::Dummy,
+                display_name: Some(TextComponent::text("display name")),
+            }),
+        )));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Remove(
+            PlayersRemove {
+                targets: Target::Name(String::from("player1")),
+                objective: String::from("obj"),
+                score: 5,
+            },
+        ))));
+        assert_eq!(
+            game.objectives
+                .get("obj")
+                .unwrap()
+                .data
+                .get(&holder("player1"))
+                .unwrap(),
+            &-5
+        );
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Remove(
+            PlayersRemove {
+                targets: Target::Name(String::from("player1")),
+                objective: String::from("obj"),
+                score: 4,
+            },
+        ))));
+        assert_eq!(
+            game.objectives
+                .get("obj")
+                .unwrap()
+                .data
+                .get(&holder("player1"))
+                .unwrap(),
+            &-9
+        );
+        logger.skip();
+        logger.assert_logged(
+            Level::Info,
+            "Removed 5 from [display name] for player1 (now -5)",
+        );
+        logger.assert_logged(
+            Level::Info,
+            "Removed 4 from [display name] for player1 (now -9)",
+        );
+    }
+
+    #[test]
+    fn scoreboard_players_remove_nonexistant_objective() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Remove(
+            PlayersRemove {
+                targets: Target::Name(String::from("player")),
+                objective: String::from("obj"),
+                score: 16,
+            },
+        ))));
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn scoreboard_players_set() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj"),
+                criteria: Criteria::Dummy,
+                display_name: Some(TextComponent::text("display name")),
+            }),
+        )));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
+            PlayersSet {
+                targets: Target::Name(String::from("player")),
+                objective: String::from("obj"),
+                score: -23,
+            },
+        ))));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], -23);
+        logger.skip();
+        logger.assert_logged(Level::Info, "Set [display name] for player to -23");
+    }
+
+    #[test]
+    fn scoreboard_players_set_no_objective() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
+            PlayersSet {
+                targets: Target::Name(String::from("player")),
+                objective: "obj".to_string(),
+                score: -8564,
+            },
+        ))));
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn scoreboard_players_no_entity() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&parsed("scoreboard objectives add obj dummy"));
+        game.logger.skip();
+        for line in &[
+            "scoreboard players set @e[tag=none] obj 1",
+            "scoreboard players add @e[tag=none] obj 1",
+            "scoreboard players remove @e[tag=none] obj 1",
+            "scoreboard players operation Alice obj += @e[tag=none] obj",
+            "scoreboard players operation @e[tag=none] obj += Alice obj",
+            "execute if score @e[tag=none] obj matches 1 run say hi",
+        ] {
+            game.execute(&parsed(line));
+            game.logger
+                .assert_logged(Level::Fail, "No entity was found");
+        }
+        assert!(game.objectives["obj"].data.is_empty());
+    }
+
+    fn operate<S: Chat>(
+        game: &mut Game<LoggerSpy, S>,
+        target_score: i32,
+        source_score: i32,
+        operation: OperationType,
+    ) {
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj"),
+                criteria: Criteria::Dummy,
+                display_name: Some(TextComponent::text("display name")),
+            }),
+        )));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
+            PlayersSet {
+                targets: Target::Name(String::from("target")),
+                objective: String::from("obj"),
+                score: target_score,
+            },
+        ))));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
+            PlayersSet {
+                targets: Target::Name(String::from("source")),
+                objective: String::from("obj"),
+                score: source_score,
+            },
+        ))));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(
+            Players::Operation(PlayersOperation {
+                targets: Target::Name(String::from("target")),
+                target_objective: String::from("obj"),
+                operation,
+                source: Target::Name(String::from("source")),
+                source_objective: String::from("obj"),
+            }),
+        )));
+        game.logger.skip();
+        game.logger.skip();
+        game.logger.skip();
+    }
+
+    #[test]
+    fn scoreboard_players_operation_addition() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 5, 3, OperationType::Addition);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 8);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 8");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_subtraction() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 6, 2, OperationType::Subtraction);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 4);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 4");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_multiplication() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 3, 4, OperationType::Multiplication);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 12);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 12");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_division() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 14, 3, OperationType::Division);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 4);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 4");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_modulus() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 17, 5, OperationType::Modulus);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 2);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 2");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_assign() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 12, -5, OperationType::Assign);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], -5);
+        logger.assert_logged(Level::Info, "Set [display name] for target to -5");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_min_source_less() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 12, -5, OperationType::Min);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], -5);
+        logger.assert_logged(Level::Info, "Set [display name] for target to -5");
+    }
+
+    // TODO: Investigate log behavior
+    #[test]
+    fn scoreboard_players_operation_min_source_more() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 12, 500, OperationType::Min);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 12);
+    }
+
+    #[test]
+    fn scoreboard_players_operation_max_source_less() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 12, -5, OperationType::Max);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 12);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 12");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_max_source_more() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, 12, 500, OperationType::Max);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 500);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 500");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_swap() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        operate(&mut game, -5, 7, OperationType::Swap);
+        assert_eq!(game.objectives["obj"].data[&holder("target")], 7);
+        assert_eq!(game.objectives["obj"].data[&holder("source")], -5);
+        logger.assert_logged(Level::Info, "Set [display name] for target to 7");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_no_target_objective() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: "sourceObj".to_string(),
+                criteria: Criteria::Dummy,
+                display_name: None,
+            }),
+        )));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(
+            Players::Operation(PlayersOperation {
+                targets: Target::Name("a".to_string()),
+                target_objective: "targetObj".to_string(),
+                operation: OperationType::Addition,
+                source: Target::Name("b".to_string()),
+                source_objective: "sourceObj".to_string(),
+            }),
+        )));
+        logger.skip();
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'targetObj'");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_no_source_objective() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: "targetObj".to_string(),
+                criteria: Criteria::Dummy,
+                display_name: None,
+            }),
+        )));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(
+            Players::Operation(PlayersOperation {
+                targets: Target::Name("a".to_string()),
+                target_objective: "targetObj".to_string(),
+                operation: OperationType::Addition,
+                source: Target::Name("b".to_string()),
+                source_objective: "sourceObj".to_string(),
+            }),
+        )));
+        logger.skip();
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'sourceObj'");
+    }
+
+    #[test]
+    fn scoreboard_players_operation_no_objectives() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Players(
+            Players::Operation(PlayersOperation {
+                targets: Target::Name("a".to_string()),
+                target_objective: "targetObj".to_string(),
+                operation: OperationType::Addition,
+                source: Target::Name("b".to_string()),
+                source_objective: "sourceObj".to_string(),
+            }),
+        )));
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'targetObj'");
+    }
+
+    #[test]
+    fn function_with_namespace() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        let datapack = Datapack {
+            name: String::from("datapack"),
+            functions: vec![Function {
+                identifier: FunctionIdentifier {
+                    namespace: Some(String::from("namespace")),
+                    name: String::from("func"),
+                },
+                commands: vec![
+                    Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
+                        objective: String::from("obj"),
+                        criteria: Criteria::Dummy,
+                        display_name: None,
+                    }))),
+                    Command::Scoreboard(Scoreboard::Players(Players::Add(PlayersAdd {
+                        targets: Target::Name(String::from("player")),
+                        objective: String::from("obj"),
+                        score: 7,
+                    }))),
+                ],
+            }],
+            ..Default::default()
+        };
+        let datapack = Some(datapack);
+        game.datapack = &datapack;
+        game.execute(&Command::Function(FunctionIdentifier {
+            namespace: Some(String::from("namespace")),
+            name: String::from("func"),
+        }));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], 7);
+    }
+
+    #[test]
+    fn no_function() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        let datapack = Some(Datapack {
+            name: "datapack".to_string(),
+            ..Default::default()
+        });
+        game.datapack = &datapack;
+        game.execute(&Command::Function(FunctionIdentifier {
+            namespace: Some(String::from("namespace")),
+            name: String::from("func"),
+        }));
+        logger.assert_logged(Level::Fail, "Unknown function namespace:func");
+    }
+
+    #[test]
+    fn execute_if_score_matches_value_no_match() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        compare_match(&mut game, 7, 8, Interval::Value(-55));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], 7);
+    }
+
+    #[test]
+    fn execute_if_score_matches_value_matches() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        compare_match(&mut game, 7, 8, Interval::Value(7));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], 8);
+    }
+
+    #[test]
+    fn execute_if_score_matches_bounded_range_min() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        compare_match(&mut game, -3, 7, Interval::Bounded(-3, 5));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], 7);
+    }
+
+    #[test]
+    fn execute_if_score_matches_bounded_range_max() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        compare_match(&mut game, 5, 7, Interval::Bounded(-3, 5));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], 7);
+    }
+
+    #[test]
+    fn execute_if_score_matches_bounded_range_middle() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        compare_match(&mut game, 0, 7, Interval::Bounded(-3, 5));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], 7);
+    }
+
+    #[test]
+    fn execute_if_score_matches_bounded_range_not_in_range() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        compare_match(&mut game, -20, 7, Interval::Bounded(-3, 5));
+        assert_eq!(game.objectives["obj"].data[&holder("player")], -20);
+    }
+
+    #[test]
+    fn execute_if_matches_no_objective() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Execute(Execute::If(If::Score(Score::Matches(
+            RangeComparison {
+                target: Target::Name(String::from("player")),
+                target_objective: String::from("obj"),
+                interval: Interval::Value(5),
+                command: Box::new(Command::Scoreboard(Scoreboard::Objectives(
+                    Objectives::List,
+                ))),
+            },
+        )))));
+        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+    }
+
+    #[test]
+    fn execute_if_matches_no_player() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj"),
+                criteria: Criteria::Dummy,
+                display_name: None,
+            }),
+        )));
+        game.execute(&Command::Execute(Execute::If(If::Score(Score::Matches(
+            RangeComparison {
+                target: Target::Name(String::from("player")),
+                target_objective: String::from("obj"),
+                interval: Interval::Value(0),
+                command: Box::new(Command::Scoreboard(Scoreboard::Players(Players::Set(
+                    PlayersSet {
+                        targets: Target::Name("player".to_string()),
+                        objective: "obj".to_string(),
+                        score: 10,
+                    },
+                )))),
+            },
+        )))));
+        assert!(!game.objectives["obj"].data.contains_key(&holder("player")));
+    }
+
+    fn compare_match<T: Log, S: Chat>(
+        game: &mut Game<T, S>,
+        start_score: i32,
+        new_score: i32,
+        interval: Interval,
+    ) {
+        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
+            Objectives::Add(ObjectivesAdd {
+                objective: String::from("obj"),
+                criteria: Criteria::Dummy,
+                display_name: None,
+            }),
+        )));
+        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
+            PlayersSet {
+                objective: String::from("obj"),
+                score: start_score,
+                targets: Target::Name(String::from("player")),
+            },
+        ))));
+        game.execute(&Command::Execute(Execute::If(If::Score(Score::Matches(
+            RangeComparison {
+                target: Target::Name(String::from("player")),
+                target_objective: String::from("obj"),
+                interval,
+                command: Box::new(Command::Scoreboard(Scoreboard::Players(Players::Set(
+                    PlayersSet {
+                        objective: String::from("obj"),
+                        score: new_score,
+                        targets: Target::Name(String::from("player")),
+                    },
+                )))),
+            },
+        )))));
+    }
+
+    struct ChatSpy {
+        last_message: Option<Message>,
+    }
+
+    impl ChatSpy {
+        fn new() -> ChatSpy {
+            ChatSpy { last_message: None }
+        }
+    }
+
+    impl Chat for ChatSpy {
+        fn tell(&mut self, _players: Vec<String>, _channel: Channel, message: &Message) {
+            self.last_message = Some(message.clone())
+        }
+    }
+
+    #[test]
+    fn tellraw() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = ChatSpy::new();
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("player");
+        game.execute(&Command::Tellraw(Tellraw {
+            target: Target::Name("player".to_string()),
+            message: TextComponent::text("it's a message!"),
+        }));
+        assert_eq!(
+            chat.last_message.map(|m| m.text),
+            Some("it's a message!".to_string())
+        )
+    }
+
+    fn tellraw_text(game: &mut Game<LoggerSpy, ChatSpy>, json: &str) {
+        game.execute(&Command::Tellraw(Tellraw {
+            target: Target::Selector(Selector::new(SelectorVariable::A)),
+            message: text::parse(json).unwrap(),
+        }));
+    }
+
+    #[test]
+    fn tellraw_score_component() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = ChatSpy::new();
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("player");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.set_player_score("player", "obj", 7);
+        tellraw_text(
+            &mut game,
+            "[\"Score: \",{\"score\":{\"name\":\"player\",\"objective\":\"obj\"}},\
+             {\"score\":{\"name\":\"nobody\",\"objective\":\"obj\"}},\
+             {\"score\":{\"name\":\"player\",\"objective\":\"obj\",\"value\":\"!\"}}]",
+        );
+        assert_eq!(
+            chat.last_message.map(|m| m.text),
+            Some("Score: 7!".to_string())
+        );
+    }
+
+    #[test]
+    fn tellraw_score_component_keeps_style() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = ChatSpy::new();
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("player");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.set_player_score("player", "obj", -3);
+        tellraw_text(
+            &mut game,
+            "{\"score\":{\"name\":\"player\",\"objective\":\"obj\"},\"color\":\"gold\"}",
+        );
+        let mut expected = TextComponent::text("-3");
+        expected.style.color = Some(text::Color::Gold);
+        assert_eq!(chat.last_message.unwrap().component, expected);
+    }
+
+    #[test]
+    fn tellraw_selector_component() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = ChatSpy::new();
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("Bob");
+        game.add_player("Alice");
+        tellraw_text(&mut game, "{\"selector\":\"@a\"}");
+        let message = game.chat.last_message.take().unwrap();
+        assert_eq!(message.text, "Alice, Bob");
+        assert_eq!(
+            message.component.extra[1].style.color,
+            Some(text::Color::Gray)
+        );
+        tellraw_text(&mut game, "{\"selector\":\"@a\",\"separator\":\" & \"}");
+        assert_eq!(
+            game.chat.last_message.take().map(|m| m.text),
+            Some("Alice & Bob".to_string())
+        );
+        tellraw_text(&mut game, "{\"selector\":\"@s\"}");
+        assert_eq!(
+            game.chat.last_message.take().map(|m| m.text),
+            Some("".to_string())
+        );
+    }
+
+    #[test]
+    fn tellraw_nbt_storage_component() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = ChatSpy::new();
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("player");
+        game.set_storage(
+            "ns:store",
+            nbt::parse("{names:[\"a\",\"b\"],count:3b,json:'{\"text\":\"hi\",\"bold\":true}'}")
+                .unwrap(),
+        );
+        tellraw_text(&mut game, "{\"nbt\":\"names[]\",\"storage\":\"ns:store\"}");
+        let message = game.chat.last_message.take().unwrap();
+        assert_eq!(message.text, "a, b");
+        assert_eq!(message.component.extra[1].style.color, None);
+        tellraw_text(&mut game, "{\"nbt\":\"count\",\"storage\":\"ns:store\"}");
+        assert_eq!(
+            game.chat.last_message.take().map(|m| m.text),
+            Some("3b".to_string())
+        );
+        tellraw_text(
+            &mut game,
+            "{\"nbt\":\"json\",\"storage\":\"ns:store\",\"interpret\":true}",
+        );
+        let message = game.chat.last_message.take().unwrap();
+        assert_eq!(message.text, "hi");
+        assert_eq!(message.component.extra[0].style.bold, Some(true));
+        tellraw_text(&mut game, "{\"nbt\":\"names\",\"storage\":\"ns:other\"}");
+        assert_eq!(
+            game.chat.last_message.take().map(|m| m.text),
+            Some("".to_string())
+        );
+    }
+
+    #[test]
+    fn tellraw_delivers_to_targets_only() {
+        let mut logger = LoggerSpy::new();
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&Command::Tellraw(Tellraw {
+            target: Target::Name("Alice".to_string()),
+            message: TextComponent::text("psst"),
+        }));
+        tellraw_text_inbox(&mut game, "\"everyone\"");
+        assert_eq!(inbox.texts("Alice"), vec!["psst", "everyone"]);
+        assert_eq!(inbox.texts("Bob"), vec!["everyone"]);
+    }
+
+    #[test]
+    fn tellraw_no_player() {
+        let mut logger = LoggerSpy::new();
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.execute(&Command::Tellraw(Tellraw {
+            target: Target::Name("Alice".to_string()),
+            message: TextComponent::text("psst"),
+        }));
+        logger.assert_logged(Level::Fail, "No player was found");
+        assert!(inbox.messages("Alice").is_empty());
     }
 
-    impl Log for LoggerSpy {
-        fn log(&mut self, level: Level, message: &str) {
-            self.messages.push_back((level, String::from(message)));
-        }
+    #[test]
+    fn tellraw_score_component_per_recipient() {
+        let mut logger = LoggerSpy::new();
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.set_player_score("Alice", "obj", 1);
+        game.set_player_score("Bob", "obj", 2);
+        tellraw_text_inbox(
+            &mut game,
+            "[\"You have \",{\"score\":{\"name\":\"*\",\"objective\":\"obj\"}}]",
+        );
+        assert_eq!(inbox.texts("Alice"), vec!["You have 1"]);
+        assert_eq!(inbox.texts("Bob"), vec!["You have 2"]);
     }
 
-    struct NullChat {}
-
-    impl Chat for NullChat {
-        fn tell(&mut self, players: Vec<String>, message: &str) {
-            ()
-        }
+    fn tellraw_text_inbox(game: &mut Game<LoggerSpy, Inbox>, json: &str) {
+        game.execute(&Command::Tellraw(Tellraw {
+            target: Target::Selector(Selector::new(SelectorVariable::A)),
+            message: text::parse(json).unwrap(),
+        }));
     }
 
-    fn is_anagram<T>(a: Vec<T>, b: Vec<T>) -> bool
-    where
-        T: Hash + Eq,
-    {
-        let mut counts: HashMap<&T, i32> = HashMap::new();
-        for item in &a {
-            counts.entry(item).and_modify(|e| *e += 1).or_insert(1);
-        }
-        for item in &b {
-            counts.entry(item).and_modify(|e| *e -= 1).or_insert(-1);
-        }
-        counts.values().all(|v| *v == 0)
+    fn parsed(line: &str) -> Command {
+        parse::parse_line(line).unwrap()
     }
 
     #[test]
-    fn scoreboard_objectives_add_no_display() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: None,
-        })));
+    fn say_from_server_and_player() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&command);
-        assert!(game.objectives.get("obj").is_some());
-        logger.assert_logged(Level::Info, "Created new objective [obj]");
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed("say hello everyone"));
+        game.execute_as("Alice", &parsed("say hi @a"));
+        game.execute_as("Bob", &parsed("me waves at @s"));
+        game.player_chat("Alice", "hey");
+        assert_eq!(
+            inbox.texts("Bob"),
+            vec![
+                "[Server] hello everyone",
+                "[Alice] hi Alice, Bob",
+                "* Bob waves at Bob",
+                "<Alice> hey"
+            ]
+        );
+        assert_eq!(inbox.texts("Alice"), inbox.texts("Bob"));
     }
 
     #[test]
-    fn scoreboard_objectives_add_with_display() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("obj name")),
-        })));
+    fn tell_from_server() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&command);
-        assert!(game.objectives.get("obj").is_some());
-        logger.assert_logged(Level::Info, "Created new objective [obj name]");
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed("msg Alice meet me at spawn"));
+        logger.assert_logged(Level::Info, "You whisper to Alice: meet me at spawn");
+        assert_eq!(
+            inbox.texts("Alice"),
+            vec!["Server whispers to you: meet me at spawn"]
+        );
+        let whisper = &inbox.messages("Alice")[0].component;
+        assert_eq!(whisper.style.color, Some(text::Color::Gray));
+        assert_eq!(whisper.style.italic, Some(true));
+        assert!(inbox.messages("Bob").is_empty());
     }
 
     #[test]
-    fn scoreboard_objectives_add_twice() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("obj name")),
-        })));
+    fn tell_from_player() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&command);
-        game.execute(&command);
-        assert!(game.objectives.get("obj").is_some());
-        logger.assert_logged(Level::Info, "Created new objective [obj name]");
-        logger.assert_logged(Level::Fail, "An objective already exists by that name");
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute_as("Bob", &parsed("w Alice psst"));
+        game.execute(&parsed("tell Carol hello"));
+        logger.assert_logged(Level::Fail, "No player was found");
+        assert_eq!(inbox.texts("Alice"), vec!["Bob whispers to you: psst"]);
+        assert_eq!(inbox.texts("Bob"), vec!["You whisper to Alice: psst"]);
     }
 
     #[test]
-    fn scoreboard_objectives_add_same_display() {
-        let command1 =
-            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj1"),
-                criteria: Criteria::Dummy,
-                display_name: Some(String::from("display name")),
-            })));
-        let command2 =
-            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj2"),
-                criteria: Criteria::Dummy,
-                display_name: Some(String::from("display name")),
-            })));
+    fn title_channels() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&command1);
-        game.execute(&command2);
-        assert!(game.objectives.get("obj1").is_some());
-        assert!(game.objectives.get("obj2").is_some());
-        logger.assert_logged(Level::Info, "Created new objective [display name]");
-        logger.assert_logged(Level::Info, "Created new objective [display name]");
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed(
+            "title @a title {\"text\":\"Round 1\",\"bold\":true}",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Showing new title for 2 players");
+        game.execute(&parsed("title Alice subtitle \"Fight!\""));
+        game.logger
+            .assert_logged(Level::Info, "Showing new subtitle for Alice");
+        game.execute(&parsed("title Bob actionbar \"3 lives\""));
+        game.logger
+            .assert_logged(Level::Info, "Showing new actionbar title for Bob");
+        game.execute(&parsed("title @a clear"));
+        game.logger
+            .assert_logged(Level::Info, "Cleared titles for 2 players");
+        let texts = |player, channel| -> Vec<String> {
+            inbox
+                .received(player, channel)
+                .iter()
+                .map(|m| m.text.clone())
+                .collect()
+        };
+        assert_eq!(texts("Alice", Channel::Title), vec!["Round 1"]);
+        assert_eq!(texts("Bob", Channel::Title), vec!["Round 1"]);
+        assert_eq!(texts("Alice", Channel::Subtitle), vec!["Fight!"]);
+        assert!(texts("Bob", Channel::Subtitle).is_empty());
+        assert_eq!(texts("Bob", Channel::Actionbar), vec!["3 lives"]);
+        assert!(inbox.messages("Alice").is_empty());
     }
 
     #[test]
-    fn scoreboard_objectives_list_0_objectives() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::List));
+    fn title_times_and_reset() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&command);
-        logger.assert_logged(Level::Info, "There are no objectives");
+        game.add_player("Alice");
+        game.execute(&parsed("title Alice times 5 40 10"));
+        game.logger
+            .assert_logged(Level::Info, "Changing title display times for Alice");
+        assert_eq!(
+            game.title_times("Alice"),
+            TitleTimes {
+                fade_in: 5,
+                stay: 40,
+                fade_out: 10
+            }
+        );
+        game.execute(&parsed("title Alice reset"));
+        game.logger
+            .assert_logged(Level::Info, "Reset title options for Alice");
+        assert_eq!(game.title_times("Alice"), TitleTimes::default());
     }
 
     #[test]
-    fn scoreboard_objectives_list_1_objective() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::List));
-        let add_command =
-            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj"),
-                criteria: Criteria::Dummy,
-                display_name: Some(String::from("obj name")),
-            })));
+    fn entity_scores() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add_command);
-        game.execute(&command);
-        logger.skip();
-        logger.assert_logged(Level::Info, "There are 1 objectives: [obj name]");
+        let marker = game.spawn("marker");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.logger.skip();
+        game.execute(&parsed("scoreboard players set @e obj 5"));
+        game.logger
+            .assert_logged(Level::Info, "Set [obj] for Marker to 5");
+        game.execute(&parsed(&format!("scoreboard players add {} obj 2", marker)));
+        game.logger
+            .assert_logged(Level::Info, "Added 2 to [obj] for Marker (now 7)");
+        assert_eq!(game.score(&ScoreHolder::Entity(marker), "obj"), Some(7));
+        assert_eq!(game.score(&holder("Marker"), "obj"), None);
     }
 
     #[test]
-    fn scoreboard_objectives_list_many_objectives() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::List));
-        let add_first =
-            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj1"),
-                criteria: Criteria::Dummy,
-                display_name: None,
-            })));
-        let add_second =
-            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj2"),
-                criteria: Criteria::Dummy,
-                display_name: None,
-            })));
-        let add_third =
-            Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj3"),
-                criteria: Criteria::Dummy,
-                display_name: None,
-            })));
+    fn fake_and_offline_holders() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add_first);
-        game.execute(&add_second);
-        game.execute(&add_third);
-        game.execute(&command);
-        logger.skip();
-        logger.skip();
-        logger.skip();
-        logger.assert_matches(|level, message: &str| {
-            level == Level::Info
-                && message.starts_with("There are 3 objectives: ")
-                && is_anagram(
-                    message[(message.find(":").unwrap() + 2)..]
-                        .split(" ")
-                        .collect(),
-                    vec!["[obj1]", "[obj2]", "[obj3]"],
-                )
-        });
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.execute(&parsed("scoreboard players set #temp obj 3"));
+        game.execute(&parsed("scoreboard players set Steve obj 4"));
+        assert_eq!(
+            game.score(&ScoreHolder::Fake("#temp".to_string()), "obj"),
+            Some(3)
+        );
+        game.add_player("Steve");
+        assert_eq!(
+            game.score(&ScoreHolder::Player("Steve".to_string()), "obj"),
+            Some(4)
+        );
+        game.execute(&parsed("scoreboard players add @a obj 1"));
+        assert_eq!(game.score(&holder("Steve"), "obj"), Some(5));
     }
 
     #[test]
-    fn scoreboard_objectives_modify_display_name() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("prev display name")),
-        })));
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
-            ObjectivesModify {
-                objective: String::from("obj"),
-                modification: Modification::DisplayName(String::from("new display name")),
-            },
-        )));
+    fn selector_component_names_entities() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
+        let mut chat = ChatSpy::new();
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        game.execute(&command);
+        game.add_player("Alice");
+        let stand = game.spawn("armor_stand");
+        game.entity_mut(stand).unwrap().custom_name = Some(TextComponent::text("Statue"));
+        game.spawn("marker");
+        tellraw_text(&mut game, "{\"selector\":\"@e\"}");
         assert_eq!(
-            game.objectives.get("obj").unwrap().display_name,
-            "new display name".to_owned()
-        );
-        logger.skip();
-        logger.assert_logged(
-            Level::Info,
-            "Changed objective obj display name to [new display name]",
+            game.chat.last_message.take().map(|m| m.text),
+            Some("Alice, Statue, Marker".to_string())
         );
     }
 
     #[test]
-    fn scoreboard_objectives_modify_display_name_no_objective() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
-            ObjectivesModify {
-                objective: String::from("obj"),
-                modification: Modification::DisplayName(String::from("new display name")),
-            },
-        )));
+    fn summon_player_fails() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&command);
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        game.execute(&parsed("summon minecraft:player 0 0 0"));
+        game.logger
+            .assert_logged(Level::Fail, "Unable to summon entity");
+        assert_eq!(game.uuid_seed, 0);
+        assert!(game.entities.is_empty());
     }
 
     #[test]
-    fn scoreboard_objectives_modify_display_name_no_change() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("display name")),
-        })));
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
-            ObjectivesModify {
-                objective: String::from("obj"),
-                modification: Modification::DisplayName(String::from("display name")),
-            },
-        )));
+    fn summon_and_select() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        game.execute(&command);
-        logger.skip();
-        logger.assert_no_logs();
+        game.execute(&parsed("summon marker 1 2 3 {Tags:[\"flag\"]}"));
+        game.logger
+            .assert_logged(Level::Info, "Summoned new Marker");
+        game.execute(&parsed(
+            "summon armor_stand ~ ~ ~ {CustomName:'\"Statue\"',Tags:[\"flag\",\"big\"]}",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Summoned new Statue");
+        game.execute(&parsed("summon player"));
+        game.logger
+            .assert_logged(Level::Fail, "Unable to summon entity");
+        game.add_player("Alice");
+
+        let names = |game: &Game<LoggerSpy, NullChat>, target: &str| {
+            game.get_entity_names(&parse::parse_target(target).unwrap())
+        };
+        assert_eq!(names(&game, "@e[tag=flag]"), vec!["Marker", "Statue"]);
+        assert_eq!(names(&game, "@e[tag=flag,tag=!big]"), vec!["Marker"]);
+        assert_eq!(names(&game, "@e[type=!marker]"), vec!["Statue", "Alice"]);
+        assert_eq!(names(&game, "@e[tag=]"), vec!["Alice"]);
+        assert_eq!(names(&game, "@e[name=Statue]"), vec!["Statue"]);
+        assert_eq!(names(&game, "@e[limit=1]"), vec!["Marker"]);
+        let marker = game.get_entities(&parse::parse_target("@e[type=marker]").unwrap())[0];
+        assert_eq!(marker.position, [1.5, 2.0, 3.5]);
     }
 
     #[test]
-    fn scoreboard_objectives_remove_existing() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("display name")),
-        })));
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Remove(
-            ObjectivesRemove {
-                objective: String::from("obj"),
-            },
-        )));
+    fn select_by_scores() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        game.execute(&command);
-        assert!(game.objectives.get("obj").is_none());
-        logger.skip();
-        logger.assert_logged(Level::Info, "Removed objective [display name]");
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.set_player_score("Alice", "obj", 3);
+        game.set_player_score("Bob", "obj", 10);
+        assert_eq!(
+            game.get_entity_names(&parse::parse_target("@a[scores={obj=..5}]").unwrap()),
+            vec!["Alice"]
+        );
     }
 
     #[test]
-    fn scoreboard_objectives_remove_nothing() {
-        let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Remove(
-            ObjectivesRemove {
-                objective: String::from("obj"),
-            },
-        )));
+    fn kill_removes_entities_and_scores() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&command);
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        let first = game.spawn("marker");
+        game.spawn("marker");
+        let cow = game.spawn("cow");
+        game.add_player("Alice");
+        game.execute(&parsed("scoreboard players set @e obj 1"));
+        for _ in 0..5 {
+            game.logger.skip();
+        }
+        game.execute(&parsed("kill @e[type=marker]"));
+        game.logger.assert_logged(Level::Info, "Killed 2 entities");
+        assert_eq!(game.entity(first), None);
+        assert_eq!(game.score(&ScoreHolder::Entity(first), "obj"), None);
+        assert_eq!(game.score(&ScoreHolder::Entity(cow), "obj"), Some(1));
+        game.execute(&parsed(&format!("kill {}", cow)));
+        game.logger.assert_logged(Level::Info, "Killed Cow");
+        game.execute(&parsed("kill @e[type=marker]"));
+        game.logger
+            .assert_logged(Level::Fail, "No entity was found");
+        game.execute_as("Alice", &parsed("kill"));
+        game.logger.assert_logged(Level::Info, "Killed Alice");
+        assert_eq!(game.score(&holder("Alice"), "obj"), Some(1));
+        assert!(game.find_player("Alice").is_some());
     }
 
     #[test]
-    fn initial_render_type() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: None,
-        })));
+    fn tags() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        assert_eq!(
-            game.objectives.get("obj").unwrap().render_type,
-            RenderType::Integer
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed("tag @a add team.red"));
+        game.logger
+            .assert_logged(Level::Info, "Added tag 'team.red' to 2 entities");
+        game.execute(&parsed("tag Alice add team.red"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Target either already has the tag or has too many tags",
+        );
+        game.execute(&parsed("tag Alice add captain"));
+        game.logger
+            .assert_logged(Level::Info, "Added tag 'captain' to Alice");
+        game.execute(&parsed("tag Alice list"));
+        game.logger
+            .assert_logged(Level::Info, "Alice has 2 tags: captain, team.red");
+        game.execute(&parsed("tag @a list"));
+        game.logger.assert_logged(
+            Level::Info,
+            "The 2 entities have 2 total tags: captain, team.red",
         );
+        game.execute(&parsed("tag Bob remove captain"));
+        game.logger
+            .assert_logged(Level::Fail, "Target does not have this tag");
+        game.execute(&parsed("tag @a remove team.red"));
+        game.logger
+            .assert_logged(Level::Info, "Removed tag 'team.red' from 2 entities");
+        game.execute(&parsed("tag Bob list"));
+        game.logger.assert_logged(Level::Info, "Bob has no tags");
+        game.execute(&parsed("tag @e[tag=nothing] list"));
+        game.logger
+            .assert_logged(Level::Fail, "No entity was found");
+    }
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3]) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    fn context_at(position: [f64; 3], rotation: [f32; 2]) -> ExecutionContext {
+        ExecutionContext {
+            position,
+            rotation,
+            ..ExecutionContext::server()
+        }
     }
 
     #[test]
-    fn scoreboard_objectives_modify_render_type() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("display name")),
-        })));
-        let modify = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
-            ObjectivesModify {
-                objective: String::from("obj"),
-                modification: Modification::RenderType(RenderType::Hearts),
-            },
-        )));
-        let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        game.execute(&modify);
-        assert_eq!(
-            game.objectives.get("obj").unwrap().render_type,
-            RenderType::Hearts
-        );
-        logger.skip();
-        logger.assert_logged(Level::Info, "Changed objective [display name] render type");
+    fn resolve_world_coordinates() {
+        let context = context_at([10.0, 64.0, -5.5], [0.0, 0.0]);
+        let position = Vec3 {
+            x: Coordinate::Relative(1.0),
+            y: Coordinate::Absolute(70.0, false),
+            z: Coordinate::Relative(-1.5),
+        };
+        assert_close(context.resolve(&position), [11.0, 70.0, -7.0]);
+        let block = BlockPos {
+            x: Coordinate::Relative(0.5),
+            y: Coordinate::Relative(-0.5),
+            z: Coordinate::Relative(0.0),
+        };
+        assert_eq!(context.resolve_block_pos(&block), [10, 63, -6]);
+        let column = Vec2 {
+            x: Coordinate::Relative(2.0),
+            z: Coordinate::Absolute(3.0, false),
+        };
+        assert_eq!(context.resolve_vec2(&column), [12.0, 3.0]);
+    }
+
+    #[test]
+    fn resolve_local_coordinates() {
+        let local = |left, up, forwards| Vec3 {
+            x: Coordinate::Local(left),
+            y: Coordinate::Local(up),
+            z: Coordinate::Local(forwards),
+        };
+        let south = context_at([0.0, 0.0, 0.0], [0.0, 0.0]);
+        assert_close(south.resolve(&local(0.0, 0.0, 1.0)), [0.0, 0.0, 1.0]);
+        assert_close(south.resolve(&local(1.0, 0.0, 0.0)), [1.0, 0.0, 0.0]);
+        assert_close(south.resolve(&local(0.0, 1.0, 0.0)), [0.0, 1.0, 0.0]);
+        let west = context_at([5.0, 0.0, 5.0], [90.0, 0.0]);
+        assert_close(west.resolve(&local(0.0, 0.0, 2.0)), [3.0, 0.0, 5.0]);
+        assert_close(west.resolve(&local(1.0, 0.0, 0.0)), [5.0, 0.0, 6.0]);
+        let up = context_at([0.0, 0.0, 0.0], [0.0, -90.0]);
+        assert_close(up.resolve(&local(0.0, 0.0, 3.0)), [0.0, 3.0, 0.0]);
+        assert_close(up.resolve(&local(0.0, 1.0, 0.0)), [0.0, 0.0, -1.0]);
     }
 
     #[test]
-    fn scoreboard_objectives_modify_render_type_no_objective() {
-        let modify = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
-            ObjectivesModify {
-                objective: String::from("obj"),
-                modification: Modification::RenderType(RenderType::Integer),
-            },
-        )));
+    fn summon_at_local_coordinates() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&modify);
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        let alice = game.add_player("Alice");
+        game.entity_mut(alice).unwrap().position = [1.0, 64.0, 1.0];
+        game.entity_mut(alice).unwrap().rotation = [-90.0, 0.0];
+        game.execute_as("Alice", &parsed("summon marker ^ ^ ^2"));
+        let marker = game.get_entities(&parse::parse_target("@e[type=marker]").unwrap())[0];
+        assert_close(marker.position, [3.0, 64.0, 1.0]);
     }
 
     #[test]
-    fn scoreboard_objectives_modify_render_type_no_change() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("display name")),
-        })));
-        let modify = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
-            ObjectivesModify {
-                objective: String::from("obj"),
-                modification: Modification::RenderType(RenderType::Integer),
-            },
-        )));
+    fn summon_centres_only_integer_coordinates() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        game.execute(&modify);
-        logger.skip();
-        logger.assert_no_logs();
+        game.execute(&parsed("summon marker 10 64 10 {Tags:[\"integer\"]}"));
+        game.execute(&parsed("summon marker 10.0 64 10.0 {Tags:[\"decimal\"]}"));
+        let position = |game: &Game<LoggerSpy, NullChat>, target: &str| {
+            game.get_entities(&parse::parse_target(target).unwrap())[0].position
+        };
+        assert_eq!(position(&game, "@e[tag=integer]"), [10.5, 64.0, 10.5]);
+        assert_eq!(position(&game, "@e[tag=decimal]"), [10.0, 64.0, 10.0]);
     }
 
     #[test]
-    fn scoreboard_objectives_set_display() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("display name")),
-        })));
-        let set_display = Command::Scoreboard(Scoreboard::Objectives(Objectives::SetDisplay(
-            ObjectivesSetDisplay {
-                slot: DisplaySlot::Sidebar,
-                objective: String::from("obj"),
-            },
-        )));
+    fn position_selector_arguments() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        game.execute(&set_display);
+        for (name, x) in [("Alice", 10.0), ("Bob", 2.0), ("Carol", -5.0)] {
+            let uuid = game.add_player(name);
+            game.entity_mut(uuid).unwrap().position = [x, 0.0, 0.0];
+        }
+        let names = |game: &Game<LoggerSpy, NullChat>, target: &str| {
+            game.get_entity_names(&parse::parse_target(target).unwrap())
+        };
+        assert_eq!(names(&game, "@p"), vec!["Bob"]);
+        assert_eq!(names(&game, "@p[x=8]"), vec!["Alice"]);
+        assert_eq!(names(&game, "@a[distance=..4]"), vec!["Bob"]);
+        assert_eq!(names(&game, "@a[distance=4..]"), vec!["Alice", "Carol"]);
+        assert_eq!(names(&game, "@a[x=0,dx=10]"), vec!["Alice", "Bob"]);
+        assert_eq!(names(&game, "@a[x=0,dx=-5]"), vec!["Carol"]);
         assert_eq!(
-            game.displays.get(&DisplaySlot::Sidebar).unwrap(),
-            &Some(String::from("obj"))
+            names(&game, "@a[sort=nearest]"),
+            vec!["Bob", "Carol", "Alice"]
         );
-        logger.skip();
-        logger.assert_logged(
-            Level::Info,
-            "Set display slot sidebar to show objective display name",
+        assert_eq!(
+            names(&game, "@a[sort=furthest,limit=2]"),
+            vec!["Alice", "Carol"]
         );
+        assert_eq!(names(&game, "@p[sort=arbitrary]"), vec!["Alice"]);
     }
 
     #[test]
-    fn scoreboard_objectives_set_display_no_objective() {
-        let set_display = Command::Scoreboard(Scoreboard::Objectives(Objectives::SetDisplay(
-            ObjectivesSetDisplay {
-                slot: DisplaySlot::BelowName,
-                objective: String::from("obj"),
-            },
-        )));
+    fn teleport() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&set_display);
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        let alice = game.add_player("Alice");
+        let bob = game.add_player("Bob");
+        game.entity_mut(bob).unwrap().position = [5.0, 70.0, 5.0];
+        game.entity_mut(bob).unwrap().rotation = [45.0, 10.0];
+
+        game.execute(&parsed("tp Alice 1 2 3"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Teleported Alice to 1.500000, 2.000000, 3.500000",
+        );
+        assert_close(game.entity(alice).unwrap().position, [1.5, 2.0, 3.5]);
+        game.execute(&parsed("tp Alice 1.25 2 -3.75"));
+        game.logger.skip();
+        assert_close(game.entity(alice).unwrap().position, [1.25, 2.0, -3.75]);
+
+        game.execute(&parsed("tp Alice Bob"));
+        game.logger
+            .assert_logged(Level::Info, "Teleported Alice to Bob");
+        assert_close(game.entity(alice).unwrap().position, [5.0, 70.0, 5.0]);
+        assert_eq!(game.entity(alice).unwrap().rotation, [45.0, 10.0]);
+
+        game.execute_as("Alice", &parsed("tp @s ~1 ~ ~ ~90 ~"));
+        game.logger.skip();
+        assert_close(game.entity(alice).unwrap().position, [6.0, 70.0, 5.0]);
+        assert_eq!(game.entity(alice).unwrap().rotation, [135.0, 10.0]);
+
+        game.execute(&parsed("tp @a 0 0 0"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Teleported 2 entities to 0.500000, 0.000000, 0.500000",
+        );
+
+        game.execute(&parsed("tp Alice 0 0 0 facing 0 1.62 -10"));
+        game.logger.skip();
+        assert_eq!(game.entity(alice).unwrap().rotation, [-180.0, 0.0]);
+        game.entity_mut(bob).unwrap().position = [10.5, 0.0, 0.5];
+        game.execute(&parsed("tp Alice 0 0 0 facing entity Bob eyes"));
+        game.logger.skip();
+        assert_eq!(game.entity(alice).unwrap().rotation, [-90.0, 0.0]);
+
+        game.execute(&parsed("tp 1 2 3"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "An entity is required to run this command here",
+        );
+        game.execute(&parsed("tp Alice Carol"));
+        game.logger
+            .assert_logged(Level::Fail, "No entity was found");
+    }
+
+    fn block_at(game: &Game<LoggerSpy, NullChat>, position: [i32; 3]) -> String {
+        game.world()
+            .get("minecraft:overworld", position)
+            .to_string()
     }
 
     #[test]
-    fn scoreboard_objectives_set_display_twice() {
-        let add = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-            objective: String::from("obj"),
-            criteria: Criteria::Dummy,
-            display_name: Some(String::from("display name")),
-        })));
-        let set_display = Command::Scoreboard(Scoreboard::Objectives(Objectives::SetDisplay(
-            ObjectivesSetDisplay {
-                slot: DisplaySlot::Sidebar,
-                objective: String::from("obj"),
-            },
-        )));
+    fn setblock() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&add);
-        game.execute(&set_display);
-        game.execute(&set_display);
-        logger.skip();
-        logger.skip();
-        logger.assert_logged(
-            Level::Fail,
-            "Nothing changed. That display slot is already showing that objective",
+        game.execute(&parsed("setblock 1 2 3 stone"));
+        game.logger
+            .assert_logged(Level::Info, "Changed the block at 1, 2, 3");
+        assert_eq!(block_at(&game, [1, 2, 3]), "minecraft:stone");
+        game.execute(&parsed("setblock 1 2 3 stone"));
+        game.logger
+            .assert_logged(Level::Fail, "Could not set the block");
+        game.execute(&parsed("setblock 1 2 3 dirt keep"));
+        game.logger
+            .assert_logged(Level::Fail, "Could not set the block");
+        game.execute(&parsed("setblock 1 2 3 stone destroy"));
+        game.logger
+            .assert_logged(Level::Info, "Changed the block at 1, 2, 3");
+        game.execute(&parsed("setblock 1 2 3 chest[facing=west]{Lock:\"key\"}"));
+        game.logger.skip();
+        assert_eq!(
+            block_at(&game, [1, 2, 3]),
+            "minecraft:chest[facing=west]{Lock:\"key\"}"
         );
+        game.execute(&parsed("setblock 0 320 0 stone"));
+        game.logger
+            .assert_logged(Level::Fail, "That position is out of this world!");
     }
 
     #[test]
-    fn add_player_to_game() {
+    fn fill() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.add_player("player1");
-        assert_eq!(
-            game.players.get("player1").unwrap(),
-            &Player {
-                name: String::from("player1")
-            }
+        game.execute(&parsed("fill 0 0 0 2 2 2 stone"));
+        game.logger
+            .assert_logged(Level::Info, "Successfully filled 27 block(s)");
+        game.execute(&parsed("fill 0 0 0 2 2 2 glass hollow"));
+        game.logger
+            .assert_logged(Level::Info, "Successfully filled 27 block(s)");
+        assert_eq!(block_at(&game, [1, 1, 1]), "minecraft:air");
+        assert_eq!(block_at(&game, [0, 1, 1]), "minecraft:glass");
+        game.execute(&parsed("fill 0 0 0 2 2 2 dirt keep"));
+        game.logger
+            .assert_logged(Level::Info, "Successfully filled 1 block(s)");
+        game.execute(&parsed("fill 2 2 2 0 0 0 stone replace glass"));
+        game.logger
+            .assert_logged(Level::Info, "Successfully filled 26 block(s)");
+        assert_eq!(block_at(&game, [1, 1, 1]), "minecraft:dirt");
+        game.execute(&parsed("fill 0 0 0 2 2 2 stone outline"));
+        game.logger
+            .assert_logged(Level::Fail, "No blocks were filled");
+        game.execute(&parsed("fill 0 0 0 31 31 32 stone"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Too many blocks in the specified area (maximum 32768, specified 33792)",
         );
     }
 
     #[test]
-    fn scoreboard_players_add_player_not_ingame() {
+    fn clone() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.add_player("player1");
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj"),
-                criteria: Criteria::Dummy,
-                display_name: Some(String::from("display name")),
-            }),
-        )));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Add(
-            PlayersAdd {
-                targets: Target::Name(String::from("player1")),
-                objective: String::from("obj"),
-                score: 11,
-            },
-        ))));
-        assert_eq!(
-            game.objectives
-                .get("obj")
-                .unwrap()
-                .data
-                .get("player1")
-                .unwrap(),
-            &11
-        );
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Add(
-            PlayersAdd {
-                targets: Target::Name(String::from("player1")),
-                objective: String::from("obj"),
-                score: 4,
-            },
-        ))));
-        assert_eq!(
-            game.objectives
-                .get("obj")
-                .unwrap()
-                .data
-                .get("player1")
-                .unwrap(),
-            &15
-        );
-        logger.skip();
-        logger.assert_logged(
-            Level::Info,
-            "Added 11 to [display name] for player1 (now 11)",
-        );
-        logger.assert_logged(
-            Level::Info,
-            "Added 4 to [display name] for player1 (now 15)",
-        );
+        game.execute(&parsed("setblock 0 0 0 stone"));
+        game.execute(&parsed("setblock 1 0 0 lever[powered=true]"));
+        game.logger.skip();
+        game.logger.skip();
+        game.execute(&parsed("clone 0 0 0 1 1 0 10 0 0"));
+        game.logger
+            .assert_logged(Level::Info, "Successfully cloned 4 block(s)");
+        assert_eq!(block_at(&game, [10, 0, 0]), "minecraft:stone");
+        game.execute(&parsed("clone 0 0 0 1 1 0 20 0 0 masked"));
+        game.logger
+            .assert_logged(Level::Info, "Successfully cloned 2 block(s)");
+        game.execute(&parsed(
+            "clone 0 0 0 1 1 0 30 0 0 filtered lever[powered=true] move",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Successfully cloned 1 block(s)");
+        assert_eq!(block_at(&game, [1, 0, 0]), "minecraft:air");
+        assert_eq!(block_at(&game, [31, 0, 0]), "minecraft:lever[powered=true]");
+        game.execute(&parsed("clone 0 0 0 1 1 0 1 0 0"));
+        game.logger
+            .assert_logged(Level::Fail, "The source and destination can not overlap");
+        game.execute(&parsed("clone 0 0 0 1 1 0 1 0 0 replace force"));
+        game.logger
+            .assert_logged(Level::Info, "Successfully cloned 4 block(s)");
+        assert_eq!(block_at(&game, [1, 0, 0]), "minecraft:stone");
+        game.execute(&parsed("clone 5 5 5 6 6 6 0 10 0 masked"));
+        game.logger
+            .assert_logged(Level::Fail, "No blocks were cloned");
     }
 
     #[test]
-    fn scoreboard_players_add_nonexistant_objective() {
+    fn execute_if_block() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Add(
-            PlayersAdd {
-                targets: Target::Name(String::from("player")),
-                objective: String::from("obj"),
-                score: 16,
-            },
-        ))));
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.logger.skip();
+        game.execute(&parsed("setblock 0 0 0 oak_stairs[facing=east,half=top]"));
+        game.execute(&parsed("setblock 5 0 0 oak_stairs[facing=east,half=top]"));
+        game.logger.skip();
+        game.logger.skip();
+        game.execute(&parsed(
+            "execute if block 0 0 0 oak_stairs[facing=east] run scoreboard players set a obj 1",
+        ));
+        game.execute(&parsed(
+            "execute if block 0 0 0 oak_stairs[facing=west] run scoreboard players set b obj 1",
+        ));
+        game.execute(&parsed(
+            "execute unless block 0 1 0 stone run scoreboard players set c obj 1",
+        ));
+        game.execute(&parsed(
+            "execute if blocks 0 0 0 0 1 0 5 0 0 all run scoreboard players set d obj 1",
+        ));
+        game.execute(&parsed(
+            "execute if blocks 0 0 0 1 0 0 4 0 0 all run scoreboard players set e obj 1",
+        ));
+        game.execute(&parsed(
+            "execute unless blocks 0 0 0 1 0 0 5 0 0 masked run scoreboard players set f obj 1",
+        ));
+        let scored: Vec<&str> = ["a", "b", "c", "d", "e", "f"]
+            .iter()
+            .copied()
+            .filter(|name| game.score(&holder(name), "obj").is_some())
+            .collect();
+        assert_eq!(scored, vec!["a", "c", "d"]);
+    }
+
+    fn house() -> Nbt {
+        crate::nbt::parse(
+            "{size:[2,1,1],palette:[{Name:\"minecraft:stone\"},\
+             {Name:\"minecraft:furnace\",Properties:{facing:\"north\"}}],\
+             blocks:[{pos:[0,0,0],state:0},{pos:[1,0,0],state:1}],\
+             entities:[{pos:[0.5d,0.0d,0.5d],nbt:{id:\"minecraft:marker\",Tags:[\"guard\"]}}]}",
+        )
+        .unwrap()
     }
 
     #[test]
-    fn scoreboard_players_remove() {
+    fn place_template() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.add_player("player1");
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj"),
-                criteria: Criteria::Dummy,
-                display_name: Some(String::from("display name")),
-            }),
-        )));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Remove(
-            PlayersRemove {
-                targets: Target::Name(String::from("player1")),
-                objective: String::from("obj"),
-                score: 5,
-            },
-        ))));
-        assert_eq!(
-            game.objectives
-                .get("obj")
-                .unwrap()
-                .data
-                .get("player1")
-                .unwrap(),
-            &-5
+        let mut structures = HashMap::new();
+        structures.insert(
+            "ns:house".to_string(),
+            Structure::from_nbt(&house()).unwrap(),
         );
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Remove(
-            PlayersRemove {
-                targets: Target::Name(String::from("player1")),
-                objective: String::from("obj"),
-                score: 4,
-            },
-        ))));
+        let datapack = Some(Datapack {
+            name: "datapack".to_string(),
+            structures,
+            ..Default::default()
+        });
+        game.datapack = &datapack;
+
+        game.execute(&parsed("place template ns:house 10 0 10"));
+        game.logger
+            .assert_logged(Level::Info, "Loaded template \"ns:house\" at 10, 0, 10");
+        assert_eq!(block_at(&game, [10, 0, 10]), "minecraft:stone");
         assert_eq!(
-            game.objectives
-                .get("obj")
-                .unwrap()
-                .data
-                .get("player1")
-                .unwrap(),
-            &-9
-        );
-        logger.skip();
-        logger.assert_logged(
-            Level::Info,
-            "Removed 5 from [display name] for player1 (now -5)",
+            block_at(&game, [11, 0, 10]),
+            "minecraft:furnace[facing=north]"
         );
-        logger.assert_logged(
-            Level::Info,
-            "Removed 4 from [display name] for player1 (now -9)",
+        let guards = game.get_entities(&parse::parse_target("@e[tag=guard]").unwrap());
+        assert_close(guards[0].position, [10.5, 0.0, 10.5]);
+
+        game.execute(&parsed("place template ns:house 0 0 0 clockwise_90"));
+        game.logger.skip();
+        assert_eq!(block_at(&game, [0, 0, 0]), "minecraft:stone");
+        assert_eq!(block_at(&game, [0, 0, 1]), "minecraft:furnace[facing=east]");
+
+        game.execute(&parsed("place template ns:shed"));
+        game.logger
+            .assert_logged(Level::Fail, "There is no template with id \"ns:shed\"");
+    }
+
+    #[test]
+    fn place_template_from_disk() {
+        let directory = std::env::temp_dir().join(format!("structures-{}", std::process::id()));
+        fs::create_dir_all(directory.join("ns").join("structures")).unwrap();
+        fs::write(
+            directory.join("ns").join("structures").join("house.nbt"),
+            crate::nbt::write(&house()),
+        )
+        .unwrap();
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.set_structure_directory(directory.clone());
+        game.execute(&parsed("place template ns:house ~ ~1 ~"));
+        game.logger
+            .assert_logged(Level::Info, "Loaded template \"ns:house\" at 0, 1, 0");
+        assert_eq!(
+            block_at(&game, [1, 1, 0]),
+            "minecraft:furnace[facing=north]"
         );
+        fs::write(
+            directory.join("ns").join("structures").join("broken.nbt"),
+            [0x1f, 0x8b, 0x00],
+        )
+        .unwrap();
+        game.execute(&parsed("place template ns:broken"));
+        game.logger
+            .assert_logged(Level::Fail, "Failed to load structure ns:broken: Gzip");
+        fs::remove_dir_all(directory).unwrap();
     }
 
     #[test]
-    fn scoreboard_players_remove_nonexistant_objective() {
+    fn give_and_clear() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Remove(
-            PlayersRemove {
-                targets: Target::Name(String::from("player")),
-                objective: String::from("obj"),
-                score: 16,
-            },
-        ))));
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        let alice = game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed("give Alice diamond 70"));
+        game.logger
+            .assert_logged(Level::Info, "Gave 70 [Diamond] to Alice");
+        game.execute(&parsed("give @a diamond_sword[custom_name='\"Ruby\"']"));
+        game.logger
+            .assert_logged(Level::Info, "Gave 1 [Ruby] to 2 players");
+        game.execute(&parsed("give Alice ender_pearl 1601"));
+        game.logger
+            .assert_logged(Level::Fail, "Can't give more than 1600 of [Ender Pearl]");
+        let inventory = &game.entity(alice).unwrap().inventory;
+        assert_eq!(inventory.get(0).unwrap().count, 64);
+        assert_eq!(inventory.get(1).unwrap().count, 6);
+        assert_eq!(inventory.get(2).unwrap().id, "minecraft:diamond_sword");
+
+        game.execute(&parsed("clear Alice diamond 0"));
+        game.logger
+            .assert_logged(Level::Info, "Found 70 matching item(s) on player Alice");
+        game.execute(&parsed("clear Alice diamond 10"));
+        game.logger
+            .assert_logged(Level::Info, "Removed 10 item(s) from player Alice");
+        game.execute(&parsed("clear @a"));
+        game.logger
+            .assert_logged(Level::Info, "Removed 62 item(s) from 2 players");
+        game.execute(&parsed("clear @a"));
+        game.logger
+            .assert_logged(Level::Fail, "No items were found on 2 players");
+        game.execute(&parsed("clear"));
+        game.logger
+            .assert_logged(Level::Fail, "A player is required to run this command here");
     }
 
     #[test]
-    fn scoreboard_players_set() {
+    fn item_replace_and_modify() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj"),
-                criteria: Criteria::Dummy,
-                display_name: Some(String::from("display name")),
-            }),
-        )));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
-            PlayersSet {
-                targets: Target::Name(String::from("player")),
-                objective: String::from("obj"),
-                score: -23,
-            },
-        ))));
-        assert_eq!(game.objectives["obj"].data["player"], -23);
-        logger.skip();
-        logger.assert_logged(Level::Info, "Set [display name] for player to -23");
+        let mut item_modifiers = HashMap::new();
+        item_modifiers.insert(
+            "ns:double".to_string(),
+            crate::json::parse(r#"{"function":"set_count","count":2}"#).unwrap(),
+        );
+        let datapack = Some(Datapack {
+            name: "datapack".to_string(),
+            item_modifiers,
+            ..Default::default()
+        });
+        game.datapack = &datapack;
+        let alice = game.add_player("Alice");
+        let stand = game.spawn("armor_stand");
+
+        game.execute(&parsed(
+            "item replace entity Alice weapon.offhand with shield",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Replaced a slot on Alice with [Shield]");
+        game.execute(&parsed(
+            "item replace entity @e armor.head from entity Alice weapon.offhand",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Replaced a slot on 2 entities with [Shield]");
+        assert_eq!(
+            game.entity(stand).unwrap().inventory.get(103).unwrap().id,
+            "minecraft:shield"
+        );
+        game.execute(&parsed(
+            "item replace entity @e[type=armor_stand] hotbar.0 with stone",
+        ));
+        game.logger
+            .assert_logged(Level::Fail, "No targets accepted item into slot hotbar.0");
+
+        game.execute(&parsed("item replace entity Alice hotbar.0 with emerald"));
+        game.logger.skip();
+        game.execute(&parsed("item modify entity Alice weapon ns:double"));
+        game.logger
+            .assert_logged(Level::Info, "Replaced a slot on Alice with [Emerald]");
+        assert_eq!(
+            game.entity(alice).unwrap().inventory.get(0).unwrap().count,
+            2
+        );
+        game.execute(&parsed("item modify entity Alice hotbar.5 ns:double"));
+        game.logger
+            .assert_logged(Level::Fail, "No targets accepted item into slot hotbar.5");
+        game.execute(&parsed("item modify entity Alice hotbar.0 ns:missing"));
+        game.logger
+            .assert_logged(Level::Fail, "Unknown item modifier 'ns:missing'");
     }
 
     #[test]
-    fn scoreboard_players_set_no_objective() {
+    fn execute_if_items() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
-            PlayersSet {
-                targets: Target::Name(String::from("player")),
-                objective: "obj".to_string(),
-                score: -8564,
-            },
-        ))));
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        game.add_objective("obj", Criteria::Dummy, TextComponent::text("obj"));
+        game.add_player("Alice");
+        game.execute(&parsed("give Alice emerald{shop:1b} 3"));
+        game.execute(&parsed(
+            "execute if items entity Alice hotbar.* emerald run scoreboard players set a obj 1",
+        ));
+        game.execute(&parsed(
+            "execute if items entity Alice armor.* * run scoreboard players set b obj 1",
+        ));
+        game.execute(&parsed(
+            "execute if items entity Alice container.* *[custom_data~{shop:1b}] run scoreboard players set c obj 1",
+        ));
+        game.execute(&parsed(
+            "execute unless items entity Alice weapon emerald run scoreboard players set d obj 1",
+        ));
+        let scored: Vec<&str> = ["a", "b", "c", "d"]
+            .iter()
+            .copied()
+            .filter(|name| game.score(&holder(name), "obj").is_some())
+            .collect();
+        assert_eq!(scored, vec!["a", "c"]);
     }
 
-    fn operate<S: Chat>(
-        game: &mut Game<LoggerSpy, S>,
-        target_score: i32,
-        source_score: i32,
-        operation: OperationType,
-    ) {
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj"),
-                criteria: Criteria::Dummy,
-                display_name: Some(String::from("display name")),
-            }),
-        )));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
-            PlayersSet {
-                targets: Target::Name(String::from("target")),
-                objective: String::from("obj"),
-                score: target_score,
-            },
-        ))));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
-            PlayersSet {
-                targets: Target::Name(String::from("source")),
-                objective: String::from("obj"),
-                score: source_score,
-            },
-        ))));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(
-            Players::Operation(PlayersOperation {
-                targets: Target::Name(String::from("target")),
-                target_objective: String::from("obj"),
-                operation,
-                source: Target::Name(String::from("source")),
-                source_objective: String::from("obj"),
-            }),
-        )));
-        game.logger.skip();
+    #[test]
+    fn read_only_criteria() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&parsed("scoreboard objectives add hp health"));
+        game.execute(&parsed("scoreboard objectives add dummy dummy"));
         game.logger.skip();
         game.logger.skip();
+        assert_eq!(game.objectives["hp"].render_type, RenderType::Hearts);
+        game.execute(&parsed("scoreboard players set Alice hp 3"));
+        game.logger
+            .assert_logged(Level::Fail, "Scoreboard objective 'hp' is read-only");
+        game.execute(&parsed("scoreboard players add Alice hp 3"));
+        game.logger
+            .assert_logged(Level::Fail, "Scoreboard objective 'hp' is read-only");
+        game.execute(&parsed("scoreboard players operation Alice hp = Bob dummy"));
+        game.logger
+            .assert_logged(Level::Fail, "Scoreboard objective 'hp' is read-only");
+        game.execute(&parsed("scoreboard players operation Alice dummy = Bob hp"));
+        game.logger
+            .assert_logged(Level::Info, "Set [dummy] for Alice to 0");
+        assert_eq!(game.score(&holder("Alice"), "hp"), None);
     }
 
     #[test]
-    fn scoreboard_players_operation_addition() {
+    fn execute_store_into_read_only_objective() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 5, 3, OperationType::Addition);
-        assert_eq!(game.objectives["obj"].data["target"], 8);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 8");
+        game.execute(&parsed("scoreboard objectives add hunger food"));
+        game.execute(&parsed("scoreboard objectives add dummy dummy"));
+        game.logger.messages.clear();
+        game.execute(&parsed(
+            "execute store result score Alice hunger run scoreboard players set Bob dummy 5",
+        ));
+        game.logger
+            .assert_logged(Level::Fail, "Scoreboard objective 'hunger' is read-only");
+        assert!(game.logger.messages.is_empty());
+        assert_eq!(game.score(&holder("Bob"), "dummy"), None);
+        assert_eq!(game.score(&holder("Alice"), "hunger"), None);
     }
 
     #[test]
-    fn scoreboard_players_operation_subtraction() {
+    fn read_only_criteria_follow_players() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 6, 2, OperationType::Subtraction);
-        assert_eq!(game.objectives["obj"].data["target"], 4);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 4");
+        game.execute(&parsed("scoreboard objectives add hp health"));
+        game.execute(&parsed("scoreboard objectives add lvl level"));
+        let alice = game.add_player("Alice");
+        game.execute(&parsed("say tick"));
+        assert_eq!(game.score(&holder("Alice"), "hp"), Some(20));
+        assert_eq!(game.score(&holder("Alice"), "lvl"), Some(0));
+
+        let player = game.entity_mut(alice).unwrap();
+        player.health = 12.5;
+        player.xp_level = 7;
+        game.execute(&parsed("say tick"));
+        assert_eq!(game.score(&holder("Alice"), "hp"), Some(13));
+        assert_eq!(game.score(&holder("Alice"), "lvl"), Some(7));
+
+        // Like vanilla, a new objective only picks up values as they change.
+        game.execute(&parsed("scoreboard objectives add food food"));
+        game.execute(&parsed("say tick"));
+        assert_eq!(game.score(&holder("Alice"), "food"), None);
+        game.entity_mut(alice).unwrap().food = 17;
+        game.execute(&parsed("say tick"));
+        assert_eq!(game.score(&holder("Alice"), "food"), Some(17));
     }
 
     #[test]
-    fn scoreboard_players_operation_multiplication() {
+    fn player_joins() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 3, 4, OperationType::Multiplication);
-        assert_eq!(game.objectives["obj"].data["target"], 12);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 12");
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        let alice = game.player_joins("Alice");
+        assert_eq!(game.player_joins("Alice"), alice);
+        game.player_joins("Bob");
+        assert!(game.find_player("Bob").is_some());
+        assert_eq!(
+            inbox.texts("Alice"),
+            vec!["Alice joined the game", "Bob joined the game"]
+        );
     }
 
     #[test]
-    fn scoreboard_players_operation_division() {
+    fn player_leaves() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 14, 3, OperationType::Division);
-        assert_eq!(game.objectives["obj"].data["target"], 4);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 4");
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.execute(&parsed("scoreboard objectives add deaths deathCount"));
+        game.player_joins("Alice");
+        game.player_joins("Bob");
+        game.player_dies("Bob", None);
+        game.player_leaves("Bob");
+        assert!(game.find_player("Bob").is_none());
+        assert_eq!(game.score(&holder("Bob"), "deaths"), Some(1));
+        assert_eq!(game.player_joins("Bob"), game.add_player("Bob"));
+        assert_eq!(
+            game.find_player("Bob")
+                .unwrap()
+                .stat(StatType::Custom, "leave_game"),
+            1
+        );
+        assert_eq!(
+            inbox.texts("Alice"),
+            vec![
+                "Alice joined the game",
+                "Bob joined the game",
+                "Bob died",
+                "Bob left the game",
+                "Bob joined the game",
+            ]
+        );
     }
 
     #[test]
-    fn scoreboard_players_operation_modulus() {
+    fn player_dies() {
         let mut logger = LoggerSpy::new();
-        let mut chat = NullChat {};
-        let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 17, 5, OperationType::Modulus);
-        assert_eq!(game.objectives["obj"].data["target"], 2);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 2");
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        for line in &[
+            "scoreboard objectives add deaths deathCount",
+            "scoreboard objectives add kills totalKillCount",
+            "scoreboard objectives add pvp playerKillCount",
+        ] {
+            game.execute(&parsed(line));
+        }
+        let alice = game.player_joins("Alice");
+        game.player_joins("Bob");
+        game.player_dies("Bob", Some(alice));
+        game.player_dies("Alice", None);
+        assert_eq!(game.score(&holder("Alice"), "kills"), Some(1));
+        assert_eq!(game.score(&holder("Alice"), "pvp"), Some(1));
+        assert_eq!(game.score(&holder("Alice"), "deaths"), Some(1));
+        assert_eq!(game.score(&holder("Bob"), "deaths"), Some(1));
+        assert_eq!(game.score(&holder("Bob"), "kills"), None);
+        let bob = game.find_player("Bob").unwrap();
+        assert_eq!(bob.stat(StatType::KilledBy, "player"), 1);
+        assert_eq!(bob.stat(StatType::Custom, "deaths"), 1);
+
+        game.execute(&parsed("kill Bob"));
+        assert_eq!(game.score(&holder("Bob"), "deaths"), Some(2));
+        assert!(game.find_player("Bob").is_some());
+        assert_eq!(
+            inbox.texts("Alice"),
+            vec![
+                "Alice joined the game",
+                "Bob joined the game",
+                "Bob was slain by Alice",
+                "Alice died",
+                "Bob was killed",
+            ]
+        );
     }
 
     #[test]
-    fn scoreboard_players_operation_assign() {
+    fn use_item() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 12, -5, OperationType::Assign);
-        assert_eq!(game.objectives["obj"].data["target"], -5);
-        logger.assert_logged(Level::Info, "Set [display name] for target to -5");
+        game.execute(&parsed(
+            "scoreboard objectives add clicks used:carrot_on_a_stick",
+        ));
+        game.player_joins("Alice");
+        game.use_item("Alice", "carrot_on_a_stick");
+        game.use_item("Alice", "minecraft:carrot_on_a_stick");
+        game.use_item("Bob", "carrot_on_a_stick");
+        assert_eq!(game.score(&holder("Alice"), "clicks"), Some(2));
+        assert_eq!(game.score(&holder("Bob"), "clicks"), None);
     }
 
     #[test]
-    fn scoreboard_players_operation_min_source_less() {
+    fn kill_entity() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 12, -5, OperationType::Min);
-        assert_eq!(game.objectives["obj"].data["target"], -5);
-        logger.assert_logged(Level::Info, "Set [display name] for target to -5");
+        for line in &[
+            "scoreboard objectives add kills totalKillCount",
+            "scoreboard objectives add zombies minecraft.killed:minecraft.zombie",
+        ] {
+            game.execute(&parsed(line));
+        }
+        game.player_joins("Alice");
+        let zombie = game.spawn("zombie");
+        game.kill_entity("Alice", zombie);
+        assert!(game.entity(zombie).is_none());
+        assert_eq!(game.score(&holder("Alice"), "kills"), Some(1));
+        assert_eq!(game.score(&holder("Alice"), "zombies"), Some(1));
+        let alice = game.find_player("Alice").unwrap();
+        assert_eq!(alice.stat(StatType::Killed, "zombie"), 1);
     }
 
-    // TODO: Investigate log behavior
     #[test]
-    fn scoreboard_players_operation_min_source_more() {
+    fn killed_by_team_counts_only_players() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 12, 500, OperationType::Min);
-        assert_eq!(game.objectives["obj"].data["target"], 12);
+        for line in &[
+            "team add red",
+            "team modify red color dark_red",
+            "team join red Alice",
+            "scoreboard objectives add deaths killedByTeam.dark_red",
+        ] {
+            game.execute(&parsed(line));
+        }
+        let alice = game.player_joins("Alice");
+        game.player_joins("Bob");
+        let zombie = game.spawn("zombie");
+        let zombie_holder = game.entity(zombie).unwrap().score_holder();
+        game.credit_kill(alice, zombie);
+        game.player_dies("Bob", Some(alice));
+        assert_eq!(game.score(&zombie_holder, "deaths"), None);
+        assert_eq!(game.score(&holder("Bob"), "deaths"), Some(1));
     }
 
     #[test]
-    fn scoreboard_players_operation_max_source_less() {
+    fn increment_stat() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 12, -5, OperationType::Max);
-        assert_eq!(game.objectives["obj"].data["target"], 12);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 12");
+        game.execute(&parsed(
+            "scoreboard objectives add jumps minecraft.custom:minecraft.jump",
+        ));
+        game.player_joins("Alice");
+        game.increment_stat("Alice", StatType::Custom, "jump", 2);
+        game.increment_stat("Alice", StatType::Custom, "minecraft:jump", 1);
+        game.increment_stat("Bob", StatType::Custom, "jump", 1);
+        assert_eq!(game.score(&holder("Alice"), "jumps"), Some(3));
+        let alice = game.find_player("Alice").unwrap();
+        assert_eq!(alice.stat(StatType::Custom, "jump"), 3);
+        assert_eq!(game.score(&holder("Bob"), "jumps"), None);
     }
 
     #[test]
-    fn scoreboard_players_operation_max_source_more() {
+    fn gamerules() {
+        let mut logger = LoggerSpy::new();
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.add_player("Alice");
+        game.execute(&parsed("gamerule keepInventory"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Gamerule keepInventory is currently set to: false",
+        );
+        game.execute(&parsed("gamerule keepInventory true"));
+        game.logger
+            .assert_logged(Level::Info, "Gamerule keepInventory is now set to: true");
+        game.execute(&parsed("gamerule showDeathMessages false"));
+        game.logger.skip();
+        game.execute(&parsed("give Alice diamond 3"));
+        game.logger.skip();
+        game.player_dies("Alice", None);
+        game.execute(&parsed("clear Alice diamond 0"));
+        game.logger
+            .assert_logged(Level::Info, "Found 3 matching item(s) on player Alice");
+        assert!(game.gamerules().bool(GameRule::KeepInventory));
+
+        game.execute(&parsed("gamerule commandModificationBlockLimit 8"));
+        game.logger.skip();
+        game.execute(&parsed("fill 0 0 0 2 2 2 stone"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Too many blocks in the specified area (maximum 8, specified 27)",
+        );
+
+        let datapack = Some(Datapack {
+            name: String::from("datapack"),
+            functions: vec![Function {
+                identifier: FunctionIdentifier {
+                    namespace: None,
+                    name: String::from("loop"),
+                },
+                commands: vec![
+                    parsed("scoreboard players add loops runs 1"),
+                    parsed("function loop"),
+                ],
+            }],
+            ..Default::default()
+        });
+        game.datapack = &datapack;
+        game.execute(&parsed("scoreboard objectives add runs dummy"));
+        game.execute(&parsed("gamerule maxCommandChainLength 9"));
+        game.execute(&parsed("function loop"));
+        assert_eq!(game.score(&holder("loops"), "runs"), Some(5));
+        game.execute(&parsed("function loop"));
+        assert_eq!(game.score(&holder("loops"), "runs"), Some(10));
+        assert!(inbox.texts("Alice").is_empty());
+    }
+
+    #[test]
+    fn time_weather_and_difficulty() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, 12, 500, OperationType::Max);
-        assert_eq!(game.objectives["obj"].data["target"], 500);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 500");
+        game.add_objective("clock", Criteria::Dummy, TextComponent::text("clock"));
+        game.execute(&parsed("time set noon"));
+        game.logger
+            .assert_logged(Level::Info, "Set the time to 6000");
+        game.execute(&parsed("time add 1d"));
+        game.logger
+            .assert_logged(Level::Info, "Set the time to 6000");
+        (0..30).for_each(|_| game.tick());
+        game.execute(&parsed(
+            "execute store result score day clock run time query day",
+        ));
+        game.logger.assert_logged(Level::Info, "The time is 1");
+        assert_eq!(game.score(&holder("day"), "clock"), Some(1));
+        game.execute(&parsed(
+            "execute store result score now clock run time query daytime",
+        ));
+        game.logger.assert_logged(Level::Info, "The time is 6030");
+        assert_eq!(game.score(&holder("now"), "clock"), Some(6030));
+        game.execute(&parsed("gamerule doDaylightCycle false"));
+        game.logger.skip();
+        game.tick();
+        game.execute(&parsed("time query gametime"));
+        game.logger.assert_logged(Level::Info, "The time is 31");
+        assert_eq!(game.day_time(), 30030);
+
+        game.execute(&parsed("weather thunder 2"));
+        game.logger
+            .assert_logged(Level::Info, "Set the weather to rain & thunder");
+        assert_eq!(game.weather(), WeatherKind::Thunder);
+        game.tick();
+        game.tick();
+        assert_eq!(game.weather(), WeatherKind::Clear);
+        game.execute(&parsed(
+            "execute store result score rain clock run weather rain",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Set the weather to rain");
+        assert_eq!(game.score(&holder("rain"), "clock"), Some(-1));
+
+        game.execute(&parsed("difficulty"));
+        game.logger
+            .assert_logged(Level::Info, "The difficulty is Easy");
+        game.execute(&parsed("difficulty hard"));
+        game.logger
+            .assert_logged(Level::Info, "The difficulty has been set to Hard");
+        game.execute(&parsed("difficulty hard"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "The difficulty did not change; it is already set to hard",
+        );
+        game.execute(&parsed(
+            "execute store result score level clock run difficulty",
+        ));
+        game.logger.skip();
+        assert_eq!(game.score(&holder("level"), "clock"), Some(3));
+    }
+
+    fn passes(game: &mut Game<LoggerSpy, NullChat>, player: Option<&str>, condition: &str) -> bool {
+        let command = parsed(&format!("execute {} run time query gametime", condition));
+        match player {
+            Some(player) => game.execute_as(player, &command),
+            None => game.execute(&command),
+        }
+        let passed = !game.logger.messages.is_empty();
+        game.logger.messages.clear();
+        passed
     }
 
     #[test]
-    fn scoreboard_players_operation_swap() {
+    fn predicates() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        operate(&mut game, -5, 7, OperationType::Swap);
-        assert_eq!(game.objectives["obj"].data["target"], 7);
-        assert_eq!(game.objectives["obj"].data["source"], -5);
-        logger.assert_logged(Level::Info, "Set [display name] for target to 7");
+        let mut predicates = HashMap::new();
+        for (id, json) in &[
+            (
+                "ns:rich",
+                r#"{"condition":"entity_scores","entity":"this","scores":{"coins":{"min":10}}}"#,
+            ),
+            (
+                "ns:night",
+                r#"{"condition":"time_check","period":24000,"value":{"min":13000,"max":23000}}"#,
+            ),
+            (
+                "ns:stormy",
+                r#"{"condition":"minecraft:weather_check","thundering":true}"#,
+            ),
+            (
+                "ns:jackpot",
+                r##"{"condition":"value_check","range":{"min":100},
+                    "value":{"type":"score","target":{"type":"fixed","name":"#pot"},"score":"coins"}}"##,
+            ),
+            (
+                "ns:rich_or_night",
+                r#"{"condition":"any_of","terms":[
+                    {"condition":"reference","name":"ns:rich"},
+                    {"condition":"reference","name":"ns:night"}]}"#,
+            ),
+            (
+                "ns:poor",
+                r#"{"condition":"inverted","term":{"condition":"reference","name":"ns:rich"}}"#,
+            ),
+            ("ns:loop", r#"{"condition":"reference","name":"ns:loop"}"#),
+            ("ns:coin", r#"{"condition":"random_chance","chance":0.5}"#),
+            (
+                "ns:nearby_marker",
+                r#"[{"condition":"entity_properties","entity":"this",
+                    "predicate":{"type":"marker","location":{"position":{"x":{"max":10}}}}}]"#,
+            ),
+        ] {
+            predicates.insert(id.to_string(), crate::json::parse(json).unwrap());
+        }
+        let datapack = Some(Datapack {
+            name: "datapack".to_string(),
+            predicates,
+            ..Default::default()
+        });
+        game.datapack = &datapack;
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.add_objective("coins", Criteria::Dummy, TextComponent::text("coins"));
+        game.set_score(&holder("Alice"), "coins", 12);
+        game.set_score(&holder("Bob"), "coins", 3);
+        game.logger.messages.clear();
+
+        game.execute(&parsed("execute if predicate ns:missing run say hi"));
+        game.logger
+            .assert_logged(Level::Fail, "Unknown predicate: ns:missing");
+        assert!(passes(&mut game, Some("Alice"), "if predicate ns:rich"));
+        assert!(!passes(&mut game, Some("Bob"), "if predicate ns:rich"));
+        assert!(passes(&mut game, Some("Bob"), "if predicate ns:poor"));
+        assert!(!passes(&mut game, None, "if predicate ns:rich"));
+        assert!(!passes(
+            &mut game,
+            Some("Bob"),
+            "if predicate ns:rich_or_night"
+        ));
+        game.execute(&parsed("time set 14000"));
+        game.logger.skip();
+        assert!(passes(
+            &mut game,
+            Some("Bob"),
+            "if predicate ns:rich_or_night"
+        ));
+        assert!(passes(&mut game, None, "unless predicate ns:stormy"));
+        game.execute(&parsed("weather thunder"));
+        game.logger.skip();
+        assert!(passes(&mut game, None, "if predicate ns:stormy"));
+        assert!(!passes(&mut game, None, "if predicate ns:jackpot"));
+        game.set_score(&holder("#pot"), "coins", 100);
+        assert!(passes(&mut game, None, "if predicate ns:jackpot"));
+        assert!(!passes(&mut game, None, "if predicate ns:loop"));
+
+        let coins = |game: &mut Game<LoggerSpy, NullChat>| -> Vec<bool> {
+            (0..20)
+                .map(|_| passes(game, None, "if predicate ns:coin"))
+                .collect()
+        };
+        game.set_seed(3);
+        let flips = coins(&mut game);
+        assert!(flips.contains(&true) && flips.contains(&false));
+        game.set_seed(3);
+        assert_eq!(coins(&mut game), flips);
+
+        let selected = |game: &Game<LoggerSpy, NullChat>, argument: &str| -> Vec<String> {
+            let target = match parsed(&format!("kill @e[{}]", argument)) {
+                Command::Kill(Kill {
+                    targets: Some(target),
+                }) => target,
+                _ => unreachable!(),
+            };
+            game.get_entity_names(&target)
+        };
+        assert_eq!(selected(&game, "predicate=ns:rich"), vec!["Alice"]);
+        assert_eq!(selected(&game, "predicate=!ns:rich"), vec!["Bob"]);
+        game.execute(&parsed("summon marker 3 0 0"));
+        game.execute(&parsed("summon marker 30 0 0 {CustomName:'\"Far\"'}"));
+        game.logger.messages.clear();
+        assert_eq!(
+            selected(&game, "predicate=ns:nearby_marker"),
+            vec!["Marker"]
+        );
+        assert_eq!(
+            selected(&game, "type=marker,predicate=!ns:nearby_marker"),
+            vec!["Far"]
+        );
+    }
+
+    fn rolled(seed: i64, commands: &[&str]) -> Vec<String> {
+        let mut logger = LoggerSpy::new();
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        game.set_seed(seed);
+        game.add_player("Alice");
+        for command in commands {
+            game.execute_as("Alice", &parsed(command));
+        }
+        inbox
+            .texts("Alice")
+            .iter()
+            .map(|text| text.to_string())
+            .collect()
     }
 
     #[test]
-    fn scoreboard_players_operation_no_target_objective() {
+    fn seeded_randomness() {
+        let rolls = [
+            "random roll 1..6",
+            "random roll 1..6",
+            "random roll 1..6 dice",
+        ];
+        let first = rolled(7, &rolls);
+        assert_eq!(first.len(), 3);
+        assert!(first[0].starts_with("Alice rolled "));
+        assert!(first[0].ends_with(" (from 1 to 6)"));
+        assert_eq!(first, rolled(7, &rolls));
+        assert_eq!(rolled(7, &["random roll 1..6 dice"])[0], first[2]);
+        let sequence = |seed| rolled(seed, &["random roll 1..1000000 dice"]);
+        assert_ne!(sequence(7), sequence(8));
+
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: "sourceObj".to_string(),
-                criteria: Criteria::Dummy,
-                display_name: None,
-            }),
-        )));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(
-            Players::Operation(PlayersOperation {
-                targets: Target::Name("a".to_string()),
-                target_objective: "targetObj".to_string(),
-                operation: OperationType::Addition,
-                source: Target::Name("b".to_string()),
-                source_objective: "sourceObj".to_string(),
-            }),
-        )));
-        logger.skip();
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'targetObj'");
+        game.add_objective("dice", Criteria::Dummy, TextComponent::text("dice"));
+        game.execute(&parsed("random value 5"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "The range of the random value must be at least 2",
+        );
+        game.execute(&parsed("random value ..0"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "The range of the random value must be at most 2147483646",
+        );
+        let draw = |game: &mut Game<LoggerSpy, NullChat>| {
+            game.execute(&parsed(
+                "execute store result score draw dice run random value 1..1000000 minecraft:dice",
+            ));
+            game.logger.skip();
+            game.score(&holder("draw"), "dice").unwrap()
+        };
+        let a = draw(&mut game);
+        let b = draw(&mut game);
+        game.execute(&parsed("random reset dice"));
+        game.logger
+            .assert_logged(Level::Info, "Reset random sequence minecraft:dice");
+        assert_eq!((draw(&mut game), draw(&mut game)), (a, b));
+        game.execute(&parsed("random reset * 12 false false"));
+        game.logger
+            .assert_logged(Level::Info, "Reset 1 random sequence(s)");
+        let salted = draw(&mut game);
+        game.set_seed(99);
+        game.execute(&parsed("random reset dice 12 false false"));
+        game.logger.skip();
+        assert_eq!(draw(&mut game), salted);
+
+        for name in ["a", "b", "c", "d"].iter() {
+            game.add_player(name);
+        }
+        let picked: BTreeSet<String> = (0..40)
+            .map(|_| {
+                game.get_player_names(&Target::Selector(Selector::new(SelectorVariable::R)))[0]
+                    .clone()
+            })
+            .collect();
+        assert_eq!(picked.len(), 4);
     }
 
     #[test]
-    fn scoreboard_players_operation_no_source_objective() {
+    fn advancement_reward_removes_victim() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: "targetObj".to_string(),
-                criteria: Criteria::Dummy,
-                display_name: None,
-            }),
-        )));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(
-            Players::Operation(PlayersOperation {
-                targets: Target::Name("a".to_string()),
-                target_objective: "targetObj".to_string(),
-                operation: OperationType::Addition,
-                source: Target::Name("b".to_string()),
-                source_objective: "sourceObj".to_string(),
-            }),
-        )));
-        logger.skip();
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'sourceObj'");
+        let mut advancements = HashMap::new();
+        advancements.insert(
+            String::from("ns:slayer"),
+            AdvancementNode::from_json(
+                &crate::json::parse(
+                    r#"{"criteria":{"kill":{"trigger":"player_killed_entity"}},
+                        "rewards":{"function":"ns:cleanup"}}"#,
+                )
+                .unwrap(),
+            ),
+        );
+        let datapack = Some(Datapack {
+            name: "datapack".to_string(),
+            functions: vec![Function {
+                identifier: FunctionIdentifier {
+                    namespace: Some(String::from("ns")),
+                    name: String::from("cleanup"),
+                },
+                commands: vec![parsed("kill @e[type=zombie]")],
+            }],
+            advancements,
+            ..Default::default()
+        });
+        game.datapack = &datapack;
+        game.add_player("Alice");
+        let zombie = game.spawn("zombie");
+        game.execute(&parsed("team add undead"));
+        game.execute(&parsed("team modify undead color green"));
+        game.execute(&parsed("team join undead @e[type=zombie]"));
+        game.execute(&parsed("scoreboard objectives add kills teamkill.green"));
+
+        game.kill_entity("Alice", zombie);
+        assert!(game.has_advancement("Alice", "ns:slayer"));
+        assert_eq!(game.entity(zombie), None);
+        assert_eq!(game.score(&holder("Alice"), "kills"), Some(1));
+    }
+
+    #[test]
+    fn advancements() {
+        let mut logger = LoggerSpy::new();
+        let mut inbox = Inbox::new();
+        let mut game = Game::new(&mut logger, &mut inbox);
+        let mut advancements = HashMap::new();
+        for (id, json) in &[
+            (
+                "ns:root",
+                r#"{"display":{"title":"Start"},"criteria":{"joined":{"trigger":"tick"}}}"#,
+            ),
+            (
+                "ns:explore",
+                r#"{"parent":"ns:root","display":{"title":"Explorer","frame":"goal"},
+                    "criteria":{"far":{"trigger":"location",
+                        "conditions":{"player":{"location":{"position":{"x":{"min":100}}}}}}}}"#,
+            ),
+            (
+                "ns:hunter",
+                r#"{"parent":"ns:explore","display":{"title":"Hunter","frame":"challenge"},
+                    "criteria":{"kill":{"trigger":"player_killed_entity",
+                        "conditions":{"entity":{"type":"zombie"}}}}}"#,
+            ),
+            (
+                "ns:carrot",
+                r#"{"criteria":{"use":{"trigger":"using_item",
+                        "conditions":{"item":{"items":["carrot_on_a_stick"]}}}},
+                    "rewards":{"function":"ns:clicked"}}"#,
+            ),
+            (
+                "ns:never",
+                r#"{"criteria":{"never":{"trigger":"impossible"}}}"#,
+            ),
+        ] {
+            advancements.insert(
+                id.to_string(),
+                AdvancementNode::from_json(&crate::json::parse(json).unwrap()),
+            );
+        }
+        let datapack = Some(Datapack {
+            name: "datapack".to_string(),
+            functions: vec![Function {
+                identifier: FunctionIdentifier {
+                    namespace: Some(String::from("ns")),
+                    name: String::from("clicked"),
+                },
+                commands: vec![
+                    parsed("scoreboard players add @s clicks 1"),
+                    parsed("advancement revoke @s only ns:carrot"),
+                ],
+            }],
+            advancements,
+            ..Default::default()
+        });
+        game.datapack = &datapack;
+        game.add_objective("clicks", Criteria::Dummy, TextComponent::text("clicks"));
+        game.add_player("Alice");
+
+        game.tick();
+        assert!(game.has_advancement("Alice", "ns:root"));
+        game.use_item("Alice", "carrot_on_a_stick");
+        game.use_item("Alice", "minecraft:carrot_on_a_stick");
+        game.use_item("Alice", "stick");
+        assert_eq!(game.score(&holder("Alice"), "clicks"), Some(2));
+        assert!(!game.has_advancement("Alice", "ns:carrot"));
+        (0..20).for_each(|_| game.tick());
+        assert!(!game.has_advancement("Alice", "ns:explore"));
+        game.execute(&parsed("tp Alice 150 0 0"));
+        (0..20).for_each(|_| game.tick());
+        assert!(game.has_advancement("Alice", "ns:explore"));
+        let pig = game.spawn("pig");
+        game.kill_entity("Alice", pig);
+        assert!(!game.has_advancement("Alice", "ns:hunter"));
+        let zombie = game.spawn("zombie");
+        game.kill_entity("Alice", zombie);
+        assert!(game.has_advancement("Alice", "ns:hunter"));
+        game.logger.messages.clear();
+
+        game.execute(&parsed("advancement revoke Alice through ns:explore"));
+        game.logger
+            .assert_logged(Level::Info, "Revoked 3 advancements from Alice");
+        game.execute(&parsed("advancement revoke @a only ns:root"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Couldn't revoke advancement [Start] from Alice as they don't have it",
+        );
+        game.execute(&parsed("advancement grant @a only ns:never never"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Granted criterion 'never' of advancement ns:never to Alice",
+        );
+        game.execute(&parsed("advancement grant Alice only ns:never bogus"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "The advancement ns:never does not contain the criterion 'bogus'",
+        );
+        game.execute(&parsed("advancement grant Alice from ns:missing"));
+        game.logger
+            .assert_logged(Level::Fail, "Unknown advancement: ns:missing");
+        game.execute(&parsed("gamerule announceAdvancements false"));
+        game.logger.skip();
+        game.execute(&parsed("advancement grant Alice until ns:explore"));
+        game.logger
+            .assert_logged(Level::Info, "Granted 2 advancements to Alice");
+        game.execute(&parsed("advancement grant Alice everything"));
+        game.logger
+            .assert_logged(Level::Info, "Granted 5 advancements to Alice");
+        assert_eq!(game.score(&holder("Alice"), "clicks"), Some(3));
+        game.execute(&parsed(
+            "execute store result score revoked clicks run advancement revoke Alice everything",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Revoked 5 advancements from Alice");
+        assert_eq!(game.score(&holder("revoked"), "clicks"), Some(4));
+        game.execute(&parsed("advancement grant Nobody everything"));
+        game.logger
+            .assert_logged(Level::Fail, "No player was found");
+
+        assert_eq!(
+            inbox.texts("Alice"),
+            vec![
+                "Alice has made the advancement [Start]",
+                "Alice has reached the goal [Explorer]",
+                "Alice has completed the challenge [Hunter]",
+            ]
+        );
     }
 
     #[test]
-    fn scoreboard_players_operation_no_objectives() {
+    fn feedback_from_console() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Players(
-            Players::Operation(PlayersOperation {
-                targets: Target::Name("a".to_string()),
-                target_objective: "targetObj".to_string(),
-                operation: OperationType::Addition,
-                source: Target::Name("b".to_string()),
-                source_objective: "sourceObj".to_string(),
-            }),
-        )));
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'targetObj'");
+        game.execute(&parsed("scoreboard objectives add points dummy"));
+        game.logger
+            .assert_logged(Level::Info, "Created new objective [points]");
+        game.execute(&parsed("scoreboard players get Alice points"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Can't get value of points for Alice; none is set",
+        );
+        assert!(game.logger.admin.is_empty());
     }
 
     #[test]
-    fn function_with_namespace() {
+    fn feedback_from_function() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        let datapack = Datapack {
+        let datapack = Some(Datapack {
             name: String::from("datapack"),
             functions: vec![Function {
                 identifier: FunctionIdentifier {
-                    namespace: Some(String::from("namespace")),
-                    name: String::from("func"),
+                    namespace: None,
+                    name: String::from("setup"),
                 },
                 commands: vec![
-                    Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
-                        objective: String::from("obj"),
-                        criteria: Criteria::Dummy,
-                        display_name: None,
-                    }))),
-                    Command::Scoreboard(Scoreboard::Players(Players::Add(PlayersAdd {
-                        targets: Target::Name(String::from("player")),
-                        objective: String::from("obj"),
-                        score: 7,
-                    }))),
+                    parsed("scoreboard objectives add points dummy"),
+                    parsed("scoreboard players get Alice nope"),
                 ],
             }],
-        };
-        let datapack = Some(datapack);
+            ..Default::default()
+        });
         game.datapack = &datapack;
-        game.execute(&Command::Function(FunctionIdentifier {
-            namespace: Some(String::from("namespace")),
-            name: String::from("func"),
-        }));
-        assert_eq!(game.objectives["obj"].data["player"], 7);
+        game.execute(&parsed("function setup"));
+        game.logger.assert_no_logs();
+        assert!(game.objectives.contains_key("points"));
+
+        game.set_verbose(true);
+        game.execute(&parsed("function setup"));
+        game.logger
+            .assert_logged(Level::Fail, "An objective already exists by that name");
+        game.logger
+            .assert_logged(Level::Fail, "Unknown scoreboard objective 'nope'");
     }
 
     #[test]
-    fn no_function() {
+    fn feedback_from_player() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        let datapack = Some(Datapack {
-            name: "datapack".to_string(),
-            functions: vec![],
-        });
-        game.datapack = &datapack;
-        game.execute(&Command::Function(FunctionIdentifier {
-            namespace: Some(String::from("namespace")),
-            name: String::from("func"),
-        }));
-        logger.assert_logged(Level::Fail, "Unknown function namespace:func");
+        game.add_player("Alice");
+        game.execute(&parsed("scoreboard objectives add points dummy"));
+        game.logger.skip();
+        game.execute_as("Alice", &parsed("scoreboard players set Alice points 1"));
+        game.logger
+            .assert_logged(Level::Info, "Set [points] for Alice to 1");
+        assert_eq!(
+            game.logger.admin,
+            vec!["[Alice: Set [points] for Alice to 1]"]
+        );
     }
 
     #[test]
-    fn execute_if_score_matches_value_no_match() {
+    fn feedback_from_command_block() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        compare_match(&mut game, 7, 8, Interval::Value(-55));
-        assert_eq!(game.objectives["obj"].data["player"], 7);
+        game.execute(&parsed("scoreboard objectives add points dummy"));
+        game.logger.skip();
+        game.execute_from_command_block(&parsed("scoreboard players set Bob points 3"));
+        game.logger
+            .assert_logged(Level::Info, "Set [points] for Bob to 3");
+        assert_eq!(game.logger.admin, vec!["[@: Set [points] for Bob to 3]"]);
     }
 
     #[test]
-    fn execute_if_score_matches_value_matches() {
+    fn send_command_feedback_gamerule() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        compare_match(&mut game, 7, 8, Interval::Value(7));
-        assert_eq!(game.objectives["obj"].data["player"], 8);
+        game.add_player("Alice");
+        game.execute(&parsed("scoreboard objectives add points dummy"));
+        game.execute(&parsed("gamerule sendCommandFeedback false"));
+        (0..2).for_each(|_| game.logger.skip());
+        game.execute_as("Alice", &parsed("scoreboard players set Alice points 2"));
+        game.execute_as("Alice", &parsed("scoreboard players get Bob points"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Can't get value of points for Bob; none is set",
+        );
+        game.logger.assert_no_logs();
+        assert_eq!(
+            game.logger.admin,
+            vec!["[Alice: Set [points] for Alice to 2]"]
+        );
     }
 
     #[test]
-    fn execute_if_score_matches_bounded_range_min() {
+    fn command_block_output_gamerule() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        compare_match(&mut game, -3, 7, Interval::Bounded(-3, 5));
-        assert_eq!(game.objectives["obj"].data["player"], 7);
+        game.execute(&parsed("scoreboard objectives add points dummy"));
+        game.execute(&parsed("gamerule commandBlockOutput false"));
+        (0..2).for_each(|_| game.logger.skip());
+        game.execute_from_command_block(&parsed("scoreboard players set Bob points 3"));
+        game.execute_from_command_block(&parsed("scoreboard players get Carol points"));
+        game.logger.assert_no_logs();
+        assert!(game.logger.admin.is_empty());
+        assert_eq!(game.score(&holder("Bob"), "points"), Some(3));
     }
 
     #[test]
-    fn execute_if_score_matches_bounded_range_max() {
+    fn log_admin_commands_gamerule() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        compare_match(&mut game, 5, 7, Interval::Bounded(-3, 5));
-        assert_eq!(game.objectives["obj"].data["player"], 7);
+        game.add_player("Alice");
+        game.execute(&parsed("scoreboard objectives add points dummy"));
+        game.execute(&parsed("gamerule logAdminCommands false"));
+        (0..2).for_each(|_| game.logger.skip());
+        game.execute_as("Alice", &parsed("scoreboard players set Alice points 4"));
+        game.logger
+            .assert_logged(Level::Info, "Set [points] for Alice to 4");
+        assert!(game.logger.admin.is_empty());
     }
 
     #[test]
-    fn execute_if_score_matches_bounded_range_middle() {
+    fn trigger_locked_until_enabled() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        compare_match(&mut game, 0, 7, Interval::Bounded(-3, 5));
-        assert_eq!(game.objectives["obj"].data["player"], 7);
+        game.add_player("Alice");
+        game.execute(&parsed("scoreboard objectives add menu trigger"));
+        game.logger.skip();
+        game.execute_as("Alice", &parsed("trigger menu"));
+        game.logger
+            .assert_logged(Level::Fail, "You cannot trigger this objective yet");
+        assert_eq!(game.score(&holder("Alice"), "menu"), None);
     }
 
     #[test]
-    fn execute_if_score_matches_bounded_range_not_in_range() {
+    fn trigger_enable() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        compare_match(&mut game, -20, 7, Interval::Bounded(-3, 5));
-        assert_eq!(game.objectives["obj"].data["player"], -20);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed("scoreboard objectives add menu trigger"));
+        game.logger.skip();
+        game.execute(&parsed("scoreboard players enable Alice menu"));
+        game.logger
+            .assert_logged(Level::Info, "Enabled trigger [menu] for Alice");
+        assert_eq!(game.score(&holder("Alice"), "menu"), Some(0));
+        game.execute(&parsed("scoreboard players enable @a menu"));
+        game.logger
+            .assert_logged(Level::Info, "Enabled trigger [menu] for 2 entities");
+        game.execute(&parsed("scoreboard players enable @a menu"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Nothing changed. That trigger is already enabled",
+        );
     }
 
     #[test]
-    fn execute_if_matches_no_objective() {
+    fn trigger_relocks_after_use() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Execute(Execute::If(If::Score(Score::Matches(
-            RangeComparison {
-                target: Target::Name(String::from("player")),
-                target_objective: String::from("obj"),
-                interval: Interval::Value(5),
-                command: Box::new(Command::Scoreboard(Scoreboard::Objectives(
-                    Objectives::List,
-                ))),
-            },
-        )))));
-        logger.assert_logged(Level::Fail, "Unknown scoreboard objective 'obj'");
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed("scoreboard objectives add menu trigger"));
+        game.execute(&parsed("scoreboard players enable @a menu"));
+        game.logger.skip();
+        game.logger.skip();
+
+        game.execute_as("Alice", &parsed("trigger menu set 5"));
+        game.logger
+            .assert_logged(Level::Info, "Triggered [menu] (set value to 5)");
+        game.execute_as("Alice", &parsed("trigger menu add 2"));
+        game.logger
+            .assert_logged(Level::Fail, "You cannot trigger this objective yet");
+        game.execute_as("Bob", &parsed("trigger menu add -2"));
+        game.logger
+            .assert_logged(Level::Info, "Triggered [menu] (added -2 to value)");
+        game.execute(&parsed("scoreboard players enable Alice menu"));
+        game.logger.skip();
+        game.execute_as("Alice", &parsed("trigger menu"));
+        game.logger.assert_logged(Level::Info, "Triggered [menu]");
+        assert_eq!(game.score(&holder("Alice"), "menu"), Some(6));
+        assert_eq!(game.score(&holder("Bob"), "menu"), Some(-2));
     }
 
     #[test]
-    fn execute_if_matches_no_player() {
+    fn trigger_non_trigger_objective() {
         let mut logger = LoggerSpy::new();
         let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj"),
-                criteria: Criteria::Dummy,
-                display_name: None,
-            }),
-        )));
-        game.execute(&Command::Execute(Execute::If(If::Score(Score::Matches(
-            RangeComparison {
-                target: Target::Name(String::from("player")),
-                target_objective: String::from("obj"),
-                interval: Interval::Value(0),
-                command: Box::new(Command::Scoreboard(Scoreboard::Players(Players::Set(
-                    PlayersSet {
-                        targets: Target::Name("player".to_string()),
-                        objective: "obj".to_string(),
-                        score: 10,
-                    },
-                )))),
-            },
-        )))));
-        assert!(game.objectives["obj"].data.get("player").is_none());
+        game.add_player("Alice");
+        game.execute(&parsed("scoreboard objectives add plain dummy"));
+        game.logger.skip();
+        game.execute(&parsed("scoreboard players enable Alice plain"));
+        game.logger
+            .assert_logged(Level::Fail, "Enable only works on trigger-objectives");
+        game.execute_as("Alice", &parsed("trigger plain"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "You can only trigger objectives that are 'trigger' type",
+        );
     }
 
-    fn compare_match<T: Log, S: Chat>(
-        game: &mut Game<T, S>,
-        start_score: i32,
-        new_score: i32,
-        interval: Interval,
-    ) {
-        game.execute(&Command::Scoreboard(Scoreboard::Objectives(
-            Objectives::Add(ObjectivesAdd {
-                objective: String::from("obj"),
-                criteria: Criteria::Dummy,
-                display_name: None,
-            }),
-        )));
-        game.execute(&Command::Scoreboard(Scoreboard::Players(Players::Set(
-            PlayersSet {
-                objective: String::from("obj"),
-                score: start_score,
-                targets: Target::Name(String::from("player")),
-            },
-        ))));
-        game.execute(&Command::Execute(Execute::If(If::Score(Score::Matches(
-            RangeComparison {
-                target: Target::Name(String::from("player")),
-                target_objective: String::from("obj"),
-                interval,
-                command: Box::new(Command::Scoreboard(Scoreboard::Players(Players::Set(
-                    PlayersSet {
-                        objective: String::from("obj"),
-                        score: new_score,
-                        targets: Target::Name(String::from("player")),
-                    },
-                )))),
-            },
-        )))));
+    #[test]
+    fn trigger_needs_player_source() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.execute(&parsed("scoreboard objectives add menu trigger"));
+        game.logger.skip();
+        game.execute(&parsed("trigger menu"));
+        game.logger
+            .assert_logged(Level::Fail, "A player is required to run this command here");
     }
 
-    struct ChatSpy {
-        last_message: Option<String>,
+    #[test]
+    fn scoreboard_view() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        assert!(game.scoreboard_view(None).is_empty());
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.add_objective("points", Criteria::Dummy, TextComponent::text("Points"));
+        game.add_objective("hp", Criteria::Health, TextComponent::text("Health"));
+        game.add_objective("red", Criteria::Dummy, TextComponent::text("Red"));
+        for i in 0..20 {
+            game.execute(&parsed(&format!(
+                "scoreboard players set fake{:02} points {}",
+                i,
+                i % 5
+            )));
+        }
+        game.execute(&parsed("scoreboard players set #hidden points 100"));
+        game.execute(&parsed("scoreboard players set Bob points 9"));
+        game.execute(&parsed("scoreboard players set Alice points 9"));
+        game.execute(&parsed("scoreboard players set Alice red 1"));
+        game.execute(&parsed("scoreboard objectives setdisplay sidebar points"));
+        game.execute(&parsed(
+            "scoreboard objectives setdisplay sidebar.team.red red",
+        ));
+        game.execute(&parsed("scoreboard objectives setdisplay list hp"));
+        game.execute(&parsed("scoreboard objectives setdisplay belowName points"));
+        game.execute(&parsed("team add reds"));
+        game.execute(&parsed("team modify reds color red"));
+        game.execute(&parsed("team join reds Alice"));
+
+        let view = game.scoreboard_view(None);
+        let sidebar = view.sidebar.unwrap();
+        assert_eq!(sidebar.title.plain(), "Points");
+        assert_eq!(sidebar.lines.len(), 15);
+        let lines: Vec<(String, String)> = sidebar
+            .lines
+            .iter()
+            .map(|line| (line.name.plain(), line.score.plain()))
+            .take(4)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (String::from("Alice"), String::from("9")),
+                (String::from("Bob"), String::from("9")),
+                (String::from("fake04"), String::from("4")),
+                (String::from("fake09"), String::from("4")),
+            ]
+        );
+        assert_eq!(sidebar.lines[0].name.style.color, Some(Color::Red));
+
+        let list = view.list.unwrap();
+        assert_eq!(list.lines[0].score.plain(), "❤❤❤❤❤❤❤❤❤❤");
+        let below_name = view.below_name.unwrap();
+        assert_eq!(below_name.lines[1].score.plain(), "9 Points");
+
+        let sidebar = game.scoreboard_view(Some("Alice")).sidebar.unwrap();
+        assert_eq!(sidebar.title.plain(), "Red");
+        let sidebar = game.scoreboard_view(Some("Bob")).sidebar.unwrap();
+        assert_eq!(sidebar.title.plain(), "Points");
     }
 
-    impl ChatSpy {
-        fn new() -> ChatSpy {
-            ChatSpy { last_message: None }
-        }
+    #[test]
+    fn number_formats() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("Alice");
+        game.add_objective("points", Criteria::Dummy, TextComponent::text("Points"));
+        game.execute(&parsed("scoreboard objectives setdisplay sidebar points"));
+        game.execute(&parsed("scoreboard players set Alice points 3"));
+        game.execute(&parsed("scoreboard players set Bob points 2"));
+        game.execute(&parsed(
+            "summon armor_stand ~ ~ ~ {CustomName:'\"Statue\"',Tags:[\"statue\"]}",
+        ));
+        game.execute(&parsed("scoreboard players set @e[tag=statue] points 1"));
+        (0..5).for_each(|_| game.logger.skip());
+        let sidebar = |game: &Game<LoggerSpy, NullChat>| -> Vec<(String, String)> {
+            game.scoreboard_view(None)
+                .sidebar
+                .unwrap()
+                .lines
+                .iter()
+                .map(|line| (line.name.plain(), line.score.plain()))
+                .collect()
+        };
+        assert_ne!(sidebar(&game)[2].0, "Statue");
+
+        game.execute(&parsed(
+            "scoreboard objectives modify points numberformat fixed \"-\"",
+        ));
+        game.logger.assert_logged(
+            Level::Info,
+            "Changed default number format for objective [Points]",
+        );
+        game.execute(&parsed(
+            "scoreboard players display numberformat Alice points styled {\"bold\":true}",
+        ));
+        game.logger
+            .assert_logged(Level::Info, "Changed number format for Alice in [Points]");
+        game.execute(&parsed(
+            "scoreboard players display name Bob points \"Robert\"",
+        ));
+        game.logger.assert_logged(
+            Level::Info,
+            "Changed display name to Robert for Bob in [Points]",
+        );
+        game.execute(&parsed("scoreboard players display name @a points"));
+        game.logger
+            .assert_logged(Level::Info, "Cleared display name for Alice in [Points]");
+        game.execute(&parsed(
+            "scoreboard players display name Carol points \"C\"",
+        ));
+        game.logger.assert_logged(
+            Level::Info,
+            "Changed display name to C for Carol in [Points]",
+        );
+        assert_eq!(game.score(&holder("Carol"), "points"), Some(0));
+        game.execute(&parsed(
+            "scoreboard players display numberformat Alice nope",
+        ));
+        game.logger
+            .assert_logged(Level::Fail, "Unknown scoreboard objective 'nope'");
+        game.execute(&parsed(
+            "scoreboard objectives modify points displayautoupdate true",
+        ));
+        game.logger.assert_logged(
+            Level::Info,
+            "Enabled display auto-update for objective [Points]",
+        );
+        let line = |name: &str, score: &str| (String::from(name), String::from(score));
+        assert_eq!(
+            sidebar(&game),
+            vec![
+                line("Alice", "3"),
+                line("Robert", "-"),
+                line("Statue", "-"),
+                line("C", "-"),
+            ]
+        );
+
+        game.execute(&parsed("scoreboard objectives modify points numberformat"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Cleared default number format for objective [Points]",
+        );
+        game.execute(&parsed(
+            "scoreboard objectives modify points displayautoupdate false",
+        ));
+        game.logger.assert_logged(
+            Level::Info,
+            "Disabled display auto-update for objective [Points]",
+        );
+        assert_eq!(sidebar(&game)[1], line("Robert", "2"));
     }
 
-    impl Chat for ChatSpy {
-        fn tell(&mut self, players: Vec<String>, message: &str) {
-            self.last_message = Some(message.to_string())
-        }
+    #[test]
+    fn bossbars() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("Alice");
+        game.execute(&parsed("bossbar list"));
+        game.logger
+            .assert_logged(Level::Info, "There are no custom bossbars active");
+        game.execute(&parsed(r#"bossbar add boss "Boss""#));
+        game.logger
+            .assert_logged(Level::Info, "Created custom bossbar [Boss]");
+        game.execute(&parsed(r#"bossbar add minecraft:boss "Again""#));
+        game.logger.assert_logged(
+            Level::Fail,
+            "A bossbar already exists with the ID 'minecraft:boss'",
+        );
+        game.execute(&parsed("bossbar get other value"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "No bossbar exists with the ID 'minecraft:other'",
+        );
+
+        game.execute(&parsed("bossbar set boss value 40"));
+        game.logger
+            .assert_logged(Level::Info, "Custom bossbar [Boss] has changed value to 40");
+        game.execute(&parsed("bossbar set boss value 40"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Nothing changed. That's already the value of this bossbar",
+        );
+        game.execute(&parsed("bossbar set boss max 80"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Custom bossbar [Boss] has changed maximum to 80",
+        );
+        game.execute(&parsed("bossbar set boss color red"));
+        game.logger
+            .assert_logged(Level::Info, "Custom bossbar [Boss] has changed color");
+        game.execute(&parsed("bossbar set boss style notched_10"));
+        game.logger
+            .assert_logged(Level::Info, "Custom bossbar [Boss] has changed style");
+        game.execute(&parsed("bossbar set boss players @a"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Custom bossbar [Boss] now has 1 player(s): Alice",
+        );
+        game.execute(&parsed("bossbar get boss players"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Custom bossbar [Boss] has 1 player(s) currently online: Alice",
+        );
+        game.execute(&parsed("bossbar set boss players"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Custom bossbar [Boss] no longer has any players",
+        );
+        game.execute(&parsed("bossbar set boss visible false"));
+        game.logger
+            .assert_logged(Level::Info, "Custom bossbar [Boss] is now hidden");
+        game.execute(&parsed("bossbar get boss visible"));
+        game.logger
+            .assert_logged(Level::Info, "Custom bossbar [Boss] is currently hidden");
+        game.execute(&parsed(r#"bossbar set boss name "Big Boss""#));
+        game.logger
+            .assert_logged(Level::Info, "Custom bossbar [Boss] has been renamed");
+        game.execute(&parsed("bossbar get boss max"));
+        game.logger
+            .assert_logged(Level::Info, "Custom bossbar [Big Boss] has a maximum of 80");
+
+        let bossbar = &game.bossbars()["minecraft:boss"];
+        assert_eq!(bossbar.color, BossbarColor::Red);
+        assert_eq!(bossbar.progress(), 0.5);
+        game.execute(&parsed(r#"bossbar add other "Other""#));
+        game.logger.skip();
+        game.execute(&parsed("bossbar list"));
+        game.logger.assert_logged(
+            Level::Info,
+            "There are 2 custom bossbar(s) active: [Big Boss], [Other]",
+        );
+        game.execute(&parsed("bossbar remove boss"));
+        game.logger
+            .assert_logged(Level::Info, "Removed custom bossbar [Big Boss]");
     }
 
     #[test]
-    fn tellraw() {
+    fn execute_store() {
         let mut logger = LoggerSpy::new();
-        let mut chat = ChatSpy::new();
+        let mut chat = NullChat {};
         let mut game = Game::new(&mut logger, &mut chat);
-        game.execute(&Command::Tellraw(Tellraw {
-            target: Target::Name("player".to_string()),
-            message: "it's a message!".to_string(),
-        }));
-        assert_eq!(chat.last_message, Some("it's a message!".to_string()))
+        game.add_objective("points", Criteria::Dummy, TextComponent::text("Points"));
+        game.add_objective("stored", Criteria::Dummy, TextComponent::text("stored"));
+        game.execute(&parsed(r#"bossbar add boss "Boss""#));
+        game.logger.skip();
+
+        game.execute(&parsed("scoreboard players get Alice points"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Can't get value of points for Alice; none is set",
+        );
+        game.execute(&parsed("scoreboard players set Alice points 30"));
+        game.logger.skip();
+        game.execute(&parsed("scoreboard players get Alice points"));
+        game.logger
+            .assert_logged(Level::Info, "Alice has 30 [Points]");
+
+        game.execute(&parsed(
+            "execute store result bossbar boss value run scoreboard players get Alice points",
+        ));
+        game.logger.skip();
+        assert_eq!(game.bossbars()["minecraft:boss"].value, 30);
+        game.execute(&parsed(
+            "execute store result score Bob stored run bossbar get boss value",
+        ));
+        game.logger.skip();
+        assert_eq!(game.score(&holder("Bob"), "stored"), Some(30));
+
+        game.execute(&parsed(
+            "execute store success score Bob stored run scoreboard players get Carol points",
+        ));
+        game.logger.skip();
+        assert_eq!(game.score(&holder("Bob"), "stored"), Some(0));
+        game.execute(&parsed(
+            "execute store success score Bob stored run scoreboard players get Alice points",
+        ));
+        game.logger.skip();
+        assert_eq!(game.score(&holder("Bob"), "stored"), Some(1));
+
+        game.execute(&parsed(
+            "execute store result bossbar other max run scoreboard players get Alice points",
+        ));
+        game.logger.assert_logged(
+            Level::Fail,
+            "No bossbar exists with the ID 'minecraft:other'",
+        );
+    }
+
+    #[test]
+    fn teams() {
+        let mut logger = LoggerSpy::new();
+        let mut chat = NullChat {};
+        let mut game = Game::new(&mut logger, &mut chat);
+        game.add_player("Alice");
+        game.add_player("Bob");
+        game.execute(&parsed("team list"));
+        game.logger.assert_logged(Level::Info, "There are no teams");
+        game.execute(&parsed("team add red {\"text\":\"Red Team\"}"));
+        game.logger
+            .assert_logged(Level::Info, "Created team [Red Team]");
+        game.execute(&parsed("team add red"));
+        game.logger
+            .assert_logged(Level::Fail, "A team already exists by that name");
+        game.execute(&parsed("team add blue"));
+        game.logger
+            .assert_logged(Level::Info, "Created team [blue]");
+        game.execute(&parsed("team list"));
+        game.logger
+            .assert_logged(Level::Info, "There are 2 team(s): [blue], [Red Team]");
+
+        game.execute(&parsed("team join red @a"));
+        game.logger
+            .assert_logged(Level::Info, "Added 2 members to team [Red Team]");
+        game.execute_as("Bob", &parsed("team join blue"));
+        game.logger
+            .assert_logged(Level::Info, "Added Bob to team [blue]");
+        game.execute(&parsed("team join green Alice"));
+        game.logger
+            .assert_logged(Level::Fail, "Unknown team 'green'");
+        game.execute(&parsed("team list red"));
+        game.logger
+            .assert_logged(Level::Info, "Team [Red Team] has 1 member(s): Alice");
+        let selected = |game: &Game<LoggerSpy, NullChat>, selector: &str| match parsed(&format!(
+            "kill {}",
+            selector
+        )) {
+            Command::Kill(Kill {
+                targets: Some(Target::Selector(selector)),
+            }) => game
+                .select(&selector)
+                .iter()
+                .map(|e| e.display_name().plain())
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+        game.spawn("pig");
+        assert_eq!(selected(&game, "@a[team=red]"), vec!["Alice"]);
+        assert_eq!(selected(&game, "@a[team=!red]"), vec!["Bob"]);
+        assert_eq!(selected(&game, "@e[team=]"), vec!["Pig"]);
+        assert_eq!(selected(&game, "@e[team=!]"), vec!["Alice", "Bob"]);
+
+        game.execute(&parsed("team modify red color dark_red"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Updated the color for team [Red Team] to dark_red",
+        );
+        game.execute(&parsed("team modify red color dark_red"));
+        game.logger.assert_logged(
+            Level::Fail,
+            "Nothing changed. That team already has that color",
+        );
+        game.execute(&parsed("team modify red friendlyFire false"));
+        game.logger
+            .assert_logged(Level::Info, "Disabled friendly fire for team [Red Team]");
+        game.execute(&parsed("team modify red nametagVisibility hideForOwnTeam"));
+        game.logger.assert_logged(
+            Level::Info,
+            "Nametag visibility for team [Red Team] is now \"Hide for own team\"",
+        );
+        game.execute(&parsed("team modify red prefix {\"text\":\"[R] \"}"));
+        game.logger
+            .assert_logged(Level::Info, "Team prefix set to [R] ");
+        game.execute(&parsed("team modify red displayName {\"text\":\"Reds\"}"));
+        game.logger
+            .assert_logged(Level::Info, "Updated the name of team [Reds]");
+
+        game.execute(&parsed("team modify blue color blue"));
+        game.logger.skip();
+        game.execute(&parsed("scoreboard objectives add kills teamkill.blue"));
+        game.execute(&parsed(
+            "scoreboard objectives add deaths killedByTeam.dark_red",
+        ));
+        game.execute(&parsed(
+            "scoreboard objectives setdisplay sidebar.team.dark_red kills",
+        ));
+        game.logger.skip();
+        game.logger.skip();
+        game.logger.assert_logged(
+            Level::Info,
+            "Set display slot sidebar.team.dark_red to show objective kills",
+        );
+        let alice = game.find_player("Alice").unwrap().uuid;
+        game.player_dies("Bob", Some(alice));
+        assert_eq!(game.score(&holder("Alice"), "kills"), Some(1));
+        assert_eq!(game.score(&holder("Bob"), "deaths"), Some(1));
+
+        game.execute(&parsed("team leave @a"));
+        game.logger
+            .assert_logged(Level::Info, "Removed 2 members from any team");
+        game.execute(&parsed("team empty red"));
+        game.logger
+            .assert_logged(Level::Fail, "Nothing changed. That team is already empty");
+        game.execute(&parsed("team join red Carol"));
+        game.execute(&parsed("team empty red"));
+        game.logger.skip();
+        game.logger
+            .assert_logged(Level::Info, "Removed 1 member(s) from team [Reds]");
+        game.execute(&parsed("team remove red"));
+        game.logger
+            .assert_logged(Level::Info, "Removed team [Reds]");
+        game.execute(&parsed("team list red"));
+        game.logger.assert_logged(Level::Fail, "Unknown team 'red'");
     }
 }
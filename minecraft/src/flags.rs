@@ -0,0 +1,24 @@
+use std::env;
+
+/// Colors are on unless `--no-color` is passed or `NO_COLOR` is set.
+pub fn colors_enabled(args: &[String]) -> bool {
+    !args.iter().any(|arg| arg == "--no-color") && env::var_os("NO_COLOR").is_none()
+}
+
+/// `--structures=<dir>` points at structure templates on disk.
+pub fn structure_directory(args: &[String]) -> Option<&str> {
+    args.iter()
+        .find_map(|arg| arg.strip_prefix("--structures="))
+}
+
+/// `--verbose` shows all feedback, including that of commands run by functions.
+pub fn verbose(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--verbose")
+}
+
+/// `--seed=<n>` seeds the world's randomness.
+pub fn seed(args: &[String]) -> Option<i64> {
+    args.iter()
+        .find_map(|arg| arg.strip_prefix("--seed="))
+        .and_then(|seed| seed.parse().ok())
+}
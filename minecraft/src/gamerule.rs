@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A rule set with `gamerule`, changing how the game behaves.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum GameRule {
+    AnnounceAdvancements,
+    BlockExplosionDropDecay,
+    CommandBlockOutput,
+    CommandModificationBlockLimit,
+    DisableElytraMovementCheck,
+    DisableRaids,
+    DoDaylightCycle,
+    DoEntityDrops,
+    DoFireTick,
+    DoImmediateRespawn,
+    DoInsomnia,
+    DoLimitedCrafting,
+    DoMobLoot,
+    DoMobSpawning,
+    DoPatrolSpawning,
+    DoTileDrops,
+    DoTraderSpawning,
+    DoVinesSpread,
+    DoWardenSpawning,
+    DoWeatherCycle,
+    DrowningDamage,
+    EnderPearlsVanishOnDeath,
+    FallDamage,
+    FireDamage,
+    ForgiveDeadPlayers,
+    FreezeDamage,
+    GlobalSoundEvents,
+    KeepInventory,
+    LavaSourceConversion,
+    LogAdminCommands,
+    MaxCommandChainLength,
+    MaxCommandForkCount,
+    MaxEntityCramming,
+    MobExplosionDropDecay,
+    MobGriefing,
+    NaturalRegeneration,
+    PlayersNetherPortalCreativeDelay,
+    PlayersNetherPortalDefaultDelay,
+    PlayersSleepingPercentage,
+    ProjectilesCanBreakBlocks,
+    RandomTickSpeed,
+    ReducedDebugInfo,
+    SendCommandFeedback,
+    ShowDeathMessages,
+    SnowAccumulationHeight,
+    SpawnChunkRadius,
+    SpawnRadius,
+    SpectatorsGenerateChunks,
+    TntExplosionDropDecay,
+    UniversalAnger,
+    WaterSourceConversion,
+}
+
+/// A gamerule's setting. Each rule keeps the type of its default.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum GameRuleValue {
+    Bool(bool),
+    Int(i32),
+}
+
+const GAME_RULES: [(GameRule, &str, GameRuleValue); 51] = [
+    (
+        GameRule::AnnounceAdvancements,
+        "announceAdvancements",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::BlockExplosionDropDecay,
+        "blockExplosionDropDecay",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::CommandBlockOutput,
+        "commandBlockOutput",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::CommandModificationBlockLimit,
+        "commandModificationBlockLimit",
+        GameRuleValue::Int(32768),
+    ),
+    (
+        GameRule::DisableElytraMovementCheck,
+        "disableElytraMovementCheck",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::DisableRaids,
+        "disableRaids",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::DoDaylightCycle,
+        "doDaylightCycle",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoEntityDrops,
+        "doEntityDrops",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoFireTick,
+        "doFireTick",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoImmediateRespawn,
+        "doImmediateRespawn",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::DoInsomnia,
+        "doInsomnia",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoLimitedCrafting,
+        "doLimitedCrafting",
+        GameRuleValue::Bool(false),
+    ),
+    (GameRule::DoMobLoot, "doMobLoot", GameRuleValue::Bool(true)),
+    (
+        GameRule::DoMobSpawning,
+        "doMobSpawning",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoPatrolSpawning,
+        "doPatrolSpawning",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoTileDrops,
+        "doTileDrops",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoTraderSpawning,
+        "doTraderSpawning",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoVinesSpread,
+        "doVinesSpread",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoWardenSpawning,
+        "doWardenSpawning",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DoWeatherCycle,
+        "doWeatherCycle",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::DrowningDamage,
+        "drowningDamage",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::EnderPearlsVanishOnDeath,
+        "enderPearlsVanishOnDeath",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::FallDamage,
+        "fallDamage",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::FireDamage,
+        "fireDamage",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::ForgiveDeadPlayers,
+        "forgiveDeadPlayers",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::FreezeDamage,
+        "freezeDamage",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::GlobalSoundEvents,
+        "globalSoundEvents",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::KeepInventory,
+        "keepInventory",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::LavaSourceConversion,
+        "lavaSourceConversion",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::LogAdminCommands,
+        "logAdminCommands",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::MaxCommandChainLength,
+        "maxCommandChainLength",
+        GameRuleValue::Int(65536),
+    ),
+    (
+        GameRule::MaxCommandForkCount,
+        "maxCommandForkCount",
+        GameRuleValue::Int(65536),
+    ),
+    (
+        GameRule::MaxEntityCramming,
+        "maxEntityCramming",
+        GameRuleValue::Int(24),
+    ),
+    (
+        GameRule::MobExplosionDropDecay,
+        "mobExplosionDropDecay",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::MobGriefing,
+        "mobGriefing",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::NaturalRegeneration,
+        "naturalRegeneration",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::PlayersNetherPortalCreativeDelay,
+        "playersNetherPortalCreativeDelay",
+        GameRuleValue::Int(1),
+    ),
+    (
+        GameRule::PlayersNetherPortalDefaultDelay,
+        "playersNetherPortalDefaultDelay",
+        GameRuleValue::Int(80),
+    ),
+    (
+        GameRule::PlayersSleepingPercentage,
+        "playersSleepingPercentage",
+        GameRuleValue::Int(100),
+    ),
+    (
+        GameRule::ProjectilesCanBreakBlocks,
+        "projectilesCanBreakBlocks",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::RandomTickSpeed,
+        "randomTickSpeed",
+        GameRuleValue::Int(3),
+    ),
+    (
+        GameRule::ReducedDebugInfo,
+        "reducedDebugInfo",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::SendCommandFeedback,
+        "sendCommandFeedback",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::ShowDeathMessages,
+        "showDeathMessages",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::SnowAccumulationHeight,
+        "snowAccumulationHeight",
+        GameRuleValue::Int(1),
+    ),
+    (
+        GameRule::SpawnChunkRadius,
+        "spawnChunkRadius",
+        GameRuleValue::Int(2),
+    ),
+    (GameRule::SpawnRadius, "spawnRadius", GameRuleValue::Int(10)),
+    (
+        GameRule::SpectatorsGenerateChunks,
+        "spectatorsGenerateChunks",
+        GameRuleValue::Bool(true),
+    ),
+    (
+        GameRule::TntExplosionDropDecay,
+        "tntExplosionDropDecay",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::UniversalAnger,
+        "universalAnger",
+        GameRuleValue::Bool(false),
+    ),
+    (
+        GameRule::WaterSourceConversion,
+        "waterSourceConversion",
+        GameRuleValue::Bool(true),
+    ),
+];
+
+impl GameRule {
+    pub fn from_name(name: &str) -> Option<GameRule> {
+        GAME_RULES
+            .iter()
+            .find(|(_, n, _)| *n == name)
+            .map(|(rule, _, _)| *rule)
+    }
+
+    /// The setting in a new world.
+    pub fn default_value(self) -> GameRuleValue {
+        GAME_RULES
+            .iter()
+            .find(|(rule, _, _)| *rule == self)
+            .map(|(_, _, value)| *value)
+            .unwrap()
+    }
+}
+
+impl Display for GameRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (_, name, _) = GAME_RULES.iter().find(|(rule, _, _)| rule == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+impl GameRuleValue {
+    /// The value as a command result: 1 or 0 for true or false.
+    pub fn as_int(self) -> i32 {
+        match self {
+            GameRuleValue::Bool(value) => value as i32,
+            GameRuleValue::Int(value) => value,
+        }
+    }
+}
+
+impl Display for GameRuleValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GameRuleValue::Bool(value) => write!(f, "{}", value),
+            GameRuleValue::Int(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Every gamerule's setting, starting from the defaults.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct GameRules {
+    changed: HashMap<GameRule, GameRuleValue>,
+}
+
+impl GameRules {
+    pub fn new() -> GameRules {
+        GameRules::default()
+    }
+
+    pub fn get(&self, rule: GameRule) -> GameRuleValue {
+        self.changed
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_value())
+    }
+
+    /// A true/false rule's setting. Number rules read as true when positive.
+    pub fn bool(&self, rule: GameRule) -> bool {
+        self.get(rule).as_int() > 0
+    }
+
+    pub fn int(&self, rule: GameRule) -> i32 {
+        self.get(rule).as_int()
+    }
+
+    /// Changes a rule, provided `value` is of the rule's type.
+    pub fn set(&mut self, rule: GameRule, value: GameRuleValue) -> bool {
+        let same_type = matches!(
+            (rule.default_value(), value),
+            (GameRuleValue::Bool(_), GameRuleValue::Bool(_))
+                | (GameRuleValue::Int(_), GameRuleValue::Int(_))
+        );
+        if same_type {
+            self.changed.insert(rule, value);
+        }
+        same_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_and_changes() {
+        let mut rules = GameRules::new();
+        assert!(rules.bool(GameRule::SendCommandFeedback));
+        assert_eq!(rules.int(GameRule::MaxCommandChainLength), 65536);
+        assert!(!rules.set(GameRule::KeepInventory, GameRuleValue::Int(1)));
+        assert!(rules.set(GameRule::KeepInventory, GameRuleValue::Bool(true)));
+        assert!(rules.bool(GameRule::KeepInventory));
+        assert_eq!(
+            GameRule::from_name("commandModificationBlockLimit"),
+            Some(GameRule::CommandModificationBlockLimit)
+        );
+        assert_eq!(GameRule::RandomTickSpeed.to_string(), "randomTickSpeed");
+        assert_eq!(GameRule::from_name("keepinventory"), None);
+    }
+}
@@ -0,0 +1,534 @@
+use crate::entity::{namespaced, type_name};
+use crate::json::Json;
+use crate::nbt::Nbt;
+use crate::text;
+use crate::text::TextComponent;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Some items together in one slot: the item's id, how many there are and the item's data
+/// components, like `diamond_sword[custom_name='"Ruby"']`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ItemStack {
+    pub id: String,
+    pub count: i32,
+    pub components: BTreeMap<String, Nbt>,
+}
+
+impl ItemStack {
+    pub fn new(id: &str, count: i32) -> ItemStack {
+        ItemStack {
+            id: String::from(id),
+            count,
+            components: BTreeMap::new(),
+        }
+    }
+
+    /// The stack with its id and component names namespaced, as it is kept in inventories.
+    pub fn namespaced(&self) -> ItemStack {
+        ItemStack {
+            id: namespaced(&self.id),
+            count: self.count,
+            components: self
+                .components
+                .iter()
+                .map(|(name, value)| (namespaced(name), value.clone()))
+                .collect(),
+        }
+    }
+
+    /// Whether the two stacks could be merged: the same item with the same components.
+    pub fn same_item(&self, other: &ItemStack) -> bool {
+        let (a, b) = (self.namespaced(), other.namespaced());
+        a.id == b.id && a.components == b.components
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count <= 0 || namespaced(&self.id) == "minecraft:air"
+    }
+
+    /// How many fit in one slot: the `max_stack_size` component if there is one, otherwise a
+    /// guess from the id, since there is no item registry. Tools, weapons and armor don't
+    /// stack, and a handful of throwables and buckets stop at 16.
+    pub fn max_stack_size(&self) -> i32 {
+        let stack = self.namespaced();
+        if let Some(Nbt::Int(size)) = stack.components.get("minecraft:max_stack_size") {
+            return *size;
+        }
+        let path = stack.id.rsplit(':').next().unwrap_or_default();
+        const UNSTACKABLE: [&str; 14] = [
+            "_sword",
+            "_pickaxe",
+            "_axe",
+            "_shovel",
+            "_hoe",
+            "_helmet",
+            "_chestplate",
+            "_leggings",
+            "_boots",
+            "bow",
+            "trident",
+            "shield",
+            "elytra",
+            "potion",
+        ];
+        const SIXTEEN: [&str; 5] = ["ender_pearl", "snowball", "egg", "bucket", "honey_bottle"];
+        if path.ends_with("_bucket") || UNSTACKABLE.iter().any(|end| path.ends_with(end)) {
+            1
+        } else if SIXTEEN.contains(&path) || path.ends_with("_sign") || path.ends_with("_banner") {
+            16
+        } else {
+            64
+        }
+    }
+
+    /// The name shown in feedback, in brackets like vanilla's hover text: the custom name,
+    /// else the item name, else a name made from the id.
+    pub fn display_name(&self) -> TextComponent {
+        let stack = self.namespaced();
+        let name = ["minecraft:custom_name", "minecraft:item_name"]
+            .iter()
+            .find_map(|component| stack.components.get(*component))
+            .and_then(Nbt::as_str)
+            .map(|name| text::parse(name).unwrap_or_else(|_| TextComponent::text(name)))
+            .unwrap_or_else(|| TextComponent::text(&type_name(&stack.id)));
+        let mut brackets = TextComponent::text("[");
+        brackets.extra.push(name);
+        brackets.extra.push(TextComponent::text("]"));
+        brackets
+    }
+
+    /// Applies an item modifier, as `item modify` does. Only the common functions are known:
+    /// `set_count`, `set_name`, `set_custom_data`, `set_components` and `sequence`, or a list
+    /// of functions. Anything else leaves the stack unchanged.
+    pub fn modify(&mut self, modifier: &Json) {
+        if let Some(functions) = modifier.as_array() {
+            for function in functions {
+                self.modify(function);
+            }
+            return;
+        }
+        let function = modifier
+            .get("function")
+            .and_then(Json::as_str)
+            .map(namespaced)
+            .unwrap_or_default();
+        match function.as_str() {
+            "minecraft:sequence" => {
+                if let Some(functions) = modifier.get("functions") {
+                    self.modify(functions);
+                }
+            }
+            "minecraft:set_count" => {
+                if let Some(count) = modifier.get("count").and_then(Json::as_i32) {
+                    let add = modifier.get("add").and_then(Json::as_bool).unwrap_or(false);
+                    self.count = if add { self.count + count } else { count }
+                        .clamp(0, self.max_stack_size());
+                }
+            }
+            "minecraft:set_name" => {
+                if let Some(name) = modifier.get("name") {
+                    let key = match modifier.get("target").and_then(Json::as_str) {
+                        Some("item_name") => "minecraft:item_name",
+                        _ => "minecraft:custom_name",
+                    };
+                    self.components
+                        .insert(String::from(key), Nbt::String(name.to_string()));
+                }
+            }
+            "minecraft:set_custom_data" => {
+                let tag = match modifier.get("tag") {
+                    Some(Json::String(snbt)) => crate::nbt::parse(snbt).ok(),
+                    Some(tag) => Some(to_nbt(tag)),
+                    None => None,
+                };
+                if let Some(tag) = tag {
+                    self.components
+                        .insert(String::from("minecraft:custom_data"), tag);
+                }
+            }
+            "minecraft:set_components" => {
+                if let Some(Json::Object(components)) = modifier.get("components") {
+                    for (name, value) in components {
+                        match name.strip_prefix('!') {
+                            Some(removed) => {
+                                self.components.remove(removed);
+                                self.components.remove(&namespaced(removed));
+                            }
+                            None => {
+                                self.components.insert(namespaced(name), to_nbt(value));
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        *self = self.namespaced();
+    }
+}
+
+impl Display for ItemStack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if !self.components.is_empty() {
+            let components: Vec<String> = self
+                .components
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            write!(f, "[{}]", components.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// JSON as NBT, the way data components written in JSON are stored: whole numbers become
+/// ints and booleans bytes.
+fn to_nbt(json: &Json) -> Nbt {
+    match json {
+        Json::Null => Nbt::compound(),
+        Json::Bool(b) => Nbt::Byte(*b as i8),
+        Json::Number(n) if n.fract() == 0.0 && n.abs() <= f64::from(i32::MAX) => {
+            Nbt::Int(*n as i32)
+        }
+        Json::Number(n) => Nbt::Double(*n),
+        Json::String(s) => Nbt::String(s.clone()),
+        Json::Array(elements) => Nbt::List(elements.iter().map(to_nbt).collect()),
+        Json::Object(members) => Nbt::Compound(
+            members
+                .iter()
+                .map(|(key, value)| (key.clone(), to_nbt(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Which items `clear` and `execute if items` look for: `*` or an id, with component tests.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ItemPredicate {
+    /// `None` for `*`, which matches any item.
+    pub id: Option<String>,
+    pub tests: Vec<ComponentTest>,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum ComponentTest {
+    /// `name=value`: the component is exactly the value.
+    Equals(String, Nbt),
+    /// `name~value`: the component contains the value, as NBT paths match.
+    Matches(String, Nbt),
+}
+
+impl ItemPredicate {
+    pub fn matches(&self, stack: &ItemStack) -> bool {
+        let stack = stack.namespaced();
+        !stack.is_empty()
+            && self
+                .id
+                .as_ref()
+                .map(|id| namespaced(id) == stack.id)
+                .unwrap_or(true)
+            && self.tests.iter().all(|test| match test {
+                ComponentTest::Equals(name, value) => {
+                    stack.components.get(&namespaced(name)) == Some(value)
+                }
+                ComponentTest::Matches(name, value) => stack
+                    .components
+                    .get(&namespaced(name))
+                    .map(|component| component.matches(value))
+                    .unwrap_or(false),
+            })
+    }
+}
+
+impl Display for ItemPredicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id.as_deref().unwrap_or("*"))?;
+        if !self.tests.is_empty() {
+            let tests: Vec<String> = self
+                .tests
+                .iter()
+                .map(|test| match test {
+                    ComponentTest::Equals(name, value) => format!("{}={}", name, value),
+                    ComponentTest::Matches(name, value) => format!("{}~{}", name, value),
+                })
+                .collect();
+            write!(f, "[{}]", tests.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// The index of a single slot such as `hotbar.3` or `armor.head`, using vanilla's numbering.
+pub fn slot(name: &str) -> Option<i32> {
+    match slot_range(name)?.as_slice() {
+        [index] if !name.ends_with('*') => Some(*index),
+        _ => None,
+    }
+}
+
+/// The slots a name such as `container.*`, `armor.*` or `weapon.offhand` covers.
+pub fn slot_range(name: &str) -> Option<Vec<i32>> {
+    let numbered = |prefix: &str, start: i32, size: i32| -> Option<Vec<i32>> {
+        match name.strip_prefix(prefix)? {
+            "*" => Some((start..start + size).collect()),
+            n => n
+                .parse::<i32>()
+                .ok()
+                .filter(|n| (0..size).contains(n))
+                .map(|n| vec![start + n]),
+        }
+    };
+    match name {
+        "weapon" | "weapon.mainhand" => Some(vec![MAINHAND]),
+        "weapon.offhand" => Some(vec![OFFHAND]),
+        "weapon.*" => Some(vec![MAINHAND, OFFHAND]),
+        "armor.feet" => Some(vec![100]),
+        "armor.legs" => Some(vec![101]),
+        "armor.chest" => Some(vec![102]),
+        "armor.head" => Some(vec![103]),
+        "armor.body" => Some(vec![105]),
+        "armor.*" => Some(vec![100, 101, 102, 103, 105]),
+        _ => numbered("container.", 0, 36)
+            .or_else(|| numbered("hotbar.", 0, 9))
+            .or_else(|| numbered("inventory.", 9, 27)),
+    }
+}
+
+pub const MAINHAND: i32 = 98;
+pub const OFFHAND: i32 = 99;
+
+/// What an entity carries. Players have 36 container slots of which the first nine are the
+/// hotbar; every entity has hands and armor slots.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Inventory {
+    slots: HashMap<i32, ItemStack>,
+    /// The selected hotbar slot, which the main hand holds.
+    pub selected: i32,
+}
+
+impl Inventory {
+    pub fn new() -> Inventory {
+        Inventory::default()
+    }
+
+    fn index(&self, slot: i32) -> i32 {
+        match slot {
+            MAINHAND => self.selected,
+            other => other,
+        }
+    }
+
+    pub fn get(&self, slot: i32) -> Option<&ItemStack> {
+        self.slots.get(&self.index(slot))
+    }
+
+    /// Puts `stack` in the slot, emptying it if the stack is empty.
+    pub fn set(&mut self, slot: i32, stack: ItemStack) {
+        let index = self.index(slot);
+        if stack.is_empty() {
+            self.slots.remove(&index);
+        } else {
+            self.slots.insert(index, stack.namespaced());
+        }
+    }
+
+    /// Every occupied slot, in slot order.
+    pub fn stacks(&self) -> Vec<(i32, &ItemStack)> {
+        let mut stacks: Vec<(i32, &ItemStack)> = self
+            .slots
+            .iter()
+            .map(|(slot, stack)| (*slot, stack))
+            .collect();
+        stacks.sort_by_key(|(slot, _)| *slot);
+        stacks
+    }
+
+    /// Adds items the way picking them up does: onto matching stacks in the main hand, the
+    /// off hand and then the container slots, and then into the first empty container slot.
+    /// Returns how many didn't fit.
+    pub fn add(&mut self, stack: &ItemStack) -> i32 {
+        let stack = stack.namespaced();
+        let max = stack.max_stack_size();
+        let mut remaining = stack.count;
+        for slot in vec![self.selected, OFFHAND].into_iter().chain(0..36) {
+            if remaining == 0 {
+                break;
+            }
+            if let Some(existing) = self.slots.get_mut(&slot) {
+                if existing.same_item(&stack) && existing.count < max {
+                    let moved = remaining.min(max - existing.count);
+                    existing.count += moved;
+                    remaining -= moved;
+                }
+            }
+        }
+        for slot in 0..36 {
+            if remaining == 0 {
+                break;
+            }
+            if let Entry::Vacant(entry) = self.slots.entry(slot) {
+                let moved = remaining.min(max);
+                entry.insert(ItemStack {
+                    count: moved,
+                    ..stack.clone()
+                });
+                remaining -= moved;
+            }
+        }
+        remaining
+    }
+
+    /// Removes up to `max` items matching `predicate`, or all of them when `max` is `None`,
+    /// returning how many were removed.
+    pub fn remove(&mut self, predicate: &ItemPredicate, max: Option<i32>) -> i32 {
+        let mut slots: Vec<i32> = self.slots.keys().copied().collect();
+        slots.sort_unstable();
+        let mut removed = 0;
+        for slot in slots {
+            let stack = self.slots.get_mut(&slot).unwrap();
+            if !predicate.matches(stack) {
+                continue;
+            }
+            let taken = match max {
+                Some(max) => stack.count.min(max - removed),
+                None => stack.count,
+            };
+            stack.count -= taken;
+            removed += taken;
+            if stack.count <= 0 {
+                self.slots.remove(&slot);
+            }
+        }
+        removed
+    }
+
+    /// How many items matching `predicate` are in `slots`.
+    pub fn count(&self, slots: &[i32], predicate: &ItemPredicate) -> i32 {
+        slots
+            .iter()
+            .filter_map(|slot| self.get(*slot))
+            .filter(|stack| predicate.matches(stack))
+            .map(|stack| stack.count)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn any(id: &str) -> ItemPredicate {
+        ItemPredicate {
+            id: Some(id.to_string()),
+            tests: vec![],
+        }
+    }
+
+    #[test]
+    fn slot_names() {
+        assert_eq!(slot("container.0"), Some(0));
+        assert_eq!(slot("hotbar.8"), Some(8));
+        assert_eq!(slot("inventory.0"), Some(9));
+        assert_eq!(slot("inventory.27"), None);
+        assert_eq!(slot("armor.head"), Some(103));
+        assert_eq!(slot("weapon"), Some(MAINHAND));
+        assert_eq!(slot("hotbar.*"), None);
+        assert_eq!(slot_range("hotbar.*"), Some((0..9).collect()));
+        assert_eq!(slot_range("weapon.*"), Some(vec![MAINHAND, OFFHAND]));
+        assert_eq!(slot_range("pocket.1"), None);
+    }
+
+    #[test]
+    fn stack_sizes() {
+        assert_eq!(ItemStack::new("diamond", 1).max_stack_size(), 64);
+        assert_eq!(ItemStack::new("diamond_sword", 1).max_stack_size(), 1);
+        assert_eq!(ItemStack::new("water_bucket", 1).max_stack_size(), 1);
+        assert_eq!(ItemStack::new("ender_pearl", 1).max_stack_size(), 16);
+        let mut stone = ItemStack::new("stone", 1);
+        stone
+            .components
+            .insert("max_stack_size".to_string(), Nbt::Int(99));
+        assert_eq!(stone.max_stack_size(), 99);
+    }
+
+    #[test]
+    fn adding_fills_stacks_then_empty_slots() {
+        let mut inventory = Inventory::new();
+        inventory.set(5, ItemStack::new("diamond", 60));
+        assert_eq!(inventory.add(&ItemStack::new("diamond", 10)), 0);
+        assert_eq!(inventory.get(5).unwrap().count, 64);
+        assert_eq!(inventory.get(0).unwrap().count, 6);
+        assert_eq!(inventory.get(MAINHAND).unwrap().id, "minecraft:diamond");
+        assert_eq!(inventory.add(&ItemStack::new("diamond_sword", 40)), 6);
+        assert_eq!(inventory.stacks().len(), 36);
+    }
+
+    #[test]
+    fn removing_and_counting() {
+        let mut inventory = Inventory::new();
+        inventory.add(&ItemStack::new("diamond", 100));
+        inventory.set(OFFHAND, ItemStack::new("diamond", 3));
+        assert_eq!(
+            inventory.count(&(0..36).collect::<Vec<_>>(), &any("diamond")),
+            100
+        );
+        assert_eq!(inventory.remove(&any("minecraft:diamond"), Some(70)), 70);
+        assert_eq!(inventory.remove(&any("diamond"), None), 33);
+        assert_eq!(inventory.stacks(), vec![]);
+    }
+
+    #[test]
+    fn predicates() {
+        let mut named = ItemStack::new("diamond", 1);
+        named.components.insert(
+            "custom_data".to_string(),
+            crate::nbt::parse("{shop:1b,price:5}").unwrap(),
+        );
+        let partial = ItemPredicate {
+            id: None,
+            tests: vec![ComponentTest::Matches(
+                "minecraft:custom_data".to_string(),
+                crate::nbt::parse("{shop:1b}").unwrap(),
+            )],
+        };
+        assert!(partial.matches(&named));
+        let exact = ItemPredicate {
+            id: Some("diamond".to_string()),
+            tests: vec![ComponentTest::Equals(
+                "custom_data".to_string(),
+                crate::nbt::parse("{shop:1b}").unwrap(),
+            )],
+        };
+        assert!(!exact.matches(&named));
+        assert!(!any("emerald").matches(&named));
+        assert_eq!(partial.to_string(), "*[minecraft:custom_data~{shop:1b}]");
+    }
+
+    #[test]
+    fn modifiers() {
+        let mut stack = ItemStack::new("diamond", 5);
+        stack.modify(
+            &crate::json::parse(
+                r#"[{"function":"set_count","count":2,"add":true},
+                    {"function":"minecraft:set_name","name":"Gem"},
+                    {"function":"set_custom_data","tag":"{gem:1b}"},
+                    {"function":"set_components","components":{"max_stack_size":16,"!custom_data":{}}}]"#,
+            )
+            .unwrap(),
+        );
+        assert_eq!(stack.count, 7);
+        assert_eq!(stack.display_name().plain(), "[Gem]");
+        assert_eq!(
+            stack.to_string(),
+            "minecraft:diamond[minecraft:custom_name='\"Gem\"',minecraft:max_stack_size=16]"
+        );
+        assert_eq!(
+            ItemStack::new("golden_apple", 1).display_name().plain(),
+            "[Golden Apple]"
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    UnexpectedChar(char),
+    UnexpectedEnd,
+    Number,
+    Escape,
+    TrailingCharacters,
+}
+
+impl Json {
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_f64().map(|n| n as i32)
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Json>> {
+        match self {
+            Json::Array(elements) => Some(elements),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) => write!(f, "{}", n),
+            Json::String(s) => write!(f, "{}", quote(s)),
+            Json::Array(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{}", quote(key), value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+pub fn quote(text: &str) -> String {
+    let mut output = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+pub fn parse(text: &str) -> Result<Json, Error> {
+    let mut chars = text.chars().peekable();
+    let value = value(&mut chars)?;
+    skip_whitespace(&mut chars);
+    match chars.peek() {
+        Some(_) => Err(Error::TrailingCharacters),
+        None => Ok(value),
+    }
+}
+
+type Chars<'a> = Peekable<std::str::Chars<'a>>;
+
+fn value(chars: &mut Chars) -> Result<Json, Error> {
+    skip_whitespace(chars);
+    match chars.peek().copied() {
+        Some('{') => object(chars),
+        Some('[') => array(chars),
+        Some('"') => string(chars).map(Json::String),
+        Some(c) if c == '-' || c.is_ascii_digit() => number(chars),
+        Some(c) if c.is_alphabetic() => literal(chars),
+        Some(c) => Err(Error::UnexpectedChar(c)),
+        None => Err(Error::UnexpectedEnd),
+    }
+}
+
+fn object(chars: &mut Chars) -> Result<Json, Error> {
+    expect(chars, '{')?;
+    let mut members = Vec::new();
+    skip_whitespace(chars);
+    if chars.peek() == Some(&'}') {
+        chars.next();
+        return Ok(Json::Object(members));
+    }
+    loop {
+        skip_whitespace(chars);
+        let key = string(chars)?;
+        skip_whitespace(chars);
+        expect(chars, ':')?;
+        let value = value(chars)?;
+        members.push((key, value));
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(Json::Object(members)),
+            Some(c) => return Err(Error::UnexpectedChar(c)),
+            None => return Err(Error::UnexpectedEnd),
+        }
+    }
+}
+
+fn array(chars: &mut Chars) -> Result<Json, Error> {
+    expect(chars, '[')?;
+    let mut elements = Vec::new();
+    skip_whitespace(chars);
+    if chars.peek() == Some(&']') {
+        chars.next();
+        return Ok(Json::Array(elements));
+    }
+    loop {
+        elements.push(value(chars)?);
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some(']') => return Ok(Json::Array(elements)),
+            Some(c) => return Err(Error::UnexpectedChar(c)),
+            None => return Err(Error::UnexpectedEnd),
+        }
+    }
+}
+
+fn string(chars: &mut Chars) -> Result<String, Error> {
+    expect(chars, '"')?;
+    let mut content = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(content),
+            Some('\\') => content.push(escape(chars)?),
+            Some(c) => content.push(c),
+            None => return Err(Error::UnexpectedEnd),
+        }
+    }
+}
+
+fn escape(chars: &mut Chars) -> Result<char, Error> {
+    match chars.next() {
+        Some('"') => Ok('"'),
+        Some('\\') => Ok('\\'),
+        Some('/') => Ok('/'),
+        Some('b') => Ok('\u{8}'),
+        Some('f') => Ok('\u{c}'),
+        Some('n') => Ok('\n'),
+        Some('r') => Ok('\r'),
+        Some('t') => Ok('\t'),
+        Some('u') => {
+            let unit = code_unit(chars)?;
+            let code = match unit {
+                0xd800..=0xdbff => {
+                    if chars.next() != Some('\\') || chars.next() != Some('u') {
+                        return Err(Error::Escape);
+                    }
+                    match code_unit(chars)? {
+                        low @ 0xdc00..=0xdfff => 0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00),
+                        _ => return Err(Error::Escape),
+                    }
+                }
+                _ => unit,
+            };
+            std::char::from_u32(code).ok_or(Error::Escape)
+        }
+        Some(_) => Err(Error::Escape),
+        None => Err(Error::UnexpectedEnd),
+    }
+}
+
+/// The four hex digits of a `\u` escape. Characters outside the basic plane take two of
+/// these, a high surrogate followed by a low one.
+fn code_unit(chars: &mut Chars) -> Result<u32, Error> {
+    let hex: String = chars.take(4).collect();
+    u32::from_str_radix(&hex, 16).map_err(|_| Error::Escape)
+}
+
+fn number(chars: &mut Chars) -> Result<Json, Error> {
+    let mut lexeme = String::new();
+    while let Some(c) = chars.peek().copied() {
+        if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E' {
+            lexeme.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    lexeme.parse().map(Json::Number).map_err(|_| Error::Number)
+}
+
+fn literal(chars: &mut Chars) -> Result<Json, Error> {
+    let mut lexeme = String::new();
+    while let Some(c) = chars.peek().copied() {
+        if c.is_alphabetic() {
+            lexeme.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    match lexeme.as_str() {
+        "true" => Ok(Json::Bool(true)),
+        "false" => Ok(Json::Bool(false)),
+        "null" => Ok(Json::Null),
+        _ => Err(Error::UnexpectedChar(lexeme.chars().next().unwrap())),
+    }
+}
+
+fn expect(chars: &mut Chars, expected: char) -> Result<(), Error> {
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        Some(c) => Err(Error::UnexpectedChar(c)),
+        None => Err(Error::UnexpectedEnd),
+    }
+}
+
+fn skip_whitespace(chars: &mut Chars) {
+    while chars.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+        chars.next();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives() {
+        assert_eq!(parse("null"), Ok(Json::Null));
+        assert_eq!(parse("true"), Ok(Json::Bool(true)));
+        assert_eq!(parse(" false "), Ok(Json::Bool(false)));
+        assert_eq!(parse("-12.5"), Ok(Json::Number(-12.5)));
+        assert_eq!(parse("3e2"), Ok(Json::Number(300.0)));
+        assert_eq!(
+            parse("\"a \\\"quoted\\\" \\u0041\""),
+            Ok(Json::String("a \"quoted\" A".to_string()))
+        );
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(
+            parse("{\"a\": [1, {\"b\": null}], \"c\": \"d\"}"),
+            Ok(Json::Object(vec![
+                (
+                    "a".to_string(),
+                    Json::Array(vec![
+                        Json::Number(1.0),
+                        Json::Object(vec![("b".to_string(), Json::Null)])
+                    ])
+                ),
+                ("c".to_string(), Json::String("d".to_string())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("{\"a\" 1}"), Err(Error::UnexpectedChar('1')));
+        assert_eq!(parse("[1, 2"), Err(Error::UnexpectedEnd));
+        assert_eq!(parse("nope"), Err(Error::UnexpectedChar('n')));
+        assert_eq!(parse("1 2"), Err(Error::TrailingCharacters));
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        assert_eq!(
+            parse("\"\\ud83d\\ude00\""),
+            Ok(Json::String("\u{1f600}".to_string()))
+        );
+        assert_eq!(parse("\"\\ud83d\""), Err(Error::Escape));
+        assert_eq!(parse("\"\\ud83d\\u0041\""), Err(Error::Escape));
+        assert_eq!(parse("\"\\ude00\""), Err(Error::Escape));
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "{\"text\":\"line\\nbreak\",\"extra\":[1,true,null]}";
+        assert_eq!(parse(text).unwrap().to_string(), text);
+    }
+}
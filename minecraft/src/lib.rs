@@ -1,11 +1,31 @@
 #![allow(dead_code)]
 
+use block::Block;
+use gamerule::{GameRule, GameRuleValue};
+use item::{ItemPredicate, ItemStack};
+use nbt::Nbt;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use text::{Color, Style, TextComponent};
 
+pub mod advancement;
+pub mod ansi;
+pub mod block;
+pub mod bossbar;
+pub mod entity;
 pub mod execute;
+pub mod flags;
+pub mod gamerule;
+pub mod item;
+pub mod json;
 pub mod lower;
+pub mod nbt;
 pub mod parse;
+pub mod random;
+pub mod scoreboard;
+pub mod structure;
+pub mod team;
+pub mod text;
 
 /// See [commands](commands.html) for more information
 #[derive(Debug, PartialEq)]
@@ -14,6 +34,31 @@ pub enum Command {
     Function(FunctionIdentifier),
     Execute(Execute),
     Tellraw(Tellraw),
+    Say(Say),
+    Tell(Tell),
+    Me(Me),
+    Title(Title),
+    Summon(Summon),
+    Kill(Kill),
+    Tag(Tag),
+    Teleport(Teleport),
+    SetBlock(SetBlock),
+    Fill(Fill),
+    Clone(CloneBlocks),
+    Place(Place),
+    Give(Give),
+    Clear(Clear),
+    Item(Item),
+    Trigger(Trigger),
+    Team(Team),
+    Bossbar(Bossbar),
+    Gamerule(Gamerule),
+    Time(Time),
+    Weather(Weather),
+    /// Sets the difficulty, or reads it without one.
+    Difficulty(Option<Difficulty>),
+    Random(Random),
+    Advancement(Advancement),
 }
 
 #[derive(Debug, PartialEq)]
@@ -31,16 +76,162 @@ pub enum Objectives {
     SetDisplay(ObjectivesSetDisplay),
 }
 
-#[derive(Debug, PartialEq)]
+/// What an objective counts. Most criteria are kept up to date by the game; the read-only ones
+/// mirror a value of the player and can't be changed by commands.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub enum Criteria {
     Dummy,
+    Trigger,
+    DeathCount,
+    PlayerKillCount,
+    TotalKillCount,
+    Health,
+    Xp,
+    Level,
+    Food,
+    Air,
+    Armor,
+    TeamKill(Color),
+    KilledByTeam(Color),
+    /// A statistic, like `minecraft.used:minecraft.carrot_on_a_stick`. The id is namespaced.
+    Statistic(StatType, String),
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum StatType {
+    Mined,
+    Crafted,
+    Used,
+    Broken,
+    PickedUp,
+    Dropped,
+    Killed,
+    KilledBy,
+    Custom,
+}
+
+const STAT_TYPES: [(StatType, &str); 9] = [
+    (StatType::Mined, "mined"),
+    (StatType::Crafted, "crafted"),
+    (StatType::Used, "used"),
+    (StatType::Broken, "broken"),
+    (StatType::PickedUp, "picked_up"),
+    (StatType::Dropped, "dropped"),
+    (StatType::Killed, "killed"),
+    (StatType::KilledBy, "killed_by"),
+    (StatType::Custom, "custom"),
+];
+
+const SIMPLE_CRITERIA: [(Criteria, &str); 11] = [
+    (Criteria::Dummy, "dummy"),
+    (Criteria::Trigger, "trigger"),
+    (Criteria::DeathCount, "deathCount"),
+    (Criteria::PlayerKillCount, "playerKillCount"),
+    (Criteria::TotalKillCount, "totalKillCount"),
+    (Criteria::Health, "health"),
+    (Criteria::Xp, "xp"),
+    (Criteria::Level, "level"),
+    (Criteria::Food, "food"),
+    (Criteria::Air, "air"),
+    (Criteria::Armor, "armor"),
+];
+
+impl Criteria {
+    /// Reads a criterion as `scoreboard objectives add` takes it. Statistics may leave out the
+    /// `minecraft.` namespaces, as in `used:carrot_on_a_stick`.
+    pub fn from_name(name: &str) -> Option<Criteria> {
+        if let Some((criteria, _)) = SIMPLE_CRITERIA.iter().find(|(_, n)| *n == name) {
+            return Some(criteria.clone());
+        }
+        if let Some(color) = name.strip_prefix("teamkill.") {
+            return team_color(color).map(Criteria::TeamKill);
+        }
+        if let Some(color) = name.strip_prefix("killedByTeam.") {
+            return team_color(color).map(Criteria::KilledByTeam);
+        }
+        let mut parts = name.splitn(2, ':');
+        let (stat_type, id) = (parts.next()?, parts.next()?);
+        let stat_type = match stat_type.split_once('.') {
+            Some(("minecraft", path)) => path,
+            Some(_) => return None,
+            None => stat_type,
+        };
+        let (stat_type, _) = STAT_TYPES.iter().find(|(_, n)| *n == stat_type)?;
+        let id = match id.split_once('.') {
+            Some((namespace, path)) => format!("{}:{}", namespace, path),
+            None => format!("minecraft:{}", id),
+        };
+        let valid = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-/".contains(c))
+        };
+        match id.split_once(':') {
+            Some((namespace, path)) if valid(namespace) && valid(path) => {
+                Some(Criteria::Statistic(*stat_type, id))
+            }
+            _ => None,
+        }
+    }
+
+    /// The criteria whose scores mirror a player's state, which commands may not change.
+    pub const READ_ONLY: [Criteria; 6] = [
+        Criteria::Health,
+        Criteria::Xp,
+        Criteria::Level,
+        Criteria::Food,
+        Criteria::Air,
+        Criteria::Armor,
+    ];
+
+    /// Whether commands are refused when they try to change scores for this criterion.
+    pub fn is_read_only(&self) -> bool {
+        Criteria::READ_ONLY.contains(self)
+    }
+
+    /// How new objectives with this criterion are shown.
+    pub fn default_render_type(&self) -> RenderType {
+        match self {
+            Criteria::Health => RenderType::Hearts,
+            _ => RenderType::Integer,
+        }
+    }
+}
+
+/// Teams can only have the sixteen named colors.
+pub(crate) fn team_color(name: &str) -> Option<Color> {
+    match Color::from_name(name) {
+        Some(Color::Hex(_)) | None => None,
+        color => color,
+    }
+}
+
+impl Display for Criteria {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Criteria::TeamKill(color) => write!(f, "teamkill.{}", color),
+            Criteria::KilledByTeam(color) => write!(f, "killedByTeam.{}", color),
+            Criteria::Statistic(stat_type, id) => write!(
+                f,
+                "minecraft.{}:{}",
+                STAT_TYPES.iter().find(|(t, _)| t == stat_type).unwrap().1,
+                id.replacen(':', ".", 1)
+            ),
+            simple => write!(
+                f,
+                "{}",
+                SIMPLE_CRITERIA.iter().find(|(c, _)| c == simple).unwrap().1
+            ),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct ObjectivesAdd {
     pub objective: String,
     pub criteria: Criteria,
-    pub display_name: Option<String>,
+    pub display_name: Option<TextComponent>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -51,8 +242,19 @@ pub struct ObjectivesModify {
 
 #[derive(Debug, PartialEq)]
 pub enum Modification {
-    DisplayName(String),
+    DisplayName(TextComponent),
     RenderType(RenderType),
+    DisplayAutoUpdate(bool),
+    /// `None` puts back the default of plain numbers.
+    NumberFormat(Option<NumberFormat>),
+}
+
+/// How scores are written in the sidebar, the player list and below names.
+#[derive(PartialEq, Clone, Debug)]
+pub enum NumberFormat {
+    Blank,
+    Fixed(TextComponent),
+    Styled(Style),
 }
 
 #[derive(PartialEq, Copy, Clone, Debug)]
@@ -77,25 +279,39 @@ pub enum DisplaySlot {
     BelowName,
     List,
     Sidebar,
+    /// The sidebar as seen by players on teams of this color, instead of the usual one.
+    SidebarTeam(Color),
+}
+
+impl DisplaySlot {
+    pub fn from_name(name: &str) -> Option<DisplaySlot> {
+        match name {
+            "belowName" => Some(DisplaySlot::BelowName),
+            "list" => Some(DisplaySlot::List),
+            "sidebar" => Some(DisplaySlot::Sidebar),
+            _ => name
+                .strip_prefix("sidebar.team.")
+                .and_then(team_color)
+                .map(DisplaySlot::SidebarTeam),
+        }
+    }
 }
 
 impl Display for DisplaySlot {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                DisplaySlot::BelowName => String::from("belowName"),
-                DisplaySlot::List => String::from("list"),
-                DisplaySlot::Sidebar => String::from("sidebar"),
-            }
-        )
+        match self {
+            DisplaySlot::BelowName => write!(f, "belowName"),
+            DisplaySlot::List => write!(f, "list"),
+            DisplaySlot::Sidebar => write!(f, "sidebar"),
+            DisplaySlot::SidebarTeam(color) => write!(f, "sidebar.team.{}", color),
+        }
     }
 }
 
 #[derive(Debug, PartialEq)]
 pub enum Players {
     Add(PlayersAdd),
+    Display(PlayersDisplay),
     Enable(PlayersEnable),
     Get(PlayersGet),
     List(PlayersList),
@@ -112,6 +328,20 @@ pub struct PlayersAdd {
     pub score: i32,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct PlayersDisplay {
+    pub targets: Target,
+    pub objective: String,
+    pub option: ScoreDisplayOption,
+}
+
+/// What `scoreboard players display` changes about a score. `None` clears it.
+#[derive(Debug, PartialEq)]
+pub enum ScoreDisplayOption {
+    Name(Option<TextComponent>),
+    NumberFormat(Option<NumberFormat>),
+}
+
 #[derive(Debug, PartialEq)]
 pub struct PlayersEnable {
     pub targets: Target,
@@ -177,18 +407,28 @@ pub struct FunctionIdentifier {
     pub name: String,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Target {
     Name(String),
     Selector(Selector),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Selector {
     pub variable: SelectorVariable,
+    pub arguments: Vec<SelectorArgument>,
 }
 
-#[derive(Debug, PartialEq)]
+impl Selector {
+    pub fn new(variable: SelectorVariable) -> Selector {
+        Selector {
+            variable,
+            arguments: vec![],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum SelectorVariable {
     P,
     R,
@@ -197,6 +437,67 @@ pub enum SelectorVariable {
     S,
 }
 
+/// A `key=value` filter inside a selector's brackets. Negatable arguments are written `key=!value`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SelectorArgument {
+    Type {
+        kind: String,
+        negated: bool,
+    },
+    /// `tag=` with an empty tag matches entities without any tags.
+    Tag {
+        tag: String,
+        negated: bool,
+    },
+    Name {
+        name: String,
+        negated: bool,
+    },
+    /// `team=` with an empty name matches entities on no team; negated, those on any team.
+    Team {
+        team: String,
+        negated: bool,
+    },
+    Limit(i32),
+    Scores(Vec<(String, Interval)>),
+    /// `x`, `y` and `z` move the position distances and volumes are measured from.
+    X(f64),
+    Y(f64),
+    Z(f64),
+    Distance(FloatRange),
+    Dx(f64),
+    Dy(f64),
+    Dz(f64),
+    Sort(Sort),
+    /// `predicate=` names one of the datapack's predicates, tested with each entity as `this`.
+    Predicate {
+        predicate: String,
+        negated: bool,
+    },
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Sort {
+    Nearest,
+    Furthest,
+    Arbitrary,
+    Random,
+}
+
+/// A range of decimal numbers such as `..5.5` or `1..`; either end may be open.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct FloatRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl FloatRange {
+    pub fn contains(&self, value: f64) -> bool {
+        self.min.map(|min| min <= value).unwrap_or(true)
+            && self.max.map(|max| value <= max).unwrap_or(true)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Function {
     pub identifier: FunctionIdentifier,
@@ -206,11 +507,85 @@ pub struct Function {
 #[derive(Debug, PartialEq)]
 pub enum Execute {
     If(If),
+    Unless(If),
+    Store(Store),
+}
+
+/// `execute store result|success <target> run <command>` saves what the command returned, or
+/// whether it succeeded, into `target`.
+#[derive(Debug, PartialEq)]
+pub struct Store {
+    pub kind: StoreKind,
+    pub target: StoreTarget,
+    pub command: Box<Command>,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum StoreKind {
+    Result,
+    Success,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StoreTarget {
+    Bossbar(String, BossbarValue),
+    Score(Target, String),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BossbarValue {
+    Value,
+    Max,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum If {
     Score(Score),
+    Block(BlockCondition),
+    Blocks(BlocksCondition),
+    Items(ItemsCondition),
+    Predicate(PredicateCondition),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BlockCondition {
+    pub position: BlockPos,
+    pub block: Block,
+    pub command: Box<Command>,
+}
+
+/// Compares the region from `start` to `end` with the same-sized region whose lowest corner is
+/// `destination`.
+#[derive(Debug, PartialEq)]
+pub struct BlocksCondition {
+    pub start: BlockPos,
+    pub end: BlockPos,
+    pub destination: BlockPos,
+    pub mode: BlocksMode,
+    pub command: Box<Command>,
+}
+
+/// Passes when any of the targets has a matching item in one of the slots.
+#[derive(Debug, PartialEq)]
+pub struct ItemsCondition {
+    pub targets: Target,
+    pub slots: String,
+    pub predicate: ItemPredicate,
+    pub command: Box<Command>,
+}
+
+/// Passes when the datapack's predicate `predicate` holds for the executor.
+#[derive(Debug, PartialEq)]
+pub struct PredicateCondition {
+    pub predicate: String,
+    pub command: Box<Command>,
+}
+
+/// `masked` ignores air in the source region.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BlocksMode {
+    All,
+    Masked,
 }
 
 #[derive(Debug, PartialEq)]
@@ -240,7 +615,7 @@ pub struct RangeComparison {
     command: Box<Command>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Interval {
     Value(i32),
     Bounded(i32, i32),
@@ -251,5 +626,685 @@ pub enum Interval {
 #[derive(Debug, PartialEq)]
 pub struct Tellraw {
     target: Target,
-    message: String,
+    message: TextComponent,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Say {
+    pub message: String,
+}
+
+/// `tell`, `msg` and `w` are aliases and all parse to this.
+#[derive(Debug, PartialEq)]
+pub struct Tell {
+    pub targets: Target,
+    pub message: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Me {
+    pub action: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Title {
+    pub targets: Target,
+    pub action: TitleAction,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TitleAction {
+    Title(TextComponent),
+    Subtitle(TextComponent),
+    Actionbar(TextComponent),
+    Times {
+        fade_in: i32,
+        stay: i32,
+        fade_out: i32,
+    },
+    Clear,
+    Reset,
+}
+
+/// A position as written in commands. Each axis is absolute or relative (`~`) to the position
+/// the command runs at, or all three are local (`^left ^up ^forwards`) to its rotation.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Vec3 {
+    pub x: Coordinate,
+    pub y: Coordinate,
+    pub z: Coordinate,
+}
+
+/// A block position. Absolute axes are whole numbers; resolved positions are floored.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlockPos {
+    pub x: Coordinate,
+    pub y: Coordinate,
+    pub z: Coordinate,
+}
+
+/// A column position, without the height. Local coordinates are not allowed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Vec2 {
+    pub x: Coordinate,
+    pub z: Coordinate,
+}
+
+/// Yaw and pitch as written in commands; each is absolute or relative (`~`).
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rotation {
+    pub yaw: Coordinate,
+    pub pitch: Coordinate,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Coordinate {
+    /// The flag is set when the number was written with a decimal point.
+    Absolute(f64, bool),
+    Relative(f64),
+    Local(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Summon {
+    pub entity: String,
+    pub position: Option<Vec3>,
+    pub nbt: Option<Nbt>,
+}
+
+/// `kill` without targets kills the command's source.
+#[derive(Debug, PartialEq)]
+pub struct Kill {
+    pub targets: Option<Target>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Tag {
+    pub targets: Target,
+    pub action: TagAction,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TagAction {
+    Add(String),
+    Remove(String),
+    List,
+}
+
+/// `tp`/`teleport`. Without targets the command's source is teleported.
+#[derive(Debug, PartialEq)]
+pub struct Teleport {
+    pub targets: Option<Target>,
+    pub destination: Destination,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Destination {
+    Entity(Target),
+    Location(Vec3, Option<Orientation>),
+}
+
+/// Which way teleported entities face when a location is given.
+#[derive(Debug, PartialEq)]
+pub enum Orientation {
+    Rotation(Rotation),
+    FacingLocation(Vec3),
+    FacingEntity(Target, Anchor),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Anchor {
+    Eyes,
+    Feet,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SetBlock {
+    pub position: BlockPos,
+    pub block: Block,
+    pub mode: SetBlockMode,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SetBlockMode {
+    Replace,
+    Keep,
+    Destroy,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Fill {
+    pub from: BlockPos,
+    pub to: BlockPos,
+    pub block: Block,
+    pub mode: FillMode,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FillMode {
+    /// Replaces every block, or only those matching the filter.
+    Replace(Option<Block>),
+    Destroy,
+    /// Fills the outer layer and empties the inside.
+    Hollow,
+    Keep,
+    /// Fills the outer layer and leaves the inside alone.
+    Outline,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CloneBlocks {
+    pub begin: BlockPos,
+    pub end: BlockPos,
+    pub destination: BlockPos,
+    pub mask: CloneMask,
+    pub mode: CloneMode,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CloneMask {
+    Replace,
+    /// Skips air in the source region.
+    Masked,
+    Filtered(Block),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum CloneMode {
+    /// Clones even when the source and destination overlap.
+    Force,
+    /// Leaves air where the source blocks were.
+    Move,
+    Normal,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Place {
+    Template(PlaceTemplate),
+}
+
+/// `place template`, which stamps a structure template with its origin at `position`.
+#[derive(Debug, PartialEq)]
+pub struct PlaceTemplate {
+    pub template: String,
+    pub position: Option<BlockPos>,
+    pub rotation: StructureRotation,
+    pub mirror: Mirror,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum StructureRotation {
+    None,
+    Clockwise90,
+    Clockwise180,
+    Counterclockwise90,
+}
+
+impl Display for StructureRotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                StructureRotation::None => "none",
+                StructureRotation::Clockwise90 => "clockwise_90",
+                StructureRotation::Clockwise180 => "180",
+                StructureRotation::Counterclockwise90 => "counterclockwise_90",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Mirror {
+    None,
+    LeftRight,
+    FrontBack,
+}
+
+impl Display for Mirror {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Mirror::None => "none",
+                Mirror::LeftRight => "left_right",
+                Mirror::FrontBack => "front_back",
+            }
+        )
+    }
+}
+
+/// `trigger <objective> [add|set <value>]`, which lets players change a score once it has been
+/// enabled for them. Without an action it adds 1.
+#[derive(Debug, PartialEq)]
+pub struct Trigger {
+    pub objective: String,
+    pub action: Option<TriggerAction>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TriggerAction {
+    Add(i32),
+    Set(i32),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Team {
+    Add(TeamAdd),
+    Remove(String),
+    Empty(String),
+    /// Without members, the command's source joins.
+    Join(String, Option<Target>),
+    Leave(Target),
+    List(Option<String>),
+    Modify(TeamModify),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TeamAdd {
+    pub team: String,
+    pub display_name: Option<TextComponent>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TeamModify {
+    pub team: String,
+    pub option: TeamOption,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TeamOption {
+    DisplayName(TextComponent),
+    /// `None` is `reset`, which leaves members' names uncolored.
+    Color(Option<Color>),
+    FriendlyFire(bool),
+    SeeFriendlyInvisibles(bool),
+    NametagVisibility(Visibility),
+    DeathMessageVisibility(Visibility),
+    CollisionRule(CollisionRule),
+    Prefix(TextComponent),
+    Suffix(TextComponent),
+}
+
+/// Who sees the name tags or death messages of a team's members.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Visibility {
+    Always,
+    Never,
+    HideForOtherTeams,
+    HideForOwnTeam,
+}
+
+impl Visibility {
+    pub fn from_name(name: &str) -> Option<Visibility> {
+        match name {
+            "always" => Some(Visibility::Always),
+            "never" => Some(Visibility::Never),
+            "hideForOtherTeams" => Some(Visibility::HideForOtherTeams),
+            "hideForOwnTeam" => Some(Visibility::HideForOwnTeam),
+            _ => None,
+        }
+    }
+
+    /// How command feedback describes the value.
+    pub fn label(self) -> &'static str {
+        match self {
+            Visibility::Always => "Always",
+            Visibility::Never => "Never",
+            Visibility::HideForOtherTeams => "Hide for other teams",
+            Visibility::HideForOwnTeam => "Hide for own team",
+        }
+    }
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Visibility::Always => "always",
+                Visibility::Never => "never",
+                Visibility::HideForOtherTeams => "hideForOtherTeams",
+                Visibility::HideForOwnTeam => "hideForOwnTeam",
+            }
+        )
+    }
+}
+
+/// Which entities a team's members push around.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum CollisionRule {
+    Always,
+    Never,
+    PushOtherTeams,
+    PushOwnTeam,
+}
+
+impl CollisionRule {
+    pub fn from_name(name: &str) -> Option<CollisionRule> {
+        match name {
+            "always" => Some(CollisionRule::Always),
+            "never" => Some(CollisionRule::Never),
+            "pushOtherTeams" => Some(CollisionRule::PushOtherTeams),
+            "pushOwnTeam" => Some(CollisionRule::PushOwnTeam),
+            _ => None,
+        }
+    }
+
+    /// How command feedback describes the value.
+    pub fn label(self) -> &'static str {
+        match self {
+            CollisionRule::Always => "Always",
+            CollisionRule::Never => "Never",
+            CollisionRule::PushOtherTeams => "Push other teams",
+            CollisionRule::PushOwnTeam => "Push own team",
+        }
+    }
+}
+
+impl Display for CollisionRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                CollisionRule::Always => "always",
+                CollisionRule::Never => "never",
+                CollisionRule::PushOtherTeams => "pushOtherTeams",
+                CollisionRule::PushOwnTeam => "pushOwnTeam",
+            }
+        )
+    }
+}
+
+/// `gamerule <rule> [<value>]`, which reads the rule without a value.
+#[derive(Debug, PartialEq)]
+pub struct Gamerule {
+    pub rule: GameRule,
+    pub value: Option<GameRuleValue>,
+}
+
+/// `bossbar`, for the custom boss bars datapacks show progress with. Ids are resource locations.
+#[derive(Debug, PartialEq)]
+pub enum Bossbar {
+    Add(String, TextComponent),
+    Remove(String),
+    List,
+    Get(String, BossbarGet),
+    Set(String, BossbarSet),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BossbarGet {
+    Max,
+    Players,
+    Value,
+    Visible,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BossbarSet {
+    Color(BossbarColor),
+    Max(i32),
+    Name(TextComponent),
+    /// Without targets, nobody sees the bar.
+    Players(Option<Target>),
+    Style(BossbarStyle),
+    Value(i32),
+    Visible(bool),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BossbarColor {
+    Blue,
+    Green,
+    Pink,
+    Purple,
+    Red,
+    White,
+    Yellow,
+}
+
+const BOSSBAR_COLORS: [(BossbarColor, &str); 7] = [
+    (BossbarColor::Blue, "blue"),
+    (BossbarColor::Green, "green"),
+    (BossbarColor::Pink, "pink"),
+    (BossbarColor::Purple, "purple"),
+    (BossbarColor::Red, "red"),
+    (BossbarColor::White, "white"),
+    (BossbarColor::Yellow, "yellow"),
+];
+
+impl BossbarColor {
+    pub fn from_name(name: &str) -> Option<BossbarColor> {
+        BOSSBAR_COLORS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(color, _)| *color)
+    }
+}
+
+impl Display for BossbarColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (_, name) = BOSSBAR_COLORS.iter().find(|(c, _)| c == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+/// A continuous bar, or one divided into 6, 10, 12 or 20 notches.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BossbarStyle {
+    Progress,
+    Notched6,
+    Notched10,
+    Notched12,
+    Notched20,
+}
+
+const BOSSBAR_STYLES: [(BossbarStyle, &str); 5] = [
+    (BossbarStyle::Progress, "progress"),
+    (BossbarStyle::Notched6, "notched_6"),
+    (BossbarStyle::Notched10, "notched_10"),
+    (BossbarStyle::Notched12, "notched_12"),
+    (BossbarStyle::Notched20, "notched_20"),
+];
+
+impl BossbarStyle {
+    pub fn from_name(name: &str) -> Option<BossbarStyle> {
+        BOSSBAR_STYLES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(style, _)| *style)
+    }
+
+    pub fn notches(self) -> usize {
+        match self {
+            BossbarStyle::Progress => 1,
+            BossbarStyle::Notched6 => 6,
+            BossbarStyle::Notched10 => 10,
+            BossbarStyle::Notched12 => 12,
+            BossbarStyle::Notched20 => 20,
+        }
+    }
+}
+
+impl Display for BossbarStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (_, name) = BOSSBAR_STYLES.iter().find(|(s, _)| s == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Give {
+    pub targets: Target,
+    pub item: ItemStack,
+    pub count: Option<i32>,
+}
+
+/// Without targets the source's inventory is cleared; without an item, everything.
+#[derive(Debug, PartialEq)]
+pub struct Clear {
+    pub targets: Option<Target>,
+    pub item: Option<ItemPredicate>,
+    /// A maximum of zero counts matching items without removing any.
+    pub max_count: Option<i32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Item {
+    Replace(ItemReplace),
+    Modify(ItemModify),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ItemReplace {
+    pub targets: Target,
+    pub slot: String,
+    pub source: ItemSource,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ItemSource {
+    With(ItemStack, Option<i32>),
+    /// Another entity's slot, optionally passed through an item modifier.
+    From(Target, String, Option<String>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ItemModify {
+    pub targets: Target,
+    pub slot: String,
+    pub modifier: String,
+}
+
+/// `time`. Times are in ticks; `day`, `noon` and the like are read as the ticks they stand for.
+#[derive(Debug, PartialEq)]
+pub enum Time {
+    Set(i32),
+    Add(i32),
+    Query(TimeQuery),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum TimeQuery {
+    /// Ticks since the last sunrise.
+    Daytime,
+    /// Ticks the world has run for.
+    Gametime,
+    /// Days the world has seen.
+    Day,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Weather {
+    pub kind: WeatherKind,
+    /// In ticks. Without one the game picks how long it lasts.
+    pub duration: Option<i32>,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum WeatherKind {
+    Clear,
+    Rain,
+    Thunder,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+const DIFFICULTIES: [(Difficulty, &str); 4] = [
+    (Difficulty::Peaceful, "peaceful"),
+    (Difficulty::Easy, "easy"),
+    (Difficulty::Normal, "normal"),
+    (Difficulty::Hard, "hard"),
+];
+
+impl Difficulty {
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        DIFFICULTIES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(difficulty, _)| *difficulty)
+    }
+
+    /// 0 for peaceful up to 3 for hard.
+    pub fn id(self) -> i32 {
+        DIFFICULTIES
+            .iter()
+            .position(|(difficulty, _)| *difficulty == self)
+            .unwrap() as i32
+    }
+
+    /// The name the options screen gives it, like `Peaceful`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Peaceful => "Peaceful",
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (_, name) = DIFFICULTIES.iter().find(|(d, _)| d == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+/// `random`. Values and rolls come from the named sequence, or the world's randomness without
+/// one.
+#[derive(Debug, PartialEq)]
+pub enum Random {
+    Value(Interval, Option<String>),
+    /// Like `Value`, but tells every player what was rolled.
+    Roll(Interval, Option<String>),
+    Reset(RandomReset),
+}
+
+/// `advancement grant|revoke <targets> ...`, for the players in `targets`.
+#[derive(Debug, PartialEq)]
+pub struct Advancement {
+    pub action: AdvancementAction,
+    pub targets: Target,
+    pub selection: AdvancementSelection,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum AdvancementAction {
+    Grant,
+    Revoke,
+}
+
+/// Which advancements change: all of them, or some following the tree from the named one.
+#[derive(Debug, PartialEq)]
+pub enum AdvancementSelection {
+    Everything,
+    /// The advancement, or just one of its criteria.
+    Only(String, Option<String>),
+    /// The advancement and all that come after it.
+    From(String),
+    /// The advancement and all that lead up to it.
+    Until(String),
+    /// The advancement, all that lead up to it and all that come after it.
+    Through(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RandomReset {
+    /// `None` for `*`, every sequence.
+    pub sequence: Option<String>,
+    pub seed: Option<i64>,
+    pub include_world_seed: Option<bool>,
+    pub include_sequence_id: Option<bool>,
 }
@@ -6,6 +6,62 @@ pub fn lower(command: Command) -> String {
         Command::Function(f) => function(f),
         Command::Execute(e) => execute(e),
         Command::Tellraw(t) => tellraw(t),
+        Command::Say(s) => format!("say {}", s.message),
+        Command::Tell(t) => format!("tell {} {}", target(t.targets), t.message),
+        Command::Me(m) => format!("me {}", m.action),
+        Command::Title(t) => title(t),
+        Command::Summon(s) => summon(s),
+        Command::Kill(k) => match k.targets {
+            Some(targets) => format!("kill {}", target(targets)),
+            None => String::from("kill"),
+        },
+        Command::Tag(t) => tag(t),
+        Command::Teleport(t) => teleport(t),
+        Command::SetBlock(s) => setblock(s),
+        Command::Fill(f) => fill(f),
+        Command::Clone(c) => clone(c),
+        Command::Place(Place::Template(t)) => place_template(t),
+        Command::Give(g) => match g.count {
+            Some(count) => format!("give {} {} {}", target(g.targets), g.item, count),
+            None => format!("give {} {}", target(g.targets), g.item),
+        },
+        Command::Clear(c) => clear(c),
+        Command::Item(i) => item(i),
+        Command::Team(t) => team(t),
+        Command::Bossbar(b) => bossbar(b),
+        Command::Time(t) => match t {
+            Time::Set(time) => format!("time set {}", time),
+            Time::Add(time) => format!("time add {}", time),
+            Time::Query(TimeQuery::Daytime) => String::from("time query daytime"),
+            Time::Query(TimeQuery::Gametime) => String::from("time query gametime"),
+            Time::Query(TimeQuery::Day) => String::from("time query day"),
+        },
+        Command::Weather(w) => {
+            let kind = match w.kind {
+                WeatherKind::Clear => "clear",
+                WeatherKind::Rain => "rain",
+                WeatherKind::Thunder => "thunder",
+            };
+            match w.duration {
+                Some(duration) => format!("weather {} {}", kind, duration),
+                None => format!("weather {}", kind),
+            }
+        }
+        Command::Random(r) => random(r),
+        Command::Advancement(a) => advancement(a),
+        Command::Difficulty(d) => match d {
+            Some(difficulty) => format!("difficulty {}", difficulty),
+            None => String::from("difficulty"),
+        },
+        Command::Gamerule(g) => match g.value {
+            Some(value) => format!("gamerule {} {}", g.rule, value),
+            None => format!("gamerule {}", g.rule),
+        },
+        Command::Trigger(t) => match t.action {
+            Some(TriggerAction::Add(value)) => format!("trigger {} add {}", t.objective, value),
+            Some(TriggerAction::Set(value)) => format!("trigger {} set {}", t.objective, value),
+            None => format!("trigger {}", t.objective),
+        },
     }
 }
 
@@ -20,21 +76,15 @@ fn objectives(objectives: Objectives) -> String {
     match objectives {
         Objectives::Add(a) => match &a.display_name {
             Some(display) => format!(
-                "scoreboard objectives add {} {} \"{}\"",
-                a.objective,
-                criteria(a.criteria),
-                display,
-            ),
-            None => format!(
-                "scoreboard objectives add {} {}",
-                a.objective,
-                criteria(a.criteria),
+                "scoreboard objectives add {} {} {}",
+                a.objective, a.criteria, display,
             ),
+            None => format!("scoreboard objectives add {} {}", a.objective, a.criteria,),
         },
         Objectives::List => String::from("scoreboard objectives list"),
         Objectives::Modify(m) => match &m.modification {
             Modification::DisplayName(display) => format!(
-                "scoreboard objectives modify {} displayname \"{}\"",
+                "scoreboard objectives modify {} displayname {}",
                 m.objective, display
             ),
             Modification::RenderType(render) => match render {
@@ -47,24 +97,31 @@ fn objectives(objectives: Objectives) -> String {
                     m.objective
                 ),
             },
-        },
-        Objectives::Remove(r) => format!("scoreboard objectives remove {}", r.objective),
-        Objectives::SetDisplay(sd) => match &sd.slot {
-            DisplaySlot::BelowName => format!(
-                "scoreboard objectives setdisplay belowName {}",
-                sd.objective
+            Modification::DisplayAutoUpdate(update) => format!(
+                "scoreboard objectives modify {} displayautoupdate {}",
+                m.objective, update
+            ),
+            Modification::NumberFormat(format) => format!(
+                "scoreboard objectives modify {} numberformat{}",
+                m.objective,
+                number_format(format.as_ref())
             ),
-            DisplaySlot::List => format!("scoreboard objectives setdisplay list {}", sd.objective),
-            DisplaySlot::Sidebar => {
-                format!("scoreboard objectives setdisplay sidebar {}", sd.objective)
-            }
         },
+        Objectives::Remove(r) => format!("scoreboard objectives remove {}", r.objective),
+        Objectives::SetDisplay(sd) => format!(
+            "scoreboard objectives setdisplay {} {}",
+            sd.slot, sd.objective
+        ),
     }
 }
 
-fn criteria(criteria: Criteria) -> String {
-    match criteria {
-        Criteria::Dummy => String::from("dummy"),
+/// A number format with the space before it, or nothing to reset it.
+fn number_format(format: Option<&NumberFormat>) -> String {
+    match format {
+        Some(NumberFormat::Blank) => String::from(" blank"),
+        Some(NumberFormat::Fixed(text)) => format!(" fixed {}", text),
+        Some(NumberFormat::Styled(style)) => format!(" styled {}", style),
+        None => String::new(),
     }
 }
 
@@ -76,6 +133,20 @@ fn players(players: Players) -> String {
             a.objective,
             a.score
         ),
+        Players::Display(d) => match d.option {
+            ScoreDisplayOption::Name(name) => format!(
+                "scoreboard players display name {} {}{}",
+                target(d.targets),
+                d.objective,
+                name.map(|name| format!(" {}", name)).unwrap_or_default()
+            ),
+            ScoreDisplayOption::NumberFormat(format) => format!(
+                "scoreboard players display numberformat {} {}{}",
+                target(d.targets),
+                d.objective,
+                number_format(format.as_ref())
+            ),
+        },
         Players::Enable(e) => format!(
             "scoreboard players enable {} {}",
             target(e.targets),
@@ -88,7 +159,7 @@ fn players(players: Players) -> String {
         ),
         Players::List(l) => match l.target {
             Some(t) => format!("scoreboard players list {}", target(t)),
-            None => format!("scoreboard players list"),
+            None => String::from("scoreboard players list"),
         },
         Players::Operation(o) => format!(
             "scoreboard players operation {} {} {} {} {}",
@@ -139,7 +210,7 @@ fn function(function: FunctionIdentifier) -> String {
     }
 }
 
-fn target(target: Target) -> String {
+pub(crate) fn target(target: Target) -> String {
     match target {
         Target::Name(n) => n,
         Target::Selector(s) => selector(s),
@@ -147,24 +218,176 @@ fn target(target: Target) -> String {
 }
 
 fn selector(selector: Selector) -> String {
-    match selector.variable {
-        SelectorVariable::P => String::from("@p"),
-        SelectorVariable::R => String::from("@r"),
-        SelectorVariable::A => String::from("@a"),
-        SelectorVariable::E => String::from("@e"),
-        SelectorVariable::S => String::from("@s"),
+    let variable = match selector.variable {
+        SelectorVariable::P => "@p",
+        SelectorVariable::R => "@r",
+        SelectorVariable::A => "@a",
+        SelectorVariable::E => "@e",
+        SelectorVariable::S => "@s",
+    };
+    if selector.arguments.is_empty() {
+        return String::from(variable);
+    }
+    let arguments: Vec<String> = selector
+        .arguments
+        .into_iter()
+        .map(selector_argument)
+        .collect();
+    format!("{}[{}]", variable, arguments.join(","))
+}
+
+fn selector_argument(argument: SelectorArgument) -> String {
+    let negation = |negated| if negated { "!" } else { "" };
+    match argument {
+        SelectorArgument::Type { kind, negated } => format!("type={}{}", negation(negated), kind),
+        SelectorArgument::Tag { tag, negated } => format!("tag={}{}", negation(negated), tag),
+        SelectorArgument::Name { name, negated } if name.contains(' ') => {
+            format!("name={}\"{}\"", negation(negated), name)
+        }
+        SelectorArgument::Name { name, negated } => format!("name={}{}", negation(negated), name),
+        SelectorArgument::Team { team, negated } => {
+            format!("team={}{}", negation(negated), team)
+        }
+        SelectorArgument::Limit(limit) => format!("limit={}", limit),
+        SelectorArgument::Scores(scores) => {
+            let scores: Vec<String> = scores
+                .into_iter()
+                .map(|(objective, range)| format!("{}={}", objective, interval(range)))
+                .collect();
+            format!("scores={{{}}}", scores.join(","))
+        }
+        SelectorArgument::X(x) => format!("x={}", x),
+        SelectorArgument::Y(y) => format!("y={}", y),
+        SelectorArgument::Z(z) => format!("z={}", z),
+        SelectorArgument::Distance(range) => format!("distance={}", float_range(range)),
+        SelectorArgument::Dx(dx) => format!("dx={}", dx),
+        SelectorArgument::Dy(dy) => format!("dy={}", dy),
+        SelectorArgument::Dz(dz) => format!("dz={}", dz),
+        SelectorArgument::Sort(sort) => format!(
+            "sort={}",
+            match sort {
+                Sort::Nearest => "nearest",
+                Sort::Furthest => "furthest",
+                Sort::Arbitrary => "arbitrary",
+                Sort::Random => "random",
+            }
+        ),
+        SelectorArgument::Predicate { predicate, negated } => {
+            format!("predicate={}{}", negation(negated), predicate)
+        }
+    }
+}
+
+fn advancement(advancement: Advancement) -> String {
+    let selection = match advancement.selection {
+        AdvancementSelection::Everything => String::from("everything"),
+        AdvancementSelection::Only(id, Some(criterion)) => format!("only {} {}", id, criterion),
+        AdvancementSelection::Only(id, None) => format!("only {}", id),
+        AdvancementSelection::From(id) => format!("from {}", id),
+        AdvancementSelection::Until(id) => format!("until {}", id),
+        AdvancementSelection::Through(id) => format!("through {}", id),
+    };
+    format!(
+        "advancement {} {} {}",
+        match advancement.action {
+            AdvancementAction::Grant => "grant",
+            AdvancementAction::Revoke => "revoke",
+        },
+        target(advancement.targets),
+        selection
+    )
+}
+
+fn random(random: Random) -> String {
+    let sequence = |sequence: Option<String>| {
+        sequence
+            .map(|sequence| format!(" {}", sequence))
+            .unwrap_or_default()
+    };
+    match random {
+        Random::Value(range, s) => format!("random value {}{}", interval(range), sequence(s)),
+        Random::Roll(range, s) => format!("random roll {}{}", interval(range), sequence(s)),
+        Random::Reset(reset) => {
+            let mut line = format!(
+                "random reset {}",
+                reset.sequence.unwrap_or_else(|| String::from("*"))
+            );
+            if let Some(seed) = reset.seed {
+                line.push_str(&format!(" {}", seed));
+            }
+            for flag in [reset.include_world_seed, reset.include_sequence_id]
+                .iter()
+                .flatten()
+            {
+                line.push_str(&format!(" {}", flag));
+            }
+            line
+        }
+    }
+}
+
+fn float_range(range: FloatRange) -> String {
+    let bound = |bound: Option<f64>| bound.map(|b| b.to_string()).unwrap_or_default();
+    match (range.min, range.max) {
+        (Some(min), Some(max)) if min == max => min.to_string(),
+        (min, max) => format!("{}..{}", bound(min), bound(max)),
     }
 }
 
 fn execute(execute: Execute) -> String {
     match execute {
-        Execute::If(i) => execute_if(i),
+        Execute::If(i) => format!("execute if {}", execute_if(i)),
+        Execute::Unless(i) => format!("execute unless {}", execute_if(i)),
+        Execute::Store(s) => {
+            let kind = match s.kind {
+                StoreKind::Result => "result",
+                StoreKind::Success => "success",
+            };
+            let target = match s.target {
+                StoreTarget::Bossbar(id, BossbarValue::Value) => format!("bossbar {} value", id),
+                StoreTarget::Bossbar(id, BossbarValue::Max) => format!("bossbar {} max", id),
+                StoreTarget::Score(targets, objective) => {
+                    format!("score {} {}", target(targets), objective)
+                }
+            };
+            format!(
+                "execute store {} {} run {}",
+                kind,
+                target,
+                lower(*s.command)
+            )
+        }
     }
 }
 
 fn execute_if(i: If) -> String {
     match i {
         If::Score(s) => score(s),
+        If::Block(b) => format!(
+            "block {} {} run {}",
+            block_pos(b.position),
+            b.block,
+            lower(*b.command)
+        ),
+        If::Items(i) => format!(
+            "items entity {} {} {} run {}",
+            target(i.targets),
+            i.slots,
+            i.predicate,
+            lower(*i.command)
+        ),
+        If::Predicate(p) => format!("predicate {} run {}", p.predicate, lower(*p.command)),
+        If::Blocks(b) => format!(
+            "blocks {} {} {} {} run {}",
+            block_pos(b.start),
+            block_pos(b.end),
+            block_pos(b.destination),
+            match b.mode {
+                BlocksMode::All => "all",
+                BlocksMode::Masked => "masked",
+            },
+            lower(*b.command)
+        ),
     }
 }
 
@@ -181,7 +404,7 @@ fn score(s: Score) -> String {
 
 fn source_comparison(source_comparison: SourceComparison, operation: &str) -> String {
     format!(
-        "execute if score {} {} {} {} {} run {}",
+        "score {} {} {} {} {} run {}",
         target(source_comparison.target),
         source_comparison.target_objective,
         operation,
@@ -193,7 +416,7 @@ fn source_comparison(source_comparison: SourceComparison, operation: &str) -> St
 
 fn range_comparison(range_comparison: RangeComparison) -> String {
     format!(
-        "execute if score {} {} matches {} run {}",
+        "score {} {} matches {} run {}",
         target(range_comparison.target),
         range_comparison.target_objective,
         interval(range_comparison.interval),
@@ -211,7 +434,281 @@ fn interval(interval: Interval) -> String {
 }
 
 fn tellraw(tellraw: Tellraw) -> String {
-    format!("tellraw {} \"{}\"", target(tellraw.target), tellraw.message)
+    format!("tellraw {} {}", target(tellraw.target), tellraw.message)
+}
+
+fn summon(summon: Summon) -> String {
+    let mut output = format!("summon {}", summon.entity);
+    if let Some(position) = summon.position {
+        output.push_str(&format!(" {}", vec3(position)));
+    }
+    if let Some(nbt) = summon.nbt {
+        output.push_str(&format!(" {}", nbt));
+    }
+    output
+}
+
+fn setblock(setblock: SetBlock) -> String {
+    let mode = match setblock.mode {
+        SetBlockMode::Replace => "",
+        SetBlockMode::Keep => " keep",
+        SetBlockMode::Destroy => " destroy",
+    };
+    format!(
+        "setblock {} {}{}",
+        block_pos(setblock.position),
+        setblock.block,
+        mode
+    )
+}
+
+fn fill(fill: Fill) -> String {
+    let mode = match fill.mode {
+        FillMode::Replace(None) => String::new(),
+        FillMode::Replace(Some(filter)) => format!(" replace {}", filter),
+        FillMode::Destroy => String::from(" destroy"),
+        FillMode::Hollow => String::from(" hollow"),
+        FillMode::Keep => String::from(" keep"),
+        FillMode::Outline => String::from(" outline"),
+    };
+    format!(
+        "fill {} {} {}{}",
+        block_pos(fill.from),
+        block_pos(fill.to),
+        fill.block,
+        mode
+    )
+}
+
+fn clone(clone: CloneBlocks) -> String {
+    let mut output = format!(
+        "clone {} {} {}",
+        block_pos(clone.begin),
+        block_pos(clone.end),
+        block_pos(clone.destination)
+    );
+    let mask = match clone.mask {
+        CloneMask::Replace => String::from("replace"),
+        CloneMask::Masked => String::from("masked"),
+        CloneMask::Filtered(filter) => format!("filtered {}", filter),
+    };
+    match clone.mode {
+        CloneMode::Normal if mask == "replace" => {}
+        CloneMode::Normal => output.push_str(&format!(" {}", mask)),
+        CloneMode::Force => output.push_str(&format!(" {} force", mask)),
+        CloneMode::Move => output.push_str(&format!(" {} move", mask)),
+    }
+    output
+}
+
+fn clear(clear: Clear) -> String {
+    let mut output = String::from("clear");
+    if let Some(targets) = clear.targets {
+        output.push_str(&format!(" {}", target(targets)));
+    }
+    if let Some(item) = clear.item {
+        output.push_str(&format!(" {}", item));
+    }
+    if let Some(max_count) = clear.max_count {
+        output.push_str(&format!(" {}", max_count));
+    }
+    output
+}
+
+fn item(item: Item) -> String {
+    match item {
+        Item::Replace(replace) => {
+            let source = match replace.source {
+                ItemSource::With(stack, Some(count)) => format!("with {} {}", stack, count),
+                ItemSource::With(stack, None) => format!("with {}", stack),
+                ItemSource::From(source, slot, Some(modifier)) => {
+                    format!("from entity {} {} {}", target(source), slot, modifier)
+                }
+                ItemSource::From(source, slot, None) => {
+                    format!("from entity {} {}", target(source), slot)
+                }
+            };
+            format!(
+                "item replace entity {} {} {}",
+                target(replace.targets),
+                replace.slot,
+                source
+            )
+        }
+        Item::Modify(modify) => format!(
+            "item modify entity {} {} {}",
+            target(modify.targets),
+            modify.slot,
+            modify.modifier
+        ),
+    }
+}
+
+fn place_template(place: PlaceTemplate) -> String {
+    let mut output = format!("place template {}", place.template);
+    if let Some(position) = place.position {
+        output.push_str(&format!(" {}", block_pos(position)));
+        match (place.rotation, place.mirror) {
+            (StructureRotation::None, Mirror::None) => {}
+            (rotation, Mirror::None) => output.push_str(&format!(" {}", rotation)),
+            (rotation, mirror) => output.push_str(&format!(" {} {}", rotation, mirror)),
+        }
+    }
+    output
+}
+
+fn teleport(teleport: Teleport) -> String {
+    let mut output = String::from("tp");
+    if let Some(targets) = teleport.targets {
+        output.push_str(&format!(" {}", target(targets)));
+    }
+    match teleport.destination {
+        Destination::Entity(destination) => output.push_str(&format!(" {}", target(destination))),
+        Destination::Location(position, orientation) => {
+            output.push_str(&format!(" {}", vec3(position)));
+            match orientation {
+                Some(Orientation::Rotation(rotation)) => output.push_str(&format!(
+                    " {} {}",
+                    coordinate(rotation.yaw),
+                    coordinate(rotation.pitch)
+                )),
+                Some(Orientation::FacingLocation(position)) => {
+                    output.push_str(&format!(" facing {}", vec3(position)))
+                }
+                Some(Orientation::FacingEntity(entity, anchor)) => {
+                    output.push_str(&format!(" facing entity {}", target(entity)));
+                    if anchor == Anchor::Eyes {
+                        output.push_str(" eyes");
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+    output
+}
+
+fn tag(tag: Tag) -> String {
+    let action = match tag.action {
+        TagAction::Add(name) => format!("add {}", name),
+        TagAction::Remove(name) => format!("remove {}", name),
+        TagAction::List => String::from("list"),
+    };
+    format!("tag {} {}", target(tag.targets), action)
+}
+
+fn bossbar(bossbar: Bossbar) -> String {
+    match bossbar {
+        Bossbar::Add(id, name) => format!("bossbar add {} {}", id, name),
+        Bossbar::Remove(id) => format!("bossbar remove {}", id),
+        Bossbar::List => String::from("bossbar list"),
+        Bossbar::Get(id, value) => {
+            let value = match value {
+                BossbarGet::Max => "max",
+                BossbarGet::Players => "players",
+                BossbarGet::Value => "value",
+                BossbarGet::Visible => "visible",
+            };
+            format!("bossbar get {} {}", id, value)
+        }
+        Bossbar::Set(id, setting) => {
+            let setting = match setting {
+                BossbarSet::Color(color) => format!("color {}", color),
+                BossbarSet::Max(max) => format!("max {}", max),
+                BossbarSet::Name(name) => format!("name {}", name),
+                BossbarSet::Players(Some(targets)) => format!("players {}", target(targets)),
+                BossbarSet::Players(None) => String::from("players"),
+                BossbarSet::Style(style) => format!("style {}", style),
+                BossbarSet::Value(value) => format!("value {}", value),
+                BossbarSet::Visible(visible) => format!("visible {}", visible),
+            };
+            format!("bossbar set {} {}", id, setting)
+        }
+    }
+}
+
+fn team(team: Team) -> String {
+    match team {
+        Team::Add(a) => match a.display_name {
+            Some(display) => format!("team add {} {}", a.team, display),
+            None => format!("team add {}", a.team),
+        },
+        Team::Remove(team) => format!("team remove {}", team),
+        Team::Empty(team) => format!("team empty {}", team),
+        Team::Join(team, Some(members)) => format!("team join {} {}", team, target(members)),
+        Team::Join(team, None) => format!("team join {}", team),
+        Team::Leave(members) => format!("team leave {}", target(members)),
+        Team::List(Some(team)) => format!("team list {}", team),
+        Team::List(None) => String::from("team list"),
+        Team::Modify(m) => {
+            let option = match m.option {
+                TeamOption::DisplayName(name) => format!("displayName {}", name),
+                TeamOption::Color(Some(color)) => format!("color {}", color),
+                TeamOption::Color(None) => String::from("color reset"),
+                TeamOption::FriendlyFire(value) => format!("friendlyFire {}", value),
+                TeamOption::SeeFriendlyInvisibles(value) => {
+                    format!("seeFriendlyInvisibles {}", value)
+                }
+                TeamOption::NametagVisibility(value) => format!("nametagVisibility {}", value),
+                TeamOption::DeathMessageVisibility(value) => {
+                    format!("deathMessageVisibility {}", value)
+                }
+                TeamOption::CollisionRule(value) => format!("collisionRule {}", value),
+                TeamOption::Prefix(prefix) => format!("prefix {}", prefix),
+                TeamOption::Suffix(suffix) => format!("suffix {}", suffix),
+            };
+            format!("team modify {} {}", m.team, option)
+        }
+    }
+}
+
+fn vec3(vec3: Vec3) -> String {
+    format!(
+        "{} {} {}",
+        coordinate(vec3.x),
+        coordinate(vec3.y),
+        coordinate(vec3.z)
+    )
+}
+
+fn coordinate(coordinate: Coordinate) -> String {
+    match coordinate {
+        Coordinate::Absolute(value, true) if value.fract() == 0.0 => format!("{:.1}", value),
+        Coordinate::Absolute(value, _) => format!("{}", value),
+        Coordinate::Relative(0.0) => String::from("~"),
+        Coordinate::Relative(offset) => format!("~{}", offset),
+        Coordinate::Local(0.0) => String::from("^"),
+        Coordinate::Local(offset) => format!("^{}", offset),
+    }
+}
+
+fn block_pos(block_pos: BlockPos) -> String {
+    format!(
+        "{} {} {}",
+        coordinate(block_pos.x),
+        coordinate(block_pos.y),
+        coordinate(block_pos.z)
+    )
+}
+
+fn vec2(vec2: Vec2) -> String {
+    format!("{} {}", coordinate(vec2.x), coordinate(vec2.z))
+}
+
+fn title(title: Title) -> String {
+    let action = match title.action {
+        TitleAction::Title(c) => format!("title {}", c),
+        TitleAction::Subtitle(c) => format!("subtitle {}", c),
+        TitleAction::Actionbar(c) => format!("actionbar {}", c),
+        TitleAction::Times {
+            fade_in,
+            stay,
+            fade_out,
+        } => format!("times {} {} {}", fade_in, stay, fade_out),
+        TitleAction::Clear => String::from("clear"),
+        TitleAction::Reset => String::from("reset"),
+    };
+    format!("title {} {}", target(title.targets), action)
 }
 
 #[test]
@@ -233,7 +730,7 @@ fn scoreboard_objectives_add_with_display() {
     let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(ObjectivesAdd {
         objective: String::from("objective"),
         criteria: Criteria::Dummy,
-        display_name: Some(String::from("display name")),
+        display_name: Some(TextComponent::text("display name")),
     })));
 
     assert_eq!(
@@ -254,7 +751,7 @@ fn scoreboard_objectives_modify_displayname() {
     let command = Command::Scoreboard(Scoreboard::Objectives(Objectives::Modify(
         ObjectivesModify {
             objective: String::from("objective"),
-            modification: Modification::DisplayName(String::from("new display name")),
+            modification: Modification::DisplayName(TextComponent::text("new display name")),
         },
     )));
 
@@ -356,9 +853,7 @@ fn scoreboard_objectives_setdisplay_sidebar() {
 #[test]
 fn scoreboard_players_add() {
     let command = Command::Scoreboard(Scoreboard::Players(Players::Add(PlayersAdd {
-        targets: Target::Selector(Selector {
-            variable: SelectorVariable::A,
-        }),
+        targets: Target::Selector(Selector::new(SelectorVariable::A)),
         objective: String::from("obj"),
         score: 17,
     })));
@@ -372,9 +867,7 @@ fn scoreboard_players_add() {
 #[test]
 fn scoreboard_players_enable() {
     let command = Command::Scoreboard(Scoreboard::Players(Players::Enable(PlayersEnable {
-        targets: Target::Selector(Selector {
-            variable: SelectorVariable::E,
-        }),
+        targets: Target::Selector(Selector::new(SelectorVariable::E)),
         objective: String::from("obj"),
     })));
 
@@ -387,9 +880,7 @@ fn scoreboard_players_enable() {
 #[test]
 fn scoreboard_players_get() {
     let command = Command::Scoreboard(Scoreboard::Players(Players::Get(PlayersGet {
-        target: Target::Selector(Selector {
-            variable: SelectorVariable::P,
-        }),
+        target: Target::Selector(Selector::new(SelectorVariable::P)),
         objective: String::from("obj"),
     })));
 
@@ -402,33 +893,23 @@ fn scoreboard_players_get() {
 #[test]
 fn selector_simple() {
     assert_eq!(
-        selector(Selector {
-            variable: SelectorVariable::P
-        }),
+        selector(Selector::new(SelectorVariable::P)),
         String::from("@p")
     );
     assert_eq!(
-        selector(Selector {
-            variable: SelectorVariable::R
-        }),
+        selector(Selector::new(SelectorVariable::R)),
         String::from("@r")
     );
     assert_eq!(
-        selector(Selector {
-            variable: SelectorVariable::A
-        }),
+        selector(Selector::new(SelectorVariable::A)),
         String::from("@a")
     );
     assert_eq!(
-        selector(Selector {
-            variable: SelectorVariable::E
-        }),
+        selector(Selector::new(SelectorVariable::E)),
         String::from("@e")
     );
     assert_eq!(
-        selector(Selector {
-            variable: SelectorVariable::S
-        }),
+        selector(Selector::new(SelectorVariable::S)),
         String::from("@s")
     );
 }
@@ -453,9 +934,7 @@ fn scoreboard_players_list_no_target() {
 #[test]
 fn scoreboard_players_list_with_target() {
     let command = Command::Scoreboard(Scoreboard::Players(Players::List(PlayersList {
-        target: Some(Target::Selector(Selector {
-            variable: SelectorVariable::R,
-        })),
+        target: Some(Target::Selector(Selector::new(SelectorVariable::R))),
     })));
 
     assert_eq!(lower(command), String::from("scoreboard players list @r"));
@@ -504,9 +983,7 @@ fn scoreboard_players_operation() {
 #[test]
 fn scoreboard_players_remove() {
     let command = Command::Scoreboard(Scoreboard::Players(Players::Remove(PlayersRemove {
-        targets: Target::Selector(Selector {
-            variable: SelectorVariable::E,
-        }),
+        targets: Target::Selector(Selector::new(SelectorVariable::E)),
         objective: String::from("obj"),
         score: 19,
     })));
@@ -520,9 +997,7 @@ fn scoreboard_players_remove() {
 #[test]
 fn scoreboard_players_reset() {
     let command = Command::Scoreboard(Scoreboard::Players(Players::Reset(PlayersReset {
-        targets: Target::Selector(Selector {
-            variable: SelectorVariable::R,
-        }),
+        targets: Target::Selector(Selector::new(SelectorVariable::R)),
         objective: String::from("obj"),
     })));
 
@@ -535,9 +1010,7 @@ fn scoreboard_players_reset() {
 #[test]
 fn scoreboard_players_set() {
     let command = Command::Scoreboard(Scoreboard::Players(Players::Set(PlayersSet {
-        targets: Target::Selector(Selector {
-            variable: SelectorVariable::P,
-        }),
+        targets: Target::Selector(Selector::new(SelectorVariable::P)),
         objective: String::from("obj"),
         score: -27,
     })));
@@ -550,14 +1023,10 @@ fn scoreboard_players_set() {
 
 fn generic_player_operation(operation_type: OperationType) -> Command {
     Command::Scoreboard(Scoreboard::Players(Players::Operation(PlayersOperation {
-        targets: Target::Selector(Selector {
-            variable: SelectorVariable::A,
-        }),
+        targets: Target::Selector(Selector::new(SelectorVariable::A)),
         target_objective: String::from("targetObj"),
         operation: operation_type,
-        source: Target::Selector(Selector {
-            variable: SelectorVariable::P,
-        }),
+        source: Target::Selector(Selector::new(SelectorVariable::P)),
         source_objective: String::from("sourceObj"),
     })))
 }
@@ -748,7 +1217,7 @@ fn execute_if_score_matches_right_unbounded_range() {
 fn test_tellraw() {
     let command = Command::Tellraw(Tellraw {
         target: Target::Name("person".to_string()),
-        message: "It's a message!".to_string(),
+        message: TextComponent::text("It's a message!"),
     });
 
     assert_eq!(
@@ -756,3 +1225,397 @@ fn test_tellraw() {
         "tellraw person \"It's a message!\"".to_string()
     )
 }
+
+#[test]
+fn test_tellraw_styled() {
+    let command = Command::Tellraw(Tellraw {
+        target: Target::Selector(Selector::new(SelectorVariable::A)),
+        message: TextComponent {
+            content: text::Content::Text("hi".to_string()),
+            style: Box::new(text::Style {
+                color: Some(text::Color::Red),
+                ..text::Style::default()
+            }),
+            extra: vec![TextComponent::text("!")],
+        },
+    });
+
+    assert_eq!(
+        lower(command),
+        "tellraw @a {\"text\":\"hi\",\"color\":\"red\",\"extra\":[\"!\"]}".to_string()
+    )
+}
+
+#[test]
+fn test_messaging() {
+    assert_eq!(
+        lower(Command::Say(Say {
+            message: "hello @a".to_string()
+        })),
+        "say hello @a"
+    );
+    assert_eq!(
+        lower(Command::Tell(Tell {
+            targets: Target::Name("Alice".to_string()),
+            message: "psst".to_string()
+        })),
+        "tell Alice psst"
+    );
+    assert_eq!(
+        lower(Command::Me(Me {
+            action: "waves".to_string()
+        })),
+        "me waves"
+    );
+}
+
+#[test]
+fn test_title() {
+    let title = |action| {
+        lower(Command::Title(Title {
+            targets: Target::Selector(Selector::new(SelectorVariable::A)),
+            action,
+        }))
+    };
+    assert_eq!(
+        title(TitleAction::Subtitle(TextComponent::text("sub"))),
+        "title @a subtitle \"sub\""
+    );
+    assert_eq!(
+        title(TitleAction::Times {
+            fade_in: 10,
+            stay: 70,
+            fade_out: 20
+        }),
+        "title @a times 10 70 20"
+    );
+    assert_eq!(title(TitleAction::Reset), "title @a reset");
+}
+
+#[test]
+fn test_selector_arguments() {
+    let mut selector = Selector::new(SelectorVariable::E);
+    selector.arguments = vec![
+        SelectorArgument::Type {
+            kind: "marker".to_string(),
+            negated: false,
+        },
+        SelectorArgument::Tag {
+            tag: "spawn".to_string(),
+            negated: true,
+        },
+        SelectorArgument::Name {
+            name: "Big Bob".to_string(),
+            negated: false,
+        },
+        SelectorArgument::Limit(2),
+        SelectorArgument::Scores(vec![("obj".to_string(), Interval::RightUnbounded(1))]),
+    ];
+    assert_eq!(
+        lower(Command::Kill(Kill {
+            targets: Some(Target::Selector(selector))
+        })),
+        "kill @e[type=marker,tag=!spawn,name=\"Big Bob\",limit=2,scores={obj=1..}]"
+    );
+    assert_eq!(lower(Command::Kill(Kill { targets: None })), "kill");
+}
+
+#[test]
+fn test_summon() {
+    assert_eq!(
+        lower(Command::Summon(Summon {
+            entity: "minecraft:marker".to_string(),
+            position: Some(Vec3 {
+                x: Coordinate::Absolute(1.5, true),
+                y: Coordinate::Relative(0.0),
+                z: Coordinate::Relative(-2.0),
+            }),
+            nbt: Some(nbt::parse("{Tags:[\"a\"]}").unwrap()),
+        })),
+        "summon minecraft:marker 1.5 ~ ~-2 {Tags:[\"a\"]}"
+    );
+}
+
+#[test]
+fn test_tag() {
+    let tag = |action| {
+        lower(Command::Tag(Tag {
+            targets: Target::Selector(Selector::new(SelectorVariable::S)),
+            action,
+        }))
+    };
+    assert_eq!(tag(TagAction::Add("a".to_string())), "tag @s add a");
+    assert_eq!(tag(TagAction::Remove("a".to_string())), "tag @s remove a");
+    assert_eq!(tag(TagAction::List), "tag @s list");
+}
+
+#[test]
+fn test_coordinates() {
+    assert_eq!(
+        vec3(Vec3 {
+            x: Coordinate::Local(0.0),
+            y: Coordinate::Local(1.5),
+            z: Coordinate::Local(-2.0),
+        }),
+        "^ ^1.5 ^-2"
+    );
+    assert_eq!(
+        block_pos(BlockPos {
+            x: Coordinate::Absolute(1.0, false),
+            y: Coordinate::Relative(-1.0),
+            z: Coordinate::Absolute(-3.0, false),
+        }),
+        "1 ~-1 -3"
+    );
+    assert_eq!(
+        vec2(Vec2 {
+            x: Coordinate::Relative(0.0),
+            z: Coordinate::Absolute(4.0, false),
+        }),
+        "~ 4"
+    );
+}
+
+#[test]
+fn test_teleport() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "tp Bob",
+        "tp ~ ~1 ~",
+        "tp @a Bob",
+        "tp Alice 1 2 3 ~90 -45",
+        "tp Alice ^ ^ ^1 facing 4 5 6",
+        "tp Alice 1 2 3 facing entity Bob",
+        "tp Alice 1 2 3 facing entity Bob eyes",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+    assert_eq!(lowered("teleport Bob"), "tp Bob");
+}
+
+#[test]
+fn test_position_selector_arguments() {
+    let line = "kill @e[x=1.5,y=-2,z=0,distance=..5.5,distance=1..,distance=2,dx=3,sort=nearest]";
+    assert_eq!(lower(parse::parse_line(line).unwrap()), line);
+}
+
+#[test]
+fn test_blocks() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "setblock 1 2 3 stone",
+        "setblock ~ ~-1 ~ minecraft:oak_stairs[facing=east,half=top] keep",
+        "setblock 0 0 0 chest{Lock:\"key\"} destroy",
+        "fill 0 0 0 4 4 4 glass hollow",
+        "fill 0 0 0 4 4 4 air replace stone",
+        "clone 0 0 0 1 1 1 5 0 0",
+        "clone 0 0 0 1 1 1 1 0 0 masked force",
+        "clone 0 0 0 1 1 1 5 0 0 filtered lever[powered=true] move",
+        "execute if block ~ ~ ~ stone run say hi",
+        "execute unless blocks 0 0 0 1 1 1 5 0 0 masked run say hi",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+    assert_eq!(
+        lowered("setblock 1 2 3 stone replace"),
+        "setblock 1 2 3 stone"
+    );
+    assert_eq!(
+        lowered("clone 0 0 0 1 1 1 5 0 0 replace normal"),
+        "clone 0 0 0 1 1 1 5 0 0"
+    );
+}
+
+#[test]
+fn test_place_template() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "place template ns:house",
+        "place template house 1 2 3",
+        "place template house ~ ~ ~ clockwise_90",
+        "place template house ~ ~ ~ none left_right",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_criteria() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "scoreboard objectives add hp health",
+        "scoreboard objectives add tk teamkill.dark_red",
+        "scoreboard objectives add died killedByTeam.gold",
+        "scoreboard objectives add jumps minecraft.custom:minecraft.jump",
+        "trigger menu",
+        "trigger menu add -3",
+        "trigger menu set 2",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+    assert_eq!(
+        lowered("scoreboard objectives add clicks used:carrot_on_a_stick"),
+        "scoreboard objectives add clicks minecraft.used:minecraft.carrot_on_a_stick"
+    );
+}
+
+#[test]
+fn test_teams() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "team add red",
+        "team add blue {\"text\":\"Blue Team\",\"color\":\"blue\"}",
+        "team remove red",
+        "team empty red",
+        "team join red",
+        "team join red @a[team=]",
+        "team leave @e[team=!red]",
+        "team list",
+        "team list red",
+        "team modify red color dark_red",
+        "team modify red color reset",
+        "team modify red friendlyFire false",
+        "team modify red seeFriendlyInvisibles true",
+        "team modify red nametagVisibility hideForOtherTeams",
+        "team modify red deathMessageVisibility never",
+        "team modify red collisionRule pushOwnTeam",
+        "team modify red prefix \"[R] \"",
+        "scoreboard objectives setdisplay sidebar.team.dark_red kills",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_random() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "random value 1..6",
+        "random roll -5..5 ns:dice",
+        "random reset *",
+        "random reset dice 7 true false",
+        "say @r[sort=random]",
+        "kill @e[sort=random,limit=2]",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_predicates() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "execute if predicate ns:night run say hi",
+        "execute unless predicate night run kill @e[predicate=!ns:rich,predicate=lucky]",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_advancement() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "advancement grant @a everything",
+        "advancement revoke @s only ns:root",
+        "advancement grant Alice only ns:root joined",
+        "advancement grant Alice from ns:root",
+        "advancement revoke @p until ns:end",
+        "advancement revoke Alice through ns:middle",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_time_and_weather() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "time set 13000",
+        "time add 20",
+        "time query gametime",
+        "weather clear",
+        "weather rain 6000",
+        "difficulty",
+        "difficulty peaceful",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+    assert_eq!(lowered("time set midnight"), "time set 18000");
+    assert_eq!(lowered("weather thunder 1d"), "weather thunder 24000");
+}
+
+#[test]
+fn test_gamerules() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "gamerule doDaylightCycle",
+        "gamerule doDaylightCycle false",
+        "gamerule maxCommandChainLength 10",
+        "gamerule spawnRadius -1",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_number_formats() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "scoreboard objectives modify kills displayautoupdate true",
+        "scoreboard objectives modify kills numberformat",
+        "scoreboard objectives modify kills numberformat blank",
+        "scoreboard objectives modify kills numberformat fixed \"-\"",
+        "scoreboard objectives modify kills numberformat styled {\"color\":\"gold\",\"bold\":true}",
+        "scoreboard players display name @a kills {\"text\":\"Hero\",\"color\":\"aqua\"}",
+        "scoreboard players display name Alice kills",
+        "scoreboard players display numberformat @s kills styled {\"italic\":true}",
+        "scoreboard players display numberformat #total kills",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_bossbars() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "bossbar add ns:boss \"Boss\"",
+        "bossbar remove ns:boss",
+        "bossbar list",
+        "bossbar get ns:boss players",
+        "bossbar set ns:boss color pink",
+        "bossbar set ns:boss max 20",
+        "bossbar set ns:boss name \"Wither\"",
+        "bossbar set ns:boss players @a[tag=fighting]",
+        "bossbar set ns:boss players",
+        "bossbar set ns:boss style notched_10",
+        "bossbar set ns:boss value 7",
+        "bossbar set ns:boss visible false",
+        "execute store result bossbar ns:boss value run scoreboard players get #hp obj",
+        "execute store success score @s obj run bossbar get ns:boss visible",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+}
+
+#[test]
+fn test_items() {
+    let lowered = |line: &str| lower(parse::parse_line(line).unwrap());
+    for line in &[
+        "give @a diamond",
+        "give Alice diamond_sword[minecraft:custom_name='\"Ruby\"'] 2",
+        "clear",
+        "clear @s *[custom_data~{shop:1b}] 0",
+        "item replace entity @s weapon.offhand with shield",
+        "item replace entity @s hotbar.0 from entity Bob armor.head ns:shiny",
+        "item modify entity @a container.5 ns:halve",
+        "execute if items entity @s hotbar.* minecraft:emerald run say rich",
+    ] {
+        assert_eq!(&lowered(line), line);
+    }
+    assert_eq!(
+        lowered("give @s diamond{shop:1b}"),
+        "give @s diamond[minecraft:custom_data={shop:1b}]"
+    );
+}
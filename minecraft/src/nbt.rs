@@ -0,0 +1,784 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Nbt {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    ByteArray(Vec<i8>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+    List(Vec<Nbt>),
+    Compound(BTreeMap<String, Nbt>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    UnexpectedChar(char),
+    UnexpectedEnd,
+    Number(String),
+    MixedList,
+    TrailingCharacters,
+    /// A binary tag of an id that doesn't exist.
+    TagType(u8),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PathNode {
+    Key(String),
+    Index(i32),
+    AllElements,
+    MatchElement(Nbt),
+    MatchObject(Nbt),
+}
+
+/// A parsed NBT path such as `Inventory[{Slot:0b}].tag.display.Name`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Path {
+    pub nodes: Vec<PathNode>,
+}
+
+impl Nbt {
+    pub fn compound() -> Nbt {
+        Nbt::Compound(BTreeMap::new())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Nbt> {
+        match self {
+            Nbt::Compound(tags) => tags.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Nbt::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The numeric value of the tag, as `data get` would report it before scaling.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Nbt::Byte(n) => Some(f64::from(*n)),
+            Nbt::Short(n) => Some(f64::from(*n)),
+            Nbt::Int(n) => Some(f64::from(*n)),
+            Nbt::Long(n) => Some(*n as f64),
+            Nbt::Float(n) => Some(f64::from(*n)),
+            Nbt::Double(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// The tag as text, the way `getAsString` reads it: strings unquoted, everything else as SNBT.
+    pub fn as_text(&self) -> String {
+        match self {
+            Nbt::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    /// Whether every tag in `pattern` is present in `self` with an equal value. Compounds are
+    /// compared recursively and lists match when each pattern element matches some element.
+    pub fn matches(&self, pattern: &Nbt) -> bool {
+        match (self, pattern) {
+            (Nbt::Compound(tags), Nbt::Compound(expected)) => expected
+                .iter()
+                .all(|(k, v)| tags.get(k).map(|actual| actual.matches(v)).unwrap_or(false)),
+            (Nbt::List(elements), Nbt::List(expected)) => {
+                if expected.is_empty() {
+                    elements.is_empty()
+                } else {
+                    expected
+                        .iter()
+                        .all(|e| elements.iter().any(|actual| actual.matches(e)))
+                }
+            }
+            (actual, expected) => actual == expected,
+        }
+    }
+
+    /// Recursively merges `other` into this compound, as `data merge` does.
+    pub fn merge(&mut self, other: &Nbt) {
+        if let (Nbt::Compound(tags), Nbt::Compound(incoming)) = (&mut *self, other) {
+            for (key, value) in incoming {
+                match (tags.get_mut(key), value) {
+                    (Some(existing @ Nbt::Compound(_)), Nbt::Compound(_)) => existing.merge(value),
+                    _ => {
+                        tags.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Display for Nbt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Nbt::Byte(n) => write!(f, "{}b", n),
+            Nbt::Short(n) => write!(f, "{}s", n),
+            Nbt::Int(n) => write!(f, "{}", n),
+            Nbt::Long(n) => write!(f, "{}L", n),
+            Nbt::Float(n) => write!(f, "{:?}f", n),
+            Nbt::Double(n) => write!(f, "{:?}d", n),
+            Nbt::String(s) => write!(f, "{}", quote(s)),
+            Nbt::ByteArray(values) => write_array(f, "B", values.iter().map(|v| format!("{}b", v))),
+            Nbt::IntArray(values) => write_array(f, "I", values.iter().map(i32::to_string)),
+            Nbt::LongArray(values) => write_array(f, "L", values.iter().map(|v| format!("{}L", v))),
+            Nbt::List(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Nbt::Compound(tags) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in tags.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    if key.chars().all(is_unquoted_char) && !key.is_empty() {
+                        write!(f, "{}:{}", key, value)?;
+                    } else {
+                        write!(f, "{}:{}", quote(key), value)?;
+                    }
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_array<I: Iterator<Item = String>>(
+    f: &mut Formatter<'_>,
+    prefix: &str,
+    values: I,
+) -> fmt::Result {
+    write!(f, "[{};{}]", prefix, values.collect::<Vec<_>>().join(","))
+}
+
+fn quote(text: &str) -> String {
+    let delimiter = if text.contains('"') && !text.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+    let mut output = String::new();
+    output.push(delimiter);
+    for c in text.chars() {
+        if c == delimiter || c == '\\' {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output.push(delimiter);
+    output
+}
+
+fn is_unquoted_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '+'
+}
+
+type Chars<'a> = Peekable<std::str::Chars<'a>>;
+
+/// Parses stringified NBT, as written in commands (`{Tags:["a"],Count:1b}`).
+pub fn parse(text: &str) -> Result<Nbt, Error> {
+    let mut chars = text.chars().peekable();
+    let value = value(&mut chars)?;
+    skip_whitespace(&mut chars);
+    match chars.peek() {
+        Some(_) => Err(Error::TrailingCharacters),
+        None => Ok(value),
+    }
+}
+
+/// Parses one SNBT value from the front of `chars`, leaving anything after it unread.
+pub(crate) fn parse_prefix(chars: &mut Chars) -> Result<Nbt, Error> {
+    value(chars)
+}
+
+fn value(chars: &mut Chars) -> Result<Nbt, Error> {
+    skip_whitespace(chars);
+    match chars.peek().copied() {
+        Some('{') => compound(chars),
+        Some('[') => list(chars),
+        Some('"') | Some('\'') => quoted(chars).map(Nbt::String),
+        Some(_) => Ok(primitive(&unquoted(chars)?)),
+        None => Err(Error::UnexpectedEnd),
+    }
+}
+
+fn compound(chars: &mut Chars) -> Result<Nbt, Error> {
+    expect(chars, '{')?;
+    let mut tags = BTreeMap::new();
+    skip_whitespace(chars);
+    if chars.peek() == Some(&'}') {
+        chars.next();
+        return Ok(Nbt::Compound(tags));
+    }
+    loop {
+        skip_whitespace(chars);
+        let key = key(chars)?;
+        skip_whitespace(chars);
+        expect(chars, ':')?;
+        let value = value(chars)?;
+        tags.insert(key, value);
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(Nbt::Compound(tags)),
+            Some(c) => return Err(Error::UnexpectedChar(c)),
+            None => return Err(Error::UnexpectedEnd),
+        }
+    }
+}
+
+fn list(chars: &mut Chars) -> Result<Nbt, Error> {
+    expect(chars, '[')?;
+    let mut lookahead = chars.clone();
+    if let (Some(kind), Some(';')) = (lookahead.next(), lookahead.next()) {
+        if kind == 'B' || kind == 'I' || kind == 'L' {
+            chars.next();
+            chars.next();
+            return array(chars, kind);
+        }
+    }
+    let mut elements: Vec<Nbt> = Vec::new();
+    skip_whitespace(chars);
+    if chars.peek() == Some(&']') {
+        chars.next();
+        return Ok(Nbt::List(elements));
+    }
+    loop {
+        let element = value(chars)?;
+        if let Some(first) = elements.first() {
+            if std::mem::discriminant(first) != std::mem::discriminant(&element) {
+                return Err(Error::MixedList);
+            }
+        }
+        elements.push(element);
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some(']') => return Ok(Nbt::List(elements)),
+            Some(c) => return Err(Error::UnexpectedChar(c)),
+            None => return Err(Error::UnexpectedEnd),
+        }
+    }
+}
+
+fn array(chars: &mut Chars, kind: char) -> Result<Nbt, Error> {
+    let mut values = Vec::new();
+    loop {
+        skip_whitespace(chars);
+        if chars.peek() == Some(&']') {
+            chars.next();
+            break;
+        }
+        values.push(primitive(&unquoted(chars)?));
+        skip_whitespace(chars);
+        match chars.peek() {
+            Some(',') => {
+                chars.next();
+            }
+            Some(']') => {}
+            Some(c) => return Err(Error::UnexpectedChar(*c)),
+            None => return Err(Error::UnexpectedEnd),
+        }
+    }
+    let as_i64 = |tag: &Nbt| match tag {
+        Nbt::Byte(n) => Ok(i64::from(*n)),
+        Nbt::Short(n) => Ok(i64::from(*n)),
+        Nbt::Int(n) => Ok(i64::from(*n)),
+        Nbt::Long(n) => Ok(*n),
+        other => Err(Error::Number(other.to_string())),
+    };
+    let values = values.iter().map(as_i64).collect::<Result<Vec<_>, _>>()?;
+    Ok(match kind {
+        'B' => Nbt::ByteArray(values.iter().map(|v| *v as i8).collect()),
+        'I' => Nbt::IntArray(values.iter().map(|v| *v as i32).collect()),
+        _ => Nbt::LongArray(values),
+    })
+}
+
+fn key(chars: &mut Chars) -> Result<String, Error> {
+    match chars.peek() {
+        Some('"') | Some('\'') => quoted(chars),
+        _ => unquoted(chars),
+    }
+}
+
+fn quoted(chars: &mut Chars) -> Result<String, Error> {
+    let delimiter = chars.next().ok_or(Error::UnexpectedEnd)?;
+    let mut content = String::new();
+    loop {
+        match chars.next() {
+            Some('\\') => content.push(chars.next().ok_or(Error::UnexpectedEnd)?),
+            Some(c) if c == delimiter => return Ok(content),
+            Some(c) => content.push(c),
+            None => return Err(Error::UnexpectedEnd),
+        }
+    }
+}
+
+fn unquoted(chars: &mut Chars) -> Result<String, Error> {
+    let mut lexeme = String::new();
+    while let Some(c) = chars.peek().copied().filter(|c| is_unquoted_char(*c)) {
+        lexeme.push(c);
+        chars.next();
+    }
+    match (lexeme.is_empty(), chars.peek()) {
+        (true, Some(c)) => Err(Error::UnexpectedChar(*c)),
+        (true, None) => Err(Error::UnexpectedEnd),
+        (false, _) => Ok(lexeme),
+    }
+}
+
+/// Interprets an unquoted SNBT token: a suffixed number, a boolean, or failing that a string.
+fn primitive(token: &str) -> Nbt {
+    let lower = token.to_ascii_lowercase();
+    if lower == "true" {
+        return Nbt::Byte(1);
+    }
+    if lower == "false" {
+        return Nbt::Byte(0);
+    }
+    let (digits, suffix) = match lower.chars().last() {
+        Some(c) if "bslfd".contains(c) && token.len() > 1 => (&token[..token.len() - 1], Some(c)),
+        _ => (token, None),
+    };
+    let parsed = match suffix {
+        Some('b') => digits.parse().ok().map(Nbt::Byte),
+        Some('s') => digits.parse().ok().map(Nbt::Short),
+        Some('l') => digits.parse().ok().map(Nbt::Long),
+        Some('f') => digits.parse().ok().map(Nbt::Float),
+        Some('d') => digits.parse().ok().map(Nbt::Double),
+        _ if digits.contains('.') || digits.contains('e') || digits.contains('E') => digits
+            .parse()
+            .ok()
+            .filter(|_| looks_numeric(digits))
+            .map(Nbt::Double),
+        _ => digits.parse().ok().map(Nbt::Int),
+    };
+    parsed.unwrap_or_else(|| Nbt::String(String::from(token)))
+}
+
+fn looks_numeric(text: &str) -> bool {
+    text.chars()
+        .all(|c| c.is_ascii_digit() || c == '.' || c == '-' || c == '+' || c == 'e' || c == 'E')
+}
+
+fn expect(chars: &mut Chars, expected: char) -> Result<(), Error> {
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        Some(c) => Err(Error::UnexpectedChar(c)),
+        None => Err(Error::UnexpectedEnd),
+    }
+}
+
+fn skip_whitespace(chars: &mut Chars) {
+    while chars.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+        chars.next();
+    }
+}
+
+/// Reads binary NBT, as stored in structure files once decompressed: a single named compound.
+pub fn read(bytes: &[u8]) -> Result<Nbt, Error> {
+    let mut reader = Reader { bytes, position: 0 };
+    match reader.byte()? {
+        10 => {
+            reader.string()?;
+            reader.payload(10)
+        }
+        id => Err(Error::TagType(id)),
+    }
+}
+
+/// Writes `tag` as binary NBT with an empty root name, the inverse of `read`.
+pub fn write(tag: &Nbt) -> Vec<u8> {
+    let mut bytes = vec![tag_id(tag), 0, 0];
+    write_payload(tag, &mut bytes);
+    bytes
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], Error> {
+        let end = self.position + count;
+        let taken = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(Error::UnexpectedEnd)?;
+        self.position = end;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn length(&mut self) -> Result<usize, Error> {
+        Ok(i32::from_be_bytes(self.array()?).max(0) as usize)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let length = u16::from_be_bytes(self.array()?) as usize;
+        Ok(String::from_utf8_lossy(self.take(length)?).into_owned())
+    }
+
+    fn payload(&mut self, id: u8) -> Result<Nbt, Error> {
+        Ok(match id {
+            1 => Nbt::Byte(self.byte()? as i8),
+            2 => Nbt::Short(i16::from_be_bytes(self.array()?)),
+            3 => Nbt::Int(i32::from_be_bytes(self.array()?)),
+            4 => Nbt::Long(i64::from_be_bytes(self.array()?)),
+            5 => Nbt::Float(f32::from_be_bytes(self.array()?)),
+            6 => Nbt::Double(f64::from_be_bytes(self.array()?)),
+            7 => {
+                let length = self.length()?;
+                Nbt::ByteArray(self.take(length)?.iter().map(|b| *b as i8).collect())
+            }
+            8 => Nbt::String(self.string()?),
+            9 => {
+                let element = self.byte()?;
+                let length = self.length()?;
+                let mut elements = Vec::new();
+                for _ in 0..length {
+                    elements.push(self.payload(element)?);
+                }
+                Nbt::List(elements)
+            }
+            10 => {
+                let mut tags = BTreeMap::new();
+                loop {
+                    match self.byte()? {
+                        0 => break,
+                        id => {
+                            let name = self.string()?;
+                            tags.insert(name, self.payload(id)?);
+                        }
+                    }
+                }
+                Nbt::Compound(tags)
+            }
+            11 => {
+                let length = self.length()?;
+                let mut values = Vec::new();
+                for _ in 0..length {
+                    values.push(i32::from_be_bytes(self.array()?));
+                }
+                Nbt::IntArray(values)
+            }
+            12 => {
+                let length = self.length()?;
+                let mut values = Vec::new();
+                for _ in 0..length {
+                    values.push(i64::from_be_bytes(self.array()?));
+                }
+                Nbt::LongArray(values)
+            }
+            id => return Err(Error::TagType(id)),
+        })
+    }
+}
+
+fn tag_id(tag: &Nbt) -> u8 {
+    match tag {
+        Nbt::Byte(_) => 1,
+        Nbt::Short(_) => 2,
+        Nbt::Int(_) => 3,
+        Nbt::Long(_) => 4,
+        Nbt::Float(_) => 5,
+        Nbt::Double(_) => 6,
+        Nbt::ByteArray(_) => 7,
+        Nbt::String(_) => 8,
+        Nbt::List(_) => 9,
+        Nbt::Compound(_) => 10,
+        Nbt::IntArray(_) => 11,
+        Nbt::LongArray(_) => 12,
+    }
+}
+
+fn write_string(text: &str, bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&(text.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(text.as_bytes());
+}
+
+fn write_payload(tag: &Nbt, bytes: &mut Vec<u8>) {
+    match tag {
+        Nbt::Byte(n) => bytes.push(*n as u8),
+        Nbt::Short(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Nbt::Int(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Nbt::Long(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Nbt::Float(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Nbt::Double(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Nbt::ByteArray(values) => {
+            bytes.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            bytes.extend(values.iter().map(|b| *b as u8));
+        }
+        Nbt::String(text) => write_string(text, bytes),
+        Nbt::List(elements) => {
+            bytes.push(elements.first().map(tag_id).unwrap_or(0));
+            bytes.extend_from_slice(&(elements.len() as i32).to_be_bytes());
+            for element in elements {
+                write_payload(element, bytes);
+            }
+        }
+        Nbt::Compound(tags) => {
+            for (name, tag) in tags {
+                bytes.push(tag_id(tag));
+                write_string(name, bytes);
+                write_payload(tag, bytes);
+            }
+            bytes.push(0);
+        }
+        Nbt::IntArray(values) => {
+            bytes.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for value in values {
+                bytes.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        Nbt::LongArray(values) => {
+            bytes.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for value in values {
+                bytes.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+    }
+}
+
+impl Path {
+    pub fn parse(text: &str) -> Result<Path, Error> {
+        let mut chars = text.chars().peekable();
+        let mut nodes = Vec::new();
+        let mut first = true;
+        loop {
+            match chars.peek().copied() {
+                None => break,
+                Some('{') if first => nodes.push(PathNode::MatchObject(compound(&mut chars)?)),
+                Some('[') => {
+                    chars.next();
+                    skip_whitespace(&mut chars);
+                    match chars.peek().copied() {
+                        Some(']') => {
+                            chars.next();
+                            nodes.push(PathNode::AllElements);
+                        }
+                        Some('{') => {
+                            let filter = compound(&mut chars)?;
+                            expect(&mut chars, ']')?;
+                            nodes.push(PathNode::MatchElement(filter));
+                        }
+                        _ => {
+                            let index = unquoted(&mut chars)?;
+                            expect(&mut chars, ']')?;
+                            nodes.push(PathNode::Index(
+                                index.parse().map_err(|_| Error::Number(index))?,
+                            ));
+                        }
+                    }
+                }
+                Some('.') if !first => {
+                    chars.next();
+                    nodes.push(PathNode::Key(path_key(&mut chars)?));
+                }
+                Some(_) if first => nodes.push(PathNode::Key(path_key(&mut chars)?)),
+                Some(c) => return Err(Error::UnexpectedChar(c)),
+            }
+            first = false;
+            if chars.peek() == Some(&'{') {
+                if let Some(PathNode::Key(_)) = nodes.last() {
+                    nodes.push(PathNode::MatchObject(compound(&mut chars)?));
+                }
+            }
+        }
+        Ok(Path { nodes })
+    }
+
+    /// Every tag the path selects within `root`.
+    pub fn get<'a>(&self, root: &'a Nbt) -> Vec<&'a Nbt> {
+        let mut current = vec![root];
+        for node in &self.nodes {
+            let mut next = Vec::new();
+            for tag in current {
+                match (node, tag) {
+                    (PathNode::Key(key), Nbt::Compound(tags)) => next.extend(tags.get(key)),
+                    (PathNode::MatchObject(filter), tag) if tag.matches(filter) => next.push(tag),
+                    (PathNode::Index(index), Nbt::List(elements)) => {
+                        next.extend(element(elements, *index))
+                    }
+                    (PathNode::AllElements, Nbt::List(elements)) => next.extend(elements.iter()),
+                    (PathNode::MatchElement(filter), Nbt::List(elements)) => {
+                        next.extend(elements.iter().filter(|e| e.matches(filter)))
+                    }
+                    _ => {}
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+fn element(elements: &[Nbt], index: i32) -> Option<&Nbt> {
+    let index = if index < 0 {
+        elements.len() as i32 + index
+    } else {
+        index
+    };
+    if index < 0 {
+        None
+    } else {
+        elements.get(index as usize)
+    }
+}
+
+fn path_key(chars: &mut Chars) -> Result<String, Error> {
+    match chars.peek() {
+        Some('"') | Some('\'') => quoted(chars),
+        _ => {
+            let mut lexeme = String::new();
+            while let Some(c) = chars
+                .peek()
+                .copied()
+                .filter(|c| !c.is_whitespace() && !"[]{}.\"'".contains(*c))
+            {
+                lexeme.push(c);
+                chars.next();
+            }
+            if lexeme.is_empty() {
+                Err(chars
+                    .peek()
+                    .map(|c| Error::UnexpectedChar(*c))
+                    .unwrap_or(Error::UnexpectedEnd))
+            } else {
+                Ok(lexeme)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compound(tags: Vec<(&str, Nbt)>) -> Nbt {
+        Nbt::Compound(tags.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(parse("1b"), Ok(Nbt::Byte(1)));
+        assert_eq!(parse("-3s"), Ok(Nbt::Short(-3)));
+        assert_eq!(parse("42"), Ok(Nbt::Int(42)));
+        assert_eq!(parse("9L"), Ok(Nbt::Long(9)));
+        assert_eq!(parse("1.5f"), Ok(Nbt::Float(1.5)));
+        assert_eq!(parse("2.5"), Ok(Nbt::Double(2.5)));
+        assert_eq!(parse("true"), Ok(Nbt::Byte(1)));
+        assert_eq!(parse("hello"), Ok(Nbt::String("hello".to_string())));
+        assert_eq!(parse("'it\\'s'"), Ok(Nbt::String("it's".to_string())));
+    }
+
+    #[test]
+    fn collections() {
+        assert_eq!(
+            parse("{Tags:[\"a\",\"b\"],Count:1b,\"odd key\":{}}"),
+            Ok(compound(vec![
+                (
+                    "Tags",
+                    Nbt::List(vec![
+                        Nbt::String("a".to_string()),
+                        Nbt::String("b".to_string())
+                    ])
+                ),
+                ("Count", Nbt::Byte(1)),
+                ("odd key", Nbt::compound()),
+            ]))
+        );
+        assert_eq!(parse("[I;1,2,3]"), Ok(Nbt::IntArray(vec![1, 2, 3])));
+        assert_eq!(parse("[B;1b,2b]"), Ok(Nbt::ByteArray(vec![1, 2])));
+        assert_eq!(parse("[1,2b]"), Err(Error::MixedList));
+    }
+
+    #[test]
+    fn display() {
+        let tag = parse("{b:[1.5d],a:\"x\",'c d':[L;1L]}").unwrap();
+        assert_eq!(tag.to_string(), "{a:\"x\",b:[1.5d],\"c d\":[L;1L]}");
+        assert_eq!(
+            Nbt::String("say \"hi\"".to_string()).to_string(),
+            "'say \"hi\"'"
+        );
+    }
+
+    #[test]
+    fn paths() {
+        let root = parse("{Items:[{Slot:0b,id:\"stone\"},{Slot:1b,id:\"dirt\"}],a:{b:5}}").unwrap();
+        let get = |path: &str| -> Vec<String> {
+            Path::parse(path)
+                .unwrap()
+                .get(&root)
+                .iter()
+                .map(|t| t.to_string())
+                .collect()
+        };
+        assert_eq!(get("a.b"), vec!["5"]);
+        assert_eq!(get("Items[1].id"), vec!["\"dirt\""]);
+        assert_eq!(get("Items[-1].Slot"), vec!["1b"]);
+        assert_eq!(get("Items[].id"), vec!["\"stone\"", "\"dirt\""]);
+        assert_eq!(get("Items[{Slot:0b}].id"), vec!["\"stone\""]);
+        assert_eq!(get("a{b:5}.b"), vec!["5"]);
+        assert_eq!(get("a{b:6}.b"), Vec::<String>::new());
+        assert_eq!(get("missing"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn matching() {
+        let tag = parse("{Tags:[\"a\",\"b\"],Count:1b}").unwrap();
+        assert!(tag.matches(&parse("{Tags:[\"b\"]}").unwrap()));
+        assert!(!tag.matches(&parse("{Count:2b}").unwrap()));
+        assert!(tag.matches(&Nbt::compound()));
+    }
+
+    #[test]
+    fn merging() {
+        let mut tag = parse("{a:{x:1},b:2}").unwrap();
+        tag.merge(&parse("{a:{y:3},b:4}").unwrap());
+        assert_eq!(tag, parse("{a:{x:1,y:3},b:4}").unwrap());
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let tag = parse(
+            "{size:[2,1,1],name:\"t\",data:[B;1b,-2b],longs:[L;5L],ints:[I;-1],empty:[],\
+             nested:{f:1.5f,d:-2.0d,s:3s,l:4L}}",
+        )
+        .unwrap();
+        let bytes = write(&tag);
+        assert_eq!(&bytes[..3], &[10, 0, 0]);
+        assert_eq!(read(&bytes), Ok(tag));
+        assert_eq!(read(&bytes[..bytes.len() - 1]), Err(Error::UnexpectedEnd));
+        assert_eq!(read(&[3, 0, 0]), Err(Error::TagType(3)));
+    }
+}
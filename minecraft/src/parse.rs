@@ -1,3 +1,4 @@
+use crate::item::{ComponentTest, ItemPredicate, ItemStack};
 use crate::*;
 use std::iter::Peekable;
 
@@ -7,6 +8,10 @@ pub enum Error {
     Command,
     Identifier,
     Target,
+    Coordinate,
+    Integer,
+    Text(text::Error),
+    Nbt(nbt::Error),
 }
 
 #[derive(Debug, PartialEq)]
@@ -22,7 +27,7 @@ struct Input<'a> {
 }
 
 impl<'a> Input<'a> {
-    fn new(text: &'a str) -> Input {
+    fn new(text: &'a str) -> Input<'a> {
         Input {
             current: 0,
             chars: text.chars().peekable(),
@@ -48,6 +53,15 @@ pub fn parse_line(line: &str) -> Result<Command, Error> {
     command(&mut Input::new(line))
 }
 
+pub fn parse_target(text: &str) -> Result<Target, Error> {
+    let mut input = Input::new(text);
+    let target = target(&mut input)?;
+    match input.peek() {
+        Some(_) => Err(Error::Target),
+        None => Ok(target),
+    }
+}
+
 fn command(input: &mut Input) -> Result<Command, Error> {
     match identifier(input).as_ref().map(String::as_str) {
         Ok("scoreboard") => space(input).and(scoreboard(input)).map(Command::Scoreboard),
@@ -56,7 +70,42 @@ fn command(input: &mut Input) -> Result<Command, Error> {
             .map(Command::Function),
         Ok("execute") => space(input).and(execute(input)).map(Command::Execute),
         Ok("tellraw") => space(input).and(tellraw(input)).map(Command::Tellraw),
-        Ok(other) => Err(Error::Command),
+        Ok("say") => space(input)
+            .and(message(input))
+            .map(|message| Command::Say(Say { message })),
+        Ok("tell") | Ok("msg") | Ok("w") => space(input).and(tell(input)).map(Command::Tell),
+        Ok("me") => space(input)
+            .and(message(input))
+            .map(|action| Command::Me(Me { action })),
+        Ok("title") => space(input).and(title(input)).map(Command::Title),
+        Ok("summon") => space(input).and(summon(input)).map(Command::Summon),
+        Ok("kill") => end_or(input, |input| space(input).and(target(input)))
+            .map(|targets| Command::Kill(Kill { targets })),
+        Ok("tag") => space(input).and(tag(input)).map(Command::Tag),
+        Ok("tp") | Ok("teleport") => space(input).and(teleport(input)).map(Command::Teleport),
+        Ok("setblock") => space(input).and(setblock(input)).map(Command::SetBlock),
+        Ok("fill") => space(input).and(fill(input)).map(Command::Fill),
+        Ok("clone") => space(input).and(clone(input)).map(Command::Clone),
+        Ok("place") => space(input).and(place(input)).map(Command::Place),
+        Ok("give") => space(input).and(give(input)).map(Command::Give),
+        Ok("clear") => clear(input).map(Command::Clear),
+        Ok("item") => space(input).and(item(input)).map(Command::Item),
+        Ok("trigger") => space(input).and(trigger(input)).map(Command::Trigger),
+        Ok("team") => space(input).and(team(input)).map(Command::Team),
+        Ok("bossbar") => space(input).and(bossbar(input)).map(Command::Bossbar),
+        Ok("gamerule") => space(input).and(gamerule(input)).map(Command::Gamerule),
+        Ok("time") => space(input).and(time(input)).map(Command::Time),
+        Ok("random") => space(input).and(random(input)).map(Command::Random),
+        Ok("advancement") => space(input)
+            .and(advancement(input))
+            .map(Command::Advancement),
+        Ok("weather") => space(input).and(weather(input)).map(Command::Weather),
+        Ok("difficulty") => end_or(input, |input| {
+            let name = space(input).and(identifier(input))?;
+            Difficulty::from_name(&name).ok_or(Error::Identifier)
+        })
+        .map(Command::Difficulty),
+        Ok(_) => Err(Error::Command),
         _ => todo!(),
     }
 }
@@ -91,7 +140,7 @@ fn objectives(input: &mut Input) -> Result<Objectives, Error> {
 fn objectives_add(input: &mut Input) -> Result<ObjectivesAdd, Error> {
     let objective = identifier(input)?;
     let criteria = space(input).and(criteria(input))?;
-    let display_name = end_or(input, |input| space(input).and(string(input)))?;
+    let display_name = end_or(input, |input| space(input).and(text_component(input)))?;
 
     Ok(ObjectivesAdd {
         objective,
@@ -101,10 +150,7 @@ fn objectives_add(input: &mut Input) -> Result<ObjectivesAdd, Error> {
 }
 
 fn criteria(input: &mut Input) -> Result<Criteria, Error> {
-    match identifier(input).as_ref().map(String::as_str) {
-        Ok("dummy") => Ok(Criteria::Dummy),
-        _ => todo!(),
-    }
+    Criteria::from_name(&name(input)?).ok_or(Error::Identifier)
 }
 
 fn objectives_modify(input: &mut Input) -> Result<ObjectivesModify, Error> {
@@ -120,15 +166,39 @@ fn objectives_modify(input: &mut Input) -> Result<ObjectivesModify, Error> {
 fn modification(input: &mut Input) -> Result<Modification, Error> {
     match identifier(input).as_ref().map(String::as_str) {
         Ok("displayname") => space(input)
-            .and(string(input))
+            .and(text_component(input))
             .map(Modification::DisplayName),
         Ok("rendertype") => space(input)
             .and(rendertype(input))
             .map(Modification::RenderType),
+        Ok("displayautoupdate") => space(input)
+            .and(boolean(input))
+            .map(Modification::DisplayAutoUpdate),
+        Ok("numberformat") => number_format(input).map(Modification::NumberFormat),
         _ => todo!(),
     }
 }
 
+fn number_format(input: &mut Input) -> Result<Option<NumberFormat>, Error> {
+    end_or(input, |input| {
+        space(input)?;
+        match identifier(input)?.as_str() {
+            "blank" => Ok(NumberFormat::Blank),
+            "fixed" => space(input)
+                .and(text_component(input))
+                .map(NumberFormat::Fixed),
+            "styled" => {
+                space(input)?;
+                let json = get_while(input, |c| c.is_some())?;
+                text::parse_style(&json)
+                    .map(NumberFormat::Styled)
+                    .map_err(Error::Text)
+            }
+            _ => Err(Error::Identifier),
+        }
+    })
+}
+
 fn rendertype(input: &mut Input) -> Result<RenderType, Error> {
     match identifier(input).as_ref().map(String::as_ref) {
         Ok("hearts") => Ok(RenderType::Hearts),
@@ -151,17 +221,19 @@ fn objectives_set_display(input: &mut Input) -> Result<ObjectivesSetDisplay, Err
 }
 
 fn display_slot(input: &mut Input) -> Result<DisplaySlot, Error> {
-    match identifier(input).as_ref().map(String::as_ref) {
-        Ok("belowName") => Ok(DisplaySlot::BelowName),
-        Ok("list") => Ok(DisplaySlot::List),
-        Ok("sidebar") => Ok(DisplaySlot::Sidebar),
-        _ => todo!(),
-    }
+    let slot = get_while(input, |c| {
+        c.map(|c| c.is_alphanumeric() || c == '_' || c == '.')
+            .unwrap_or(false)
+    })?;
+    DisplaySlot::from_name(&slot).ok_or(Error::Identifier)
 }
 
 fn players(input: &mut Input) -> Result<Players, Error> {
     match identifier(input).as_ref().map(String::as_ref) {
         Ok("add") => space(input).and(players_add(input)).map(Players::Add),
+        Ok("display") => space(input)
+            .and(players_display(input))
+            .map(Players::Display),
         Ok("enable") => space(input).and(players_enable(input)).map(Players::Enable),
         Ok("get") => space(input).and(players_get(input)).map(Players::Get),
         Ok("list") => players_list(input).map(Players::List),
@@ -187,6 +259,25 @@ fn players_add(input: &mut Input) -> Result<PlayersAdd, Error> {
     })
 }
 
+fn players_display(input: &mut Input) -> Result<PlayersDisplay, Error> {
+    let option = identifier(input)?;
+    let targets = space(input).and(target(input))?;
+    let objective = space(input).and(identifier(input))?;
+    let option = match option.as_str() {
+        "name" => ScoreDisplayOption::Name(end_or(input, |input| {
+            space(input).and(text_component(input))
+        })?),
+        "numberformat" => ScoreDisplayOption::NumberFormat(number_format(input)?),
+        _ => return Err(Error::Command),
+    };
+
+    Ok(PlayersDisplay {
+        targets,
+        objective,
+        option,
+    })
+}
+
 fn players_enable(input: &mut Input) -> Result<PlayersEnable, Error> {
     let target = target(input)?;
     let objective = space(input).and(identifier(input))?;
@@ -296,6 +387,8 @@ fn function_identifier(input: &mut Input) -> Result<FunctionIdentifier, Error> {
 fn execute(input: &mut Input) -> Result<Execute, Error> {
     match identifier(input).as_ref().map(String::as_str) {
         Ok("if") => space(input).and(execute_if(input)).map(Execute::If),
+        Ok("unless") => space(input).and(execute_if(input)).map(Execute::Unless),
+        Ok("store") => space(input).and(store(input)).map(Execute::Store),
         _ => todo!(),
     }
 }
@@ -303,10 +396,111 @@ fn execute(input: &mut Input) -> Result<Execute, Error> {
 fn execute_if(input: &mut Input) -> Result<If, Error> {
     match identifier(input).as_ref().map(String::as_str) {
         Ok("score") => space(input).and(score(input)).map(If::Score),
+        Ok("block") => space(input).and(block_condition(input)).map(If::Block),
+        Ok("blocks") => space(input).and(blocks_condition(input)).map(If::Blocks),
+        Ok("items") => space(input).and(items_condition(input)).map(If::Items),
+        Ok("predicate") => space(input)
+            .and(predicate_condition(input))
+            .map(If::Predicate),
         _ => todo!(),
     }
 }
 
+fn store(input: &mut Input) -> Result<Store, Error> {
+    let kind = match identifier(input).as_ref().map(String::as_str) {
+        Ok("result") => Ok(StoreKind::Result),
+        Ok("success") => Ok(StoreKind::Success),
+        _ => Err(Error::Command),
+    }?;
+    space(input)?;
+    let target = match identifier(input).as_ref().map(String::as_str) {
+        Ok("bossbar") => {
+            let id = space(input).and(resource_location(input))?;
+            space(input)?;
+            match identifier(input).as_ref().map(String::as_str) {
+                Ok("value") => Ok(StoreTarget::Bossbar(id, BossbarValue::Value)),
+                Ok("max") => Ok(StoreTarget::Bossbar(id, BossbarValue::Max)),
+                _ => Err(Error::Command),
+            }
+        }
+        Ok("score") => {
+            let targets = space(input).and(target(input))?;
+            let objective = space(input).and(identifier(input))?;
+            Ok(StoreTarget::Score(targets, objective))
+        }
+        _ => Err(Error::Command),
+    }?;
+    let command = run(input)?;
+
+    Ok(Store {
+        kind,
+        target,
+        command,
+    })
+}
+
+fn block_condition(input: &mut Input) -> Result<BlockCondition, Error> {
+    let position = block_pos(input)?;
+    let block = space(input).and(block_state(input))?;
+    let command = run(input)?;
+
+    Ok(BlockCondition {
+        position,
+        block,
+        command,
+    })
+}
+
+fn blocks_condition(input: &mut Input) -> Result<BlocksCondition, Error> {
+    let start = block_pos(input)?;
+    let end = space(input).and(block_pos(input))?;
+    let destination = space(input).and(block_pos(input))?;
+    space(input)?;
+    let mode = match identifier(input).as_ref().map(String::as_str) {
+        Ok("all") => Ok(BlocksMode::All),
+        Ok("masked") => Ok(BlocksMode::Masked),
+        _ => Err(Error::Command),
+    }?;
+    let command = run(input)?;
+
+    Ok(BlocksCondition {
+        start,
+        end,
+        destination,
+        mode,
+        command,
+    })
+}
+
+fn items_condition(input: &mut Input) -> Result<ItemsCondition, Error> {
+    let targets = entity_holder(input)?;
+    let slots = space(input).and(slot_name(input, item::slot_range))?;
+    let predicate = space(input).and(item_predicate(input))?;
+    let command = run(input)?;
+
+    Ok(ItemsCondition {
+        targets,
+        slots,
+        predicate,
+        command,
+    })
+}
+
+fn predicate_condition(input: &mut Input) -> Result<PredicateCondition, Error> {
+    let predicate = resource_location(input)?;
+    let command = run(input)?;
+
+    Ok(PredicateCondition { predicate, command })
+}
+
+fn run(input: &mut Input) -> Result<Box<Command>, Error> {
+    space(input)?;
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("run") => space(input).and(command(input)).map(Box::new),
+        _ => Err(Error::Command),
+    }
+}
+
 fn score(input: &mut Input) -> Result<Score, Error> {
     let target = target(input)?;
     let target_objective = space(input).and(identifier(input))?;
@@ -378,180 +572,1197 @@ fn interval(input: &mut Input) -> Result<Interval, Error> {
     match num_or_range_op(input).as_ref().map(String::as_str) {
         Ok("..") => signed_integer(input).map(Interval::LeftUnbounded),
         Ok(first) => {
-            let first = first.parse().map_err(|_| panic!(first.to_string()))?;
+            let first = first.parse().map_err(|_| Error::Integer)?;
             match num_or_range_op(input).as_ref().map(String::as_str) {
                 Ok("") => Ok(Interval::Value(first)),
                 Ok("..") => match num_or_range_op(input).as_ref().map(String::as_str) {
                     Ok("") => Ok(Interval::RightUnbounded(first)),
                     Ok(second) => {
-                        let second = second.parse().map_err(|_| todo!())?;
+                        let second = second.parse().map_err(|_| Error::Integer)?;
                         Ok(Interval::Bounded(first, second))
                     }
-                    _ => todo!(),
+                    Err(_) => Err(Error::Integer),
                 },
-                _ => todo!(),
+                _ => Err(Error::Integer),
             }
         }
-        _ => todo!(),
+        Err(_) => Err(Error::Integer),
     }
 }
 
 fn tellraw(input: &mut Input) -> Result<Tellraw, Error> {
     let target = target(input)?;
-    let message = space(input).and(string(input))?;
+    let message = space(input).and(text_component(input))?;
 
     Ok(Tellraw { target, message })
 }
 
-fn num_or_range_op(input: &mut Input) -> Result<String, Error> {
-    let c = input.chars.peek();
-    match c {
-        Some('.') => get_while(input, |c| c == Some('.')),
-        Some(integer) if integer.is_numeric() || integer == &'-' => get_while(input, |c| {
-            c.map(|c| c.is_numeric() || c == '-').unwrap_or(false)
-        }),
-        _ => Ok("".to_string()),
+fn tell(input: &mut Input) -> Result<Tell, Error> {
+    let targets = target(input)?;
+    let message = space(input).and(message(input))?;
+
+    Ok(Tell { targets, message })
+}
+
+fn title(input: &mut Input) -> Result<Title, Error> {
+    let targets = target(input)?;
+    space(input)?;
+    let action = match identifier(input).as_ref().map(String::as_str) {
+        Ok("title") => space(input)
+            .and(text_component(input))
+            .map(TitleAction::Title),
+        Ok("subtitle") => space(input)
+            .and(text_component(input))
+            .map(TitleAction::Subtitle),
+        Ok("actionbar") => space(input)
+            .and(text_component(input))
+            .map(TitleAction::Actionbar),
+        Ok("times") => {
+            let fade_in = space(input).and(positive_integer(input))?;
+            let stay = space(input).and(positive_integer(input))?;
+            let fade_out = space(input).and(positive_integer(input))?;
+            Ok(TitleAction::Times {
+                fade_in,
+                stay,
+                fade_out,
+            })
+        }
+        Ok("clear") => Ok(TitleAction::Clear),
+        Ok("reset") => Ok(TitleAction::Reset),
+        _ => Err(Error::Command),
+    }?;
+
+    Ok(Title { targets, action })
+}
+
+fn summon(input: &mut Input) -> Result<Summon, Error> {
+    let entity = resource_location(input)?;
+    let position = end_or(input, |input| space(input).and(vec3(input)))?;
+    let nbt = match position {
+        Some(_) => end_or(input, |input| space(input).and(nbt(input)))?,
+        None => None,
+    };
+
+    Ok(Summon {
+        entity,
+        position,
+        nbt,
+    })
+}
+
+fn tag(input: &mut Input) -> Result<Tag, Error> {
+    let targets = target(input)?;
+    space(input)?;
+    let action = match identifier(input).as_ref().map(String::as_str) {
+        Ok("add") => space(input).and(word(input)).map(TagAction::Add),
+        Ok("remove") => space(input).and(word(input)).map(TagAction::Remove),
+        Ok("list") => Ok(TagAction::List),
+        _ => Err(Error::Command),
+    }?;
+
+    Ok(Tag { targets, action })
+}
+
+fn setblock(input: &mut Input) -> Result<SetBlock, Error> {
+    let position = block_pos(input)?;
+    let block = space(input).and(block_state(input))?;
+    let mode = end_or(input, |input| {
+        space(input)?;
+        match identifier(input).as_ref().map(String::as_str) {
+            Ok("replace") => Ok(SetBlockMode::Replace),
+            Ok("keep") => Ok(SetBlockMode::Keep),
+            Ok("destroy") => Ok(SetBlockMode::Destroy),
+            _ => Err(Error::Command),
+        }
+    })?;
+
+    Ok(SetBlock {
+        position,
+        block,
+        mode: mode.unwrap_or(SetBlockMode::Replace),
+    })
+}
+
+fn fill(input: &mut Input) -> Result<Fill, Error> {
+    let from = block_pos(input)?;
+    let to = space(input).and(block_pos(input))?;
+    let block = space(input).and(block_state(input))?;
+    let mode = end_or(input, |input| {
+        space(input)?;
+        match identifier(input).as_ref().map(String::as_str) {
+            Ok("replace") => {
+                end_or(input, |input| space(input).and(block_state(input))).map(FillMode::Replace)
+            }
+            Ok("destroy") => Ok(FillMode::Destroy),
+            Ok("hollow") => Ok(FillMode::Hollow),
+            Ok("keep") => Ok(FillMode::Keep),
+            Ok("outline") => Ok(FillMode::Outline),
+            _ => Err(Error::Command),
+        }
+    })?;
+
+    Ok(Fill {
+        from,
+        to,
+        block,
+        mode: mode.unwrap_or(FillMode::Replace(None)),
+    })
+}
+
+fn clone(input: &mut Input) -> Result<CloneBlocks, Error> {
+    let begin = block_pos(input)?;
+    let end = space(input).and(block_pos(input))?;
+    let destination = space(input).and(block_pos(input))?;
+    let mask = end_or(input, |input| {
+        space(input)?;
+        match identifier(input).as_ref().map(String::as_str) {
+            Ok("replace") => Ok(CloneMask::Replace),
+            Ok("masked") => Ok(CloneMask::Masked),
+            Ok("filtered") => space(input)
+                .and(block_state(input))
+                .map(CloneMask::Filtered),
+            _ => Err(Error::Command),
+        }
+    })?;
+    let mode = match mask {
+        Some(_) => end_or(input, |input| {
+            space(input)?;
+            match identifier(input).as_ref().map(String::as_str) {
+                Ok("force") => Ok(CloneMode::Force),
+                Ok("move") => Ok(CloneMode::Move),
+                Ok("normal") => Ok(CloneMode::Normal),
+                _ => Err(Error::Command),
+            }
+        })?,
+        None => None,
+    };
+
+    Ok(CloneBlocks {
+        begin,
+        end,
+        destination,
+        mask: mask.unwrap_or(CloneMask::Replace),
+        mode: mode.unwrap_or(CloneMode::Normal),
+    })
+}
+
+fn place(input: &mut Input) -> Result<Place, Error> {
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("template") => space(input).and(place_template(input)).map(Place::Template),
+        _ => Err(Error::Command),
     }
 }
 
-fn target(input: &mut Input) -> Result<Target, Error> {
-    identifier(input)
-        .map(Target::Name)
-        .or(selector(input).map(Target::Selector))
+fn place_template(input: &mut Input) -> Result<PlaceTemplate, Error> {
+    let template = resource_location(input)?;
+    let position = end_or(input, |input| space(input).and(block_pos(input)))?;
+    let rotation = match position {
+        Some(_) => end_or(input, |input| {
+            space(input)?;
+            match resource_location(input).as_ref().map(String::as_str) {
+                Ok("none") => Ok(StructureRotation::None),
+                Ok("clockwise_90") => Ok(StructureRotation::Clockwise90),
+                Ok("180") => Ok(StructureRotation::Clockwise180),
+                Ok("counterclockwise_90") => Ok(StructureRotation::Counterclockwise90),
+                _ => Err(Error::Command),
+            }
+        })?,
+        None => None,
+    };
+    let mirror = match rotation {
+        Some(_) => end_or(input, |input| {
+            space(input)?;
+            match identifier(input).as_ref().map(String::as_str) {
+                Ok("none") => Ok(Mirror::None),
+                Ok("left_right") => Ok(Mirror::LeftRight),
+                Ok("front_back") => Ok(Mirror::FrontBack),
+                _ => Err(Error::Command),
+            }
+        })?,
+        None => None,
+    };
+
+    Ok(PlaceTemplate {
+        template,
+        position,
+        rotation: rotation.unwrap_or(StructureRotation::None),
+        mirror: mirror.unwrap_or(Mirror::None),
+    })
 }
 
-fn selector(input: &mut Input) -> Result<Selector, Error> {
-    match operator(input).as_ref().map(String::as_str) {
-        Ok("@a") => Ok(Selector {
-            variable: SelectorVariable::A,
-        }),
-        _ => Err(Error::Target),
+fn bossbar(input: &mut Input) -> Result<Bossbar, Error> {
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("add") => {
+            let id = space(input).and(resource_location(input))?;
+            let name = space(input).and(text_component(input))?;
+            Ok(Bossbar::Add(id, name))
+        }
+        Ok("remove") => space(input)
+            .and(resource_location(input))
+            .map(Bossbar::Remove),
+        Ok("list") => Ok(Bossbar::List),
+        Ok("get") => {
+            let id = space(input).and(resource_location(input))?;
+            space(input)?;
+            let value = match identifier(input).as_ref().map(String::as_str) {
+                Ok("max") => Ok(BossbarGet::Max),
+                Ok("players") => Ok(BossbarGet::Players),
+                Ok("value") => Ok(BossbarGet::Value),
+                Ok("visible") => Ok(BossbarGet::Visible),
+                _ => Err(Error::Command),
+            }?;
+            Ok(Bossbar::Get(id, value))
+        }
+        Ok("set") => {
+            let id = space(input).and(resource_location(input))?;
+            let setting = space(input).and(bossbar_set(input))?;
+            Ok(Bossbar::Set(id, setting))
+        }
+        _ => Err(Error::Command),
     }
 }
 
-fn identifier(input: &mut Input) -> Result<String, Error> {
-    let lexeme = get_while(input, is_identifier_element)?;
-    if lexeme.len() == 0 {
-        Err(Error::Identifier)
-    } else {
-        Ok(lexeme)
+fn bossbar_set(input: &mut Input) -> Result<BossbarSet, Error> {
+    let setting = identifier(input)?;
+    if setting == "players" {
+        return end_or(input, |input| space(input).and(target(input))).map(BossbarSet::Players);
+    }
+    space(input)?;
+    match setting.as_str() {
+        "color" => BossbarColor::from_name(&identifier(input)?)
+            .map(BossbarSet::Color)
+            .ok_or(Error::Identifier),
+        "max" => match positive_integer(input)? {
+            0 => Err(Error::Identifier),
+            max => Ok(BossbarSet::Max(max)),
+        },
+        "name" => text_component(input).map(BossbarSet::Name),
+        "style" => BossbarStyle::from_name(&identifier(input)?)
+            .map(BossbarSet::Style)
+            .ok_or(Error::Identifier),
+        "value" => positive_integer(input).map(BossbarSet::Value),
+        "visible" => boolean(input).map(BossbarSet::Visible),
+        _ => Err(Error::Command),
     }
 }
 
-fn is_identifier_element(c: Option<char>) -> bool {
-    c.map(|c| c.is_alphanumeric() || c == '_').unwrap_or(false)
+fn team(input: &mut Input) -> Result<Team, Error> {
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("add") => {
+            let team = space(input).and(word(input))?;
+            let display_name = end_or(input, |input| space(input).and(text_component(input)))?;
+            Ok(Team::Add(TeamAdd { team, display_name }))
+        }
+        Ok("remove") => space(input).and(word(input)).map(Team::Remove),
+        Ok("empty") => space(input).and(word(input)).map(Team::Empty),
+        Ok("join") => {
+            let team = space(input).and(word(input))?;
+            let members = end_or(input, |input| space(input).and(target(input)))?;
+            Ok(Team::Join(team, members))
+        }
+        Ok("leave") => space(input).and(target(input)).map(Team::Leave),
+        Ok("list") => end_or(input, |input| space(input).and(word(input))).map(Team::List),
+        Ok("modify") => space(input).and(team_modify(input)).map(Team::Modify),
+        _ => Err(Error::Command),
+    }
 }
 
-fn string(input: &mut Input) -> Result<String, Error> {
-    expect_char(input, '"')?;
-    let content = get_while(input, |c| c != Some('"'));
-    expect_char(input, '"')?;
-    content
+fn team_modify(input: &mut Input) -> Result<TeamModify, Error> {
+    let team = word(input)?;
+    let option = space(input).and(identifier(input))?;
+    space(input)?;
+    let option = match option.as_str() {
+        "displayName" => text_component(input).map(TeamOption::DisplayName),
+        "color" => match identifier(input)?.as_str() {
+            "reset" => Ok(TeamOption::Color(None)),
+            color => team_color(color)
+                .map(|color| TeamOption::Color(Some(color)))
+                .ok_or(Error::Identifier),
+        },
+        "friendlyFire" => boolean(input).map(TeamOption::FriendlyFire),
+        "seeFriendlyInvisibles" => boolean(input).map(TeamOption::SeeFriendlyInvisibles),
+        "nametagVisibility" => Visibility::from_name(&identifier(input)?)
+            .map(TeamOption::NametagVisibility)
+            .ok_or(Error::Identifier),
+        "deathMessageVisibility" => Visibility::from_name(&identifier(input)?)
+            .map(TeamOption::DeathMessageVisibility)
+            .ok_or(Error::Identifier),
+        "collisionRule" => CollisionRule::from_name(&identifier(input)?)
+            .map(TeamOption::CollisionRule)
+            .ok_or(Error::Identifier),
+        "prefix" => text_component(input).map(TeamOption::Prefix),
+        "suffix" => text_component(input).map(TeamOption::Suffix),
+        _ => Err(Error::Command),
+    }?;
+
+    Ok(TeamModify { team, option })
 }
 
-fn operator(input: &mut Input) -> Result<String, Error> {
-    get_while(input, |c| c != Some(' ') && c != None)
+fn gamerule(input: &mut Input) -> Result<Gamerule, Error> {
+    let rule = get_while(input, |c| c.map(char::is_alphanumeric).unwrap_or(false))?;
+    let rule = GameRule::from_name(&rule).ok_or(Error::Identifier)?;
+    let value = end_or(input, |input| {
+        space(input)?;
+        match rule.default_value() {
+            GameRuleValue::Bool(_) => boolean(input).map(GameRuleValue::Bool),
+            GameRuleValue::Int(_) => signed_integer(input).map(GameRuleValue::Int),
+        }
+    })?;
+
+    Ok(Gamerule { rule, value })
 }
 
-fn get_while<F: Fn(Option<char>) -> bool>(
-    input: &mut Input,
-    condition: F,
-) -> Result<String, Error> {
-    let mut lexeme = String::new();
-    while condition(input.peek()) {
-        if let Some(c) = input.advance() {
-            lexeme.push(c)
+fn advancement(input: &mut Input) -> Result<Advancement, Error> {
+    let action = match identifier(input)?.as_str() {
+        "grant" => AdvancementAction::Grant,
+        "revoke" => AdvancementAction::Revoke,
+        _ => return Err(Error::Command),
+    };
+    let targets = space(input).and(target(input))?;
+    space(input)?;
+    let selection = match identifier(input)?.as_str() {
+        "everything" => AdvancementSelection::Everything,
+        "only" => {
+            let advancement = space(input).and(resource_location(input))?;
+            let criterion = end_or(input, |input| space(input).and(name(input)))?;
+            AdvancementSelection::Only(advancement, criterion)
+        }
+        "from" => space(input)
+            .and(resource_location(input))
+            .map(AdvancementSelection::From)?,
+        "until" => space(input)
+            .and(resource_location(input))
+            .map(AdvancementSelection::Until)?,
+        "through" => space(input)
+            .and(resource_location(input))
+            .map(AdvancementSelection::Through)?,
+        _ => return Err(Error::Command),
+    };
+
+    Ok(Advancement {
+        action,
+        targets,
+        selection,
+    })
+}
+
+fn random(input: &mut Input) -> Result<Random, Error> {
+    let sequence =
+        |input: &mut Input| end_or(input, |input| space(input).and(resource_location(input)));
+    match identifier(input)?.as_str() {
+        "value" => {
+            let range = space(input).and(interval(input))?;
+            Ok(Random::Value(range, sequence(input)?))
+        }
+        "roll" => {
+            let range = space(input).and(interval(input))?;
+            Ok(Random::Roll(range, sequence(input)?))
+        }
+        "reset" => {
+            space(input)?;
+            let sequence = match input.peek() {
+                Some('*') => {
+                    input.advance();
+                    None
+                }
+                _ => Some(resource_location(input)?),
+            };
+            let seed = end_or(input, |input| {
+                let seed = space(input).and(get_while(input, |c| {
+                    c.map(|c| c.is_ascii_digit() || c == '-').unwrap_or(false)
+                }))?;
+                seed.parse().map_err(|_| Error::Identifier)
+            })?;
+            let include_world_seed = end_or(input, |input| space(input).and(boolean(input)))?;
+            let include_sequence_id = end_or(input, |input| space(input).and(boolean(input)))?;
+            Ok(Random::Reset(RandomReset {
+                sequence,
+                seed,
+                include_world_seed,
+                include_sequence_id,
+            }))
         }
+        _ => Err(Error::Command),
     }
-    Ok(lexeme)
 }
 
-fn space(input: &mut Input) -> Result<(), Error> {
-    expect_char(input, ' ')
+fn time(input: &mut Input) -> Result<Time, Error> {
+    match identifier(input)?.as_str() {
+        "set" => {
+            space(input)?;
+            let named = match input.peek() {
+                Some(c) if c.is_alphabetic() => Some(identifier(input)?),
+                _ => None,
+            };
+            match named.as_deref() {
+                Some("day") => Ok(Time::Set(1000)),
+                Some("noon") => Ok(Time::Set(6000)),
+                Some("night") => Ok(Time::Set(13000)),
+                Some("midnight") => Ok(Time::Set(18000)),
+                Some(_) => Err(Error::Identifier),
+                None => ticks(input).map(Time::Set),
+            }
+        }
+        "add" => space(input).and(ticks(input)).map(Time::Add),
+        "query" => match space(input).and(identifier(input))?.as_str() {
+            "daytime" => Ok(Time::Query(TimeQuery::Daytime)),
+            "gametime" => Ok(Time::Query(TimeQuery::Gametime)),
+            "day" => Ok(Time::Query(TimeQuery::Day)),
+            _ => Err(Error::Identifier),
+        },
+        _ => Err(Error::Command),
+    }
 }
 
-fn expect_char(input: &mut Input, expected: char) -> Result<(), Error> {
-    match input.peek() {
-        Some(actual) if actual == expected => {
-            input.advance();
-            Ok(())
+fn weather(input: &mut Input) -> Result<Weather, Error> {
+    let kind = match identifier(input)?.as_str() {
+        "clear" => WeatherKind::Clear,
+        "rain" => WeatherKind::Rain,
+        "thunder" => WeatherKind::Thunder,
+        _ => return Err(Error::Identifier),
+    };
+    let duration = end_or(input, |input| space(input).and(ticks(input)))?;
+    match duration {
+        Some(duration) if duration < 1 => Err(Error::Identifier),
+        duration => Ok(Weather { kind, duration }),
+    }
+}
+
+/// A length of time: a number of ticks, or of seconds or days with an `s` or `d` after it.
+fn ticks(input: &mut Input) -> Result<i32, Error> {
+    let number = get_while(input, |c| {
+        c.map(|c| c.is_ascii_digit() || c == '.').unwrap_or(false)
+    })?;
+    let number: f64 = number.parse().map_err(|_| Error::Identifier)?;
+    let unit = match input.peek() {
+        Some('d') => 24000.0,
+        Some('s') => 20.0,
+        Some('t') => 1.0,
+        _ => return Ok(number.round() as i32),
+    };
+    input.advance();
+    Ok((number * unit).round() as i32)
+}
+
+fn boolean(input: &mut Input) -> Result<bool, Error> {
+    match identifier(input)?.as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(Error::Identifier),
+    }
+}
+
+fn trigger(input: &mut Input) -> Result<Trigger, Error> {
+    let objective = identifier(input)?;
+    let action = end_or(input, |input| {
+        space(input)?;
+        let action = identifier(input)?;
+        let value = space(input).and(signed_integer(input))?;
+        match action.as_str() {
+            "add" => Ok(TriggerAction::Add(value)),
+            "set" => Ok(TriggerAction::Set(value)),
+            _ => Err(Error::Identifier),
         }
-        Some(whitespace) if whitespace.is_whitespace() => {
-            Err(Error::Space(Space::WhitespaceInstead))
+    })?;
+
+    Ok(Trigger { objective, action })
+}
+
+fn give(input: &mut Input) -> Result<Give, Error> {
+    let targets = target(input)?;
+    let item = space(input).and(item_stack(input))?;
+    let count = end_or(input, |input| space(input).and(count(input)))?;
+
+    Ok(Give {
+        targets,
+        item,
+        count,
+    })
+}
+
+fn clear(input: &mut Input) -> Result<Clear, Error> {
+    let targets = end_or(input, |input| space(input).and(target(input)))?;
+    let item = match targets {
+        Some(_) => end_or(input, |input| space(input).and(item_predicate(input)))?,
+        None => None,
+    };
+    let max_count = match item {
+        Some(_) => end_or(input, |input| space(input).and(positive_integer(input)))?,
+        None => None,
+    };
+
+    Ok(Clear {
+        targets,
+        item,
+        max_count,
+    })
+}
+
+fn item(input: &mut Input) -> Result<Item, Error> {
+    let action = identifier(input)?;
+    let targets = space(input).and(entity_holder(input))?;
+    let slot = space(input).and(slot_name(input, |name| item::slot(name).map(|_| ())))?;
+    space(input)?;
+    match action.as_str() {
+        "replace" => {
+            let source = match identifier(input).as_ref().map(String::as_str) {
+                Ok("with") => {
+                    let stack = space(input).and(item_stack(input))?;
+                    let count = end_or(input, |input| space(input).and(positive_integer(input)))?;
+                    ItemSource::With(stack, count)
+                }
+                Ok("from") => {
+                    let source = space(input).and(entity_holder(input))?;
+                    let source_slot =
+                        space(input).and(slot_name(input, |name| item::slot(name).map(|_| ())))?;
+                    let modifier =
+                        end_or(input, |input| space(input).and(resource_location(input)))?;
+                    ItemSource::From(source, source_slot, modifier)
+                }
+                _ => return Err(Error::Command),
+            };
+            Ok(Item::Replace(ItemReplace {
+                targets,
+                slot,
+                source,
+            }))
         }
-        Some(_) => Err(Error::Space(Space::SymbolInstead)),
-        None => Err(Error::Space(Space::EOFInstead)),
+        "modify" => Ok(Item::Modify(ItemModify {
+            targets,
+            slot,
+            modifier: resource_location(input)?,
+        })),
+        _ => Err(Error::Command),
     }
 }
 
-fn end_or<T, F>(input: &mut Input, f: F) -> Result<Option<T>, Error>
+/// The `entity <targets>` that `item` and `execute if items` take. Block containers aren't
+/// simulated.
+fn entity_holder(input: &mut Input) -> Result<Target, Error> {
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("entity") => space(input).and(target(input)),
+        _ => Err(Error::Command),
+    }
+}
+
+fn slot_name<T, F>(input: &mut Input, valid: F) -> Result<String, Error>
 where
-    F: Fn(&mut Input) -> Result<T, Error>,
+    F: Fn(&str) -> Option<T>,
 {
+    let name = get_while(input, |c| {
+        c.map(|c| c.is_alphanumeric() || "_.*".contains(c))
+            .unwrap_or(false)
+    })?;
+    match valid(&name) {
+        Some(_) => Ok(name),
+        None => Err(Error::Identifier),
+    }
+}
+
+fn count(input: &mut Input) -> Result<i32, Error> {
     match input.peek() {
-        Some(_) => f(input).map(Some),
-        None => Ok(None),
+        Some(c) if c.is_ascii_digit() => match positive_integer(input)? {
+            0 => Err(Error::Identifier),
+            count => Ok(count),
+        },
+        _ => Err(Error::Identifier),
     }
 }
 
-fn positive_integer(input: &mut Input) -> Result<i32, Error> {
-    let integer = get_while(input, |c| c.map(char::is_numeric).unwrap_or(false))?;
-    integer.parse().map_err(|_| todo!())
+/// An item such as `diamond` or `diamond_sword[custom_name='"Ruby"']`. Pre-component NBT,
+/// as in `diamond{shop:1b}`, becomes the `custom_data` component.
+fn item_stack(input: &mut Input) -> Result<ItemStack, Error> {
+    let mut stack = ItemStack::new(&resource_location(input)?, 1);
+    if input.peek() == Some('[') {
+        for (name, partial, value) in components(input)? {
+            if partial {
+                return Err(Error::Identifier);
+            }
+            stack.components.insert(name, value);
+        }
+    }
+    if input.peek() == Some('{') {
+        stack
+            .components
+            .insert(String::from("minecraft:custom_data"), compound(input)?);
+    }
+
+    Ok(stack)
 }
 
-fn signed_integer(input: &mut Input) -> Result<i32, Error> {
-    let integer = get_while(input, |c| {
-        c.map(|c| c.is_numeric() || c == '-').unwrap_or(false)
-    })?;
-    integer.parse().map_err(|_| todo!())
+/// `*` or an item id, with component tests like `[custom_data~{shop:1b}]`. Pre-component
+/// NBT, as in `diamond{shop:1b}`, tests `custom_data` the same way.
+fn item_predicate(input: &mut Input) -> Result<ItemPredicate, Error> {
+    let id = match input.peek() {
+        Some('*') => {
+            input.advance();
+            None
+        }
+        _ => Some(resource_location(input)?),
+    };
+    let mut tests = Vec::new();
+    if input.peek() == Some('[') {
+        for (name, partial, value) in components(input)? {
+            tests.push(match partial {
+                true => ComponentTest::Matches(name, value),
+                false => ComponentTest::Equals(name, value),
+            });
+        }
+    }
+    if input.peek() == Some('{') {
+        tests.push(ComponentTest::Matches(
+            String::from("minecraft:custom_data"),
+            compound(input)?,
+        ));
+    }
+
+    Ok(ItemPredicate { id, tests })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Bracketed `name=value` or `name~value` pairs, flagging the partial `~` ones.
+fn components(input: &mut Input) -> Result<Vec<(String, bool, Nbt)>, Error> {
+    expect_char(input, '[')?;
+    let mut components = Vec::new();
+    while input.peek() != Some(']') {
+        let name = resource_location(input)?;
+        let partial = match input.advance() {
+            Some('=') => false,
+            Some('~') => true,
+            _ => return Err(Error::Identifier),
+        };
+        let value = nbt::parse_prefix(&mut input.chars).map_err(Error::Nbt)?;
+        components.push((name, partial, value));
+        if input.peek() != Some(']') {
+            expect_char(input, ',')?;
+        }
+    }
+    input.advance();
+    Ok(components)
+}
 
-    #[test]
-    fn scoreboard_objectives_add() {
-        assert_eq!(
-            parse_line("scoreboard objectives add obj dummy"),
-            Ok(Command::Scoreboard(Scoreboard::Objectives(
-                Objectives::Add(ObjectivesAdd {
-                    objective: "obj".to_string(),
-                    criteria: Criteria::Dummy,
-                    display_name: None,
-                })
-            )))
-        );
-        assert_eq!(
-            parse_line("scoreboard objectives add obj_2 dummy \"display name 2\""),
-            Ok(Command::Scoreboard(Scoreboard::Objectives(
-                Objectives::Add(ObjectivesAdd {
-                    objective: "obj_2".to_string(),
-                    criteria: Criteria::Dummy,
-                    display_name: Some("display name 2".to_string()),
-                })
-            )))
-        );
+fn block_state(input: &mut Input) -> Result<Block, Error> {
+    let mut block = Block::new(&resource_location(input)?);
+    if input.peek() == Some('[') {
+        input.advance();
+        while input.peek() != Some(']') {
+            let state = identifier(input)?;
+            expect_char(input, '=')?;
+            let value = identifier(input)?;
+            block.states.insert(state, value);
+            if input.peek() != Some(']') {
+                expect_char(input, ',')?;
+            }
+        }
+        input.advance();
+    }
+    if input.peek() == Some('{') {
+        block.nbt = Some(compound(input)?);
     }
 
-    #[test]
-    fn scoreboard_objectives_list() {
-        assert_eq!(
-            parse_line("scoreboard objectives list"),
-            Ok(Command::Scoreboard(Scoreboard::Objectives(
-                Objectives::List
-            )))
-        )
+    Ok(block)
+}
+
+/// An SNBT compound that other arguments may follow.
+fn compound(input: &mut Input) -> Result<Nbt, Error> {
+    nbt::parse_prefix(&mut input.chars).map_err(Error::Nbt)
+}
+
+fn word(input: &mut Input) -> Result<String, Error> {
+    let lexeme = get_while(input, |c| {
+        c.map(|c| c.is_alphanumeric() || "_.+-".contains(c))
+            .unwrap_or(false)
+    })?;
+    if lexeme.is_empty() {
+        Err(Error::Identifier)
+    } else {
+        Ok(lexeme)
     }
+}
 
-    #[test]
-    fn scoreboard_objectives_modify() {
-        assert_eq!(
+fn teleport(input: &mut Input) -> Result<Teleport, Error> {
+    if is_location(input) {
+        return Ok(Teleport {
+            targets: None,
+            destination: Destination::Location(vec3(input)?, None),
+        });
+    }
+    let first = target(input)?;
+    if input.peek().is_none() {
+        return Ok(Teleport {
+            targets: None,
+            destination: Destination::Entity(first),
+        });
+    }
+    space(input)?;
+    let destination = if is_location(input) {
+        let position = vec3(input)?;
+        let orientation = end_or(input, |input| space(input).and(orientation(input)))?;
+        Destination::Location(position, orientation)
+    } else {
+        Destination::Entity(target(input)?)
+    };
+
+    Ok(Teleport {
+        targets: Some(first),
+        destination,
+    })
+}
+
+fn is_location(input: &mut Input) -> bool {
+    input
+        .peek()
+        .map(|c| c.is_ascii_digit() || "~^-.".contains(c))
+        .unwrap_or(false)
+}
+
+fn orientation(input: &mut Input) -> Result<Orientation, Error> {
+    if input.peek() != Some('f') {
+        return rotation(input).map(Orientation::Rotation);
+    }
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("facing") => space(input),
+        _ => return Err(Error::Command),
+    }?;
+    if is_location(input) {
+        return vec3(input).map(Orientation::FacingLocation);
+    }
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("entity") => space(input),
+        _ => return Err(Error::Command),
+    }?;
+    let entity = target(input)?;
+    let anchor = end_or(input, |input| space(input).and(anchor(input)))?;
+    Ok(Orientation::FacingEntity(
+        entity,
+        anchor.unwrap_or(Anchor::Feet),
+    ))
+}
+
+fn anchor(input: &mut Input) -> Result<Anchor, Error> {
+    match identifier(input).as_ref().map(String::as_str) {
+        Ok("eyes") => Ok(Anchor::Eyes),
+        Ok("feet") => Ok(Anchor::Feet),
+        _ => Err(Error::Command),
+    }
+}
+
+fn rotation(input: &mut Input) -> Result<Rotation, Error> {
+    let yaw = coordinate(input)?;
+    let pitch = space(input).and(coordinate(input))?;
+    match (yaw, pitch) {
+        (Coordinate::Local(_), _) | (_, Coordinate::Local(_)) => Err(Error::Coordinate),
+        _ => Ok(Rotation { yaw, pitch }),
+    }
+}
+
+fn vec3(input: &mut Input) -> Result<Vec3, Error> {
+    let x = coordinate(input)?;
+    let y = space(input).and(coordinate(input))?;
+    let z = space(input).and(coordinate(input))?;
+    check_local(&[x, y, z])?;
+
+    Ok(Vec3 { x, y, z })
+}
+
+fn block_pos(input: &mut Input) -> Result<BlockPos, Error> {
+    let x = coordinate(input)?;
+    let y = space(input).and(coordinate(input))?;
+    let z = space(input).and(coordinate(input))?;
+    check_local(&[x, y, z])?;
+    for axis in &[x, y, z] {
+        if let Coordinate::Absolute(value, _) = axis {
+            if value.fract() != 0.0 {
+                return Err(Error::Coordinate);
+            }
+        }
+    }
+
+    Ok(BlockPos { x, y, z })
+}
+
+fn vec2(input: &mut Input) -> Result<Vec2, Error> {
+    let x = coordinate(input)?;
+    let z = space(input).and(coordinate(input))?;
+    match (x, z) {
+        (Coordinate::Local(_), _) | (_, Coordinate::Local(_)) => Err(Error::Coordinate),
+        _ => Ok(Vec2 { x, z }),
+    }
+}
+
+/// `^` can't be mixed with world coordinates: either every axis is local or none is.
+fn check_local(axes: &[Coordinate]) -> Result<(), Error> {
+    let local = axes
+        .iter()
+        .filter(|axis| matches!(axis, Coordinate::Local(_)))
+        .count();
+    if local == 0 || local == axes.len() {
+        Ok(())
+    } else {
+        Err(Error::Coordinate)
+    }
+}
+
+fn coordinate(input: &mut Input) -> Result<Coordinate, Error> {
+    let prefix = match input.peek() {
+        Some(c) if c == '~' || c == '^' => input.advance(),
+        _ => None,
+    };
+    let number = get_while(input, |c| {
+        c.map(|c| c.is_ascii_digit() || c == '-' || c == '.')
+            .unwrap_or(false)
+    })?;
+    let value = match (prefix, number.as_str()) {
+        (Some(_), "") => Ok(0.0),
+        (_, number) => number.parse().map_err(|_| Error::Coordinate),
+    }?;
+    Ok(match prefix {
+        Some('~') => Coordinate::Relative(value),
+        Some(_) => Coordinate::Local(value),
+        None => Coordinate::Absolute(value, number.contains('.')),
+    })
+}
+
+/// SNBT data running to the end of the line.
+fn nbt(input: &mut Input) -> Result<Nbt, Error> {
+    let snbt = get_while(input, |c| c.is_some())?;
+    nbt::parse(&snbt).map_err(Error::Nbt)
+}
+
+fn resource_location(input: &mut Input) -> Result<String, Error> {
+    let lexeme = get_while(input, |c| {
+        c.map(|c| c.is_alphanumeric() || "_:/.-".contains(c))
+            .unwrap_or(false)
+    })?;
+    if lexeme.is_empty() {
+        Err(Error::Identifier)
+    } else {
+        Ok(lexeme)
+    }
+}
+
+fn message(input: &mut Input) -> Result<String, Error> {
+    let message = get_while(input, |c| c.is_some())?;
+    if message.is_empty() {
+        Err(Error::Space(Space::EOFInstead))
+    } else {
+        Ok(message)
+    }
+}
+
+fn text_component(input: &mut Input) -> Result<TextComponent, Error> {
+    let json = get_while(input, |c| c.is_some())?;
+    text::parse(&json).map_err(Error::Text)
+}
+
+fn num_or_range_op(input: &mut Input) -> Result<String, Error> {
+    let c = input.chars.peek();
+    match c {
+        Some('.') => get_while(input, |c| c == Some('.')),
+        Some(integer) if integer.is_numeric() || integer == &'-' => get_while(input, |c| {
+            c.map(|c| c.is_numeric() || c == '-').unwrap_or(false)
+        }),
+        _ => Ok("".to_string()),
+    }
+}
+
+fn target(input: &mut Input) -> Result<Target, Error> {
+    match input.peek() {
+        Some('@') => selector(input).map(Target::Selector),
+        _ => name(input).map(Target::Name),
+    }
+}
+
+/// A player name, UUID or fake score holder like `#temp`: anything up to the next space.
+fn name(input: &mut Input) -> Result<String, Error> {
+    let lexeme = get_while(input, |c| c.map(|c| !c.is_whitespace()).unwrap_or(false))?;
+    if lexeme.is_empty() {
+        Err(Error::Identifier)
+    } else {
+        Ok(lexeme)
+    }
+}
+
+fn selector(input: &mut Input) -> Result<Selector, Error> {
+    expect_char(input, '@').map_err(|_| Error::Target)?;
+    let variable = match input.advance() {
+        Some('p') => SelectorVariable::P,
+        Some('r') => SelectorVariable::R,
+        Some('a') => SelectorVariable::A,
+        Some('e') => SelectorVariable::E,
+        Some('s') => SelectorVariable::S,
+        _ => return Err(Error::Target),
+    };
+    let mut selector = Selector::new(variable);
+    if input.peek() == Some('[') {
+        input.advance();
+        selector.arguments = selector_arguments(input)?;
+    }
+    match input.peek() {
+        Some(c) if !c.is_whitespace() => Err(Error::Target),
+        _ => Ok(selector),
+    }
+}
+
+fn selector_arguments(input: &mut Input) -> Result<Vec<SelectorArgument>, Error> {
+    let mut arguments = Vec::new();
+    if input.peek() == Some(']') {
+        input.advance();
+        return Ok(arguments);
+    }
+    loop {
+        let key = identifier(input)?;
+        expect_char(input, '=').map_err(|_| Error::Target)?;
+        arguments.push(selector_argument(input, &key)?);
+        match input.advance() {
+            Some(',') => continue,
+            Some(']') => return Ok(arguments),
+            _ => return Err(Error::Target),
+        }
+    }
+}
+
+fn selector_argument(input: &mut Input, key: &str) -> Result<SelectorArgument, Error> {
+    match key {
+        "type" => {
+            let negated = negation(input);
+            let kind = resource_location(input)?;
+            Ok(SelectorArgument::Type { kind, negated })
+        }
+        "tag" => {
+            let negated = negation(input);
+            let tag = argument_value(input)?;
+            Ok(SelectorArgument::Tag { tag, negated })
+        }
+        "team" => {
+            let negated = negation(input);
+            let team = argument_value(input)?;
+            Ok(SelectorArgument::Team { team, negated })
+        }
+        "name" => {
+            let negated = negation(input);
+            let name = match input.peek() {
+                Some('"') => string(input)?,
+                _ => argument_value(input)?,
+            };
+            Ok(SelectorArgument::Name { name, negated })
+        }
+        "limit" => match positive_integer(input) {
+            Ok(0) => Err(Error::Target),
+            limit => limit.map(SelectorArgument::Limit),
+        },
+        "scores" => scores(input).map(SelectorArgument::Scores),
+        "x" => float(input).map(SelectorArgument::X),
+        "y" => float(input).map(SelectorArgument::Y),
+        "z" => float(input).map(SelectorArgument::Z),
+        "distance" => float_range(input).map(SelectorArgument::Distance),
+        "dx" => float(input).map(SelectorArgument::Dx),
+        "dy" => float(input).map(SelectorArgument::Dy),
+        "dz" => float(input).map(SelectorArgument::Dz),
+        "sort" => match identifier(input).as_ref().map(String::as_str) {
+            Ok("nearest") => Ok(SelectorArgument::Sort(Sort::Nearest)),
+            Ok("furthest") => Ok(SelectorArgument::Sort(Sort::Furthest)),
+            Ok("arbitrary") => Ok(SelectorArgument::Sort(Sort::Arbitrary)),
+            Ok("random") => Ok(SelectorArgument::Sort(Sort::Random)),
+            _ => Err(Error::Target),
+        },
+        "predicate" => {
+            let negated = negation(input);
+            let predicate = resource_location(input)?;
+            Ok(SelectorArgument::Predicate { predicate, negated })
+        }
+        _ => Err(Error::Target),
+    }
+}
+
+fn scores(input: &mut Input) -> Result<Vec<(String, Interval)>, Error> {
+    expect_char(input, '{').map_err(|_| Error::Target)?;
+    let mut scores = Vec::new();
+    if input.peek() == Some('}') {
+        input.advance();
+        return Ok(scores);
+    }
+    loop {
+        let objective = identifier(input)?;
+        expect_char(input, '=').map_err(|_| Error::Target)?;
+        scores.push((objective, interval(input)?));
+        match input.advance() {
+            Some(',') => continue,
+            Some('}') => return Ok(scores),
+            _ => return Err(Error::Target),
+        }
+    }
+}
+
+fn float(input: &mut Input) -> Result<f64, Error> {
+    decimal(input)?.parse().map_err(|_| Error::Target)
+}
+
+fn float_range(input: &mut Input) -> Result<FloatRange, Error> {
+    let lexeme = decimal(input)?;
+    let bound = |text: &str| match text {
+        "" => Ok(None),
+        text => text.parse().map(Some).map_err(|_| Error::Target),
+    };
+    match lexeme.find("..") {
+        Some(i) => Ok(FloatRange {
+            min: bound(&lexeme[..i])?,
+            max: bound(&lexeme[i + 2..])?,
+        }),
+        None => {
+            let value = bound(&lexeme)?.ok_or(Error::Target)?;
+            Ok(FloatRange {
+                min: Some(value),
+                max: Some(value),
+            })
+        }
+    }
+}
+
+fn decimal(input: &mut Input) -> Result<String, Error> {
+    get_while(input, |c| {
+        c.map(|c| c.is_ascii_digit() || c == '-' || c == '.')
+            .unwrap_or(false)
+    })
+}
+
+fn negation(input: &mut Input) -> bool {
+    let negated = input.peek() == Some('!');
+    if negated {
+        input.advance();
+    }
+    negated
+}
+
+fn argument_value(input: &mut Input) -> Result<String, Error> {
+    get_while(input, |c| {
+        c.map(|c| c != ',' && c != ']' && !c.is_whitespace())
+            .unwrap_or(false)
+    })
+}
+
+fn identifier(input: &mut Input) -> Result<String, Error> {
+    let lexeme = get_while(input, is_identifier_element)?;
+    if lexeme.is_empty() {
+        Err(Error::Identifier)
+    } else {
+        Ok(lexeme)
+    }
+}
+
+fn is_identifier_element(c: Option<char>) -> bool {
+    c.map(|c| c.is_alphanumeric() || c == '_').unwrap_or(false)
+}
+
+fn string(input: &mut Input) -> Result<String, Error> {
+    expect_char(input, '"')?;
+    let content = get_while(input, |c| c != Some('"'));
+    expect_char(input, '"')?;
+    content
+}
+
+fn operator(input: &mut Input) -> Result<String, Error> {
+    get_while(input, |c| c != Some(' ') && c.is_some())
+}
+
+fn get_while<F: Fn(Option<char>) -> bool>(
+    input: &mut Input,
+    condition: F,
+) -> Result<String, Error> {
+    let mut lexeme = String::new();
+    while condition(input.peek()) {
+        if let Some(c) = input.advance() {
+            lexeme.push(c)
+        }
+    }
+    Ok(lexeme)
+}
+
+fn space(input: &mut Input) -> Result<(), Error> {
+    expect_char(input, ' ')
+}
+
+fn expect_char(input: &mut Input, expected: char) -> Result<(), Error> {
+    match input.peek() {
+        Some(actual) if actual == expected => {
+            input.advance();
+            Ok(())
+        }
+        Some(whitespace) if whitespace.is_whitespace() => {
+            Err(Error::Space(Space::WhitespaceInstead))
+        }
+        Some(_) => Err(Error::Space(Space::SymbolInstead)),
+        None => Err(Error::Space(Space::EOFInstead)),
+    }
+}
+
+fn end_or<T, F>(input: &mut Input, f: F) -> Result<Option<T>, Error>
+where
+    F: Fn(&mut Input) -> Result<T, Error>,
+{
+    match input.peek() {
+        Some(_) => f(input).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn positive_integer(input: &mut Input) -> Result<i32, Error> {
+    let integer = get_while(input, |c| c.map(char::is_numeric).unwrap_or(false))?;
+    integer.parse().map_err(|_| Error::Integer)
+}
+
+fn signed_integer(input: &mut Input) -> Result<i32, Error> {
+    let integer = get_while(input, |c| {
+        c.map(|c| c.is_numeric() || c == '-').unwrap_or(false)
+    })?;
+    integer.parse().map_err(|_| Error::Integer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoreboard_objectives_add() {
+        assert_eq!(
+            parse_line("scoreboard objectives add obj dummy"),
+            Ok(Command::Scoreboard(Scoreboard::Objectives(
+                Objectives::Add(ObjectivesAdd {
+                    objective: "obj".to_string(),
+                    criteria: Criteria::Dummy,
+                    display_name: None,
+                })
+            )))
+        );
+        assert_eq!(
+            parse_line("scoreboard objectives add obj_2 dummy \"display name 2\""),
+            Ok(Command::Scoreboard(Scoreboard::Objectives(
+                Objectives::Add(ObjectivesAdd {
+                    objective: "obj_2".to_string(),
+                    criteria: Criteria::Dummy,
+                    display_name: Some(TextComponent::text("display name 2")),
+                })
+            )))
+        );
+    }
+
+    #[test]
+    fn scoreboard_objectives_list() {
+        assert_eq!(
+            parse_line("scoreboard objectives list"),
+            Ok(Command::Scoreboard(Scoreboard::Objectives(
+                Objectives::List
+            )))
+        )
+    }
+
+    #[test]
+    fn scoreboard_objectives_modify() {
+        assert_eq!(
             parse_line("scoreboard objectives modify obj displayname \"new name\""),
             Ok(Command::Scoreboard(Scoreboard::Objectives(
                 Objectives::Modify(ObjectivesModify {
                     objective: "obj".to_string(),
-                    modification: Modification::DisplayName("new name".to_string())
+                    modification: Modification::DisplayName(TextComponent::text("new name"))
                 })
             )))
         );
@@ -968,6 +2179,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn execute_if_score_matches_not_a_number() {
+        assert_eq!(
+            parse_line(
+                "execute if score target targetObj matches x run scoreboard objectives list"
+            ),
+            Err(Error::Integer)
+        );
+    }
+
     fn execute_if_score_source(
         comparison_type: fn(SourceComparison) -> Score,
         conditional_command: &str,
@@ -1000,17 +2221,813 @@ mod tests {
             parse_line("tellraw player \"this message\""),
             Ok(Command::Tellraw(Tellraw {
                 target: Target::Name("player".to_string()),
-                message: "this message".to_string()
+                message: TextComponent::text("this message")
             }))
         );
         assert_eq!(
             parse_line("tellraw @a \"message to everyone\""),
             Ok(Command::Tellraw(Tellraw {
-                target: Target::Selector(Selector {
-                    variable: SelectorVariable::A
-                }),
-                message: "message to everyone".to_string()
+                target: Target::Selector(Selector::new(SelectorVariable::A)),
+                message: TextComponent::text("message to everyone")
             }))
         )
     }
+
+    #[test]
+    fn tellraw_json() {
+        assert_eq!(
+            parse_line("tellraw @a {\"text\":\"hi\",\"color\":\"red\"}"),
+            Ok(Command::Tellraw(Tellraw {
+                target: Target::Selector(Selector::new(SelectorVariable::A)),
+                message: text::parse("{\"text\":\"hi\",\"color\":\"red\"}").unwrap()
+            }))
+        );
+        assert_eq!(
+            parse_line("tellraw @s [\"\",{\"score\":{\"name\":\"@s\",\"objective\":\"obj\"}}]"),
+            Ok(Command::Tellraw(Tellraw {
+                target: Target::Selector(Selector::new(SelectorVariable::S)),
+                message: text::parse("[\"\",{\"score\":{\"name\":\"@s\",\"objective\":\"obj\"}}]")
+                    .unwrap()
+            }))
+        );
+        assert_eq!(
+            parse_line("tellraw @a {\"text\":"),
+            Err(Error::Text(text::Error::Json(json::Error::UnexpectedEnd)))
+        );
+    }
+
+    #[test]
+    fn objectives_json_display_name() {
+        assert_eq!(
+            parse_line(
+                "scoreboard objectives modify obj displayname {\"text\":\"Kills\",\"bold\":true}"
+            ),
+            Ok(Command::Scoreboard(Scoreboard::Objectives(
+                Objectives::Modify(ObjectivesModify {
+                    objective: "obj".to_string(),
+                    modification: Modification::DisplayName(
+                        text::parse("{\"text\":\"Kills\",\"bold\":true}").unwrap()
+                    )
+                })
+            )))
+        );
+    }
+
+    #[test]
+    fn say_tell_me() {
+        assert_eq!(
+            parse_line("say hello @a!"),
+            Ok(Command::Say(Say {
+                message: "hello @a!".to_string()
+            }))
+        );
+        for alias in &["tell", "msg", "w"] {
+            assert_eq!(
+                parse_line(&format!("{} Alice meet me at spawn", alias)),
+                Ok(Command::Tell(Tell {
+                    targets: Target::Name("Alice".to_string()),
+                    message: "meet me at spawn".to_string()
+                }))
+            );
+        }
+        assert_eq!(
+            parse_line("me waves"),
+            Ok(Command::Me(Me {
+                action: "waves".to_string()
+            }))
+        );
+        assert_eq!(parse_line("say "), Err(Error::Space(Space::EOFInstead)));
+    }
+
+    #[test]
+    fn title() {
+        let title = |action| {
+            Ok(Command::Title(Title {
+                targets: Target::Selector(Selector::new(SelectorVariable::A)),
+                action,
+            }))
+        };
+        assert_eq!(
+            parse_line("title @a title {\"text\":\"Welcome\",\"bold\":true}"),
+            title(TitleAction::Title(
+                text::parse("{\"text\":\"Welcome\",\"bold\":true}").unwrap()
+            ))
+        );
+        assert_eq!(
+            parse_line("title @a subtitle \"sub\""),
+            title(TitleAction::Subtitle(TextComponent::text("sub")))
+        );
+        assert_eq!(
+            parse_line("title @a actionbar \"bar\""),
+            title(TitleAction::Actionbar(TextComponent::text("bar")))
+        );
+        assert_eq!(
+            parse_line("title @a times 5 40 10"),
+            title(TitleAction::Times {
+                fade_in: 5,
+                stay: 40,
+                fade_out: 10
+            })
+        );
+        assert_eq!(parse_line("title @a clear"), title(TitleAction::Clear));
+        assert_eq!(parse_line("title @a reset"), title(TitleAction::Reset));
+        assert_eq!(parse_line("title @a flash"), Err(Error::Command));
+        assert_eq!(parse_line("title @a times a 1 1"), Err(Error::Integer));
+    }
+
+    #[test]
+    fn score_holder_names() {
+        assert_eq!(parse_target("#temp"), Ok(Target::Name("#temp".to_string())));
+        assert_eq!(
+            parse_target("01234567-89ab-cdef-0123-456789abcdef"),
+            Ok(Target::Name(
+                "01234567-89ab-cdef-0123-456789abcdef".to_string()
+            ))
+        );
+        assert_eq!(parse_target(""), Err(Error::Identifier));
+    }
+
+    #[test]
+    fn selector_arguments() {
+        let mut selector = Selector::new(SelectorVariable::E);
+        selector.arguments = vec![
+            SelectorArgument::Type {
+                kind: "minecraft:marker".to_string(),
+                negated: false,
+            },
+            SelectorArgument::Tag {
+                tag: "a".to_string(),
+                negated: true,
+            },
+            SelectorArgument::Tag {
+                tag: "".to_string(),
+                negated: false,
+            },
+            SelectorArgument::Name {
+                name: "Big Bob".to_string(),
+                negated: false,
+            },
+            SelectorArgument::Limit(3),
+            SelectorArgument::Scores(vec![
+                ("obj".to_string(), Interval::Bounded(1, 5)),
+                ("other".to_string(), Interval::LeftUnbounded(0)),
+            ]),
+        ];
+        assert_eq!(
+            parse_target(
+                "@e[type=minecraft:marker,tag=!a,tag=,name=\"Big Bob\",limit=3,scores={obj=1..5,other=..0}]"
+            ),
+            Ok(Target::Selector(selector))
+        );
+        assert_eq!(
+            parse_target("@a[]"),
+            Ok(Target::Selector(Selector::new(SelectorVariable::A)))
+        );
+        assert_eq!(parse_target("@e[colour=red]"), Err(Error::Target));
+        assert_eq!(parse_target("@e[tag=a"), Err(Error::Target));
+        assert_eq!(parse_target("@x"), Err(Error::Target));
+    }
+
+    #[test]
+    fn summon() {
+        assert_eq!(
+            parse_line("summon marker"),
+            Ok(Command::Summon(Summon {
+                entity: "marker".to_string(),
+                position: None,
+                nbt: None
+            }))
+        );
+        assert_eq!(
+            parse_line("summon minecraft:armor_stand ~ ~1.5 -3 {Tags:[\"a\"]}"),
+            Ok(Command::Summon(Summon {
+                entity: "minecraft:armor_stand".to_string(),
+                position: Some(Vec3 {
+                    x: Coordinate::Relative(0.0),
+                    y: Coordinate::Relative(1.5),
+                    z: Coordinate::Absolute(-3.0, false),
+                }),
+                nbt: Some(nbt::parse("{Tags:[\"a\"]}").unwrap())
+            }))
+        );
+        assert_eq!(
+            parse_line("summon marker ~ ~"),
+            Err(Error::Space(Space::EOFInstead))
+        );
+        assert_eq!(parse_line("summon marker 1 x 2"), Err(Error::Coordinate));
+    }
+
+    #[test]
+    fn kill_and_tag() {
+        assert_eq!(
+            parse_line("kill"),
+            Ok(Command::Kill(Kill { targets: None }))
+        );
+        assert_eq!(
+            parse_line("kill @e[type=marker]"),
+            Ok(Command::Kill(Kill {
+                targets: Some(parse_target("@e[type=marker]").unwrap())
+            }))
+        );
+        assert_eq!(
+            parse_line("tag @s add in.game"),
+            Ok(Command::Tag(Tag {
+                targets: Target::Selector(Selector::new(SelectorVariable::S)),
+                action: TagAction::Add("in.game".to_string())
+            }))
+        );
+        assert_eq!(
+            parse_line("tag Alice remove a"),
+            Ok(Command::Tag(Tag {
+                targets: Target::Name("Alice".to_string()),
+                action: TagAction::Remove("a".to_string())
+            }))
+        );
+        assert_eq!(
+            parse_line("tag @e list"),
+            Ok(Command::Tag(Tag {
+                targets: Target::Selector(Selector::new(SelectorVariable::E)),
+                action: TagAction::List
+            }))
+        );
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(
+            vec3(&mut Input::new("~ ~-1.5 12.25")),
+            Ok(Vec3 {
+                x: Coordinate::Relative(0.0),
+                y: Coordinate::Relative(-1.5),
+                z: Coordinate::Absolute(12.25, true),
+            })
+        );
+        assert_eq!(
+            vec3(&mut Input::new("^ ^1 ^-2")),
+            Ok(Vec3 {
+                x: Coordinate::Local(0.0),
+                y: Coordinate::Local(1.0),
+                z: Coordinate::Local(-2.0),
+            })
+        );
+        assert_eq!(vec3(&mut Input::new("^ ~ ^")), Err(Error::Coordinate));
+        assert_eq!(vec3(&mut Input::new("1 2 x")), Err(Error::Coordinate));
+        assert_eq!(
+            block_pos(&mut Input::new("1 ~0.5 -3")),
+            Ok(BlockPos {
+                x: Coordinate::Absolute(1.0, false),
+                y: Coordinate::Relative(0.5),
+                z: Coordinate::Absolute(-3.0, false),
+            })
+        );
+        assert_eq!(
+            block_pos(&mut Input::new("1.5 2 3")),
+            Err(Error::Coordinate)
+        );
+        assert_eq!(
+            vec2(&mut Input::new("~2 -7")),
+            Ok(Vec2 {
+                x: Coordinate::Relative(2.0),
+                z: Coordinate::Absolute(-7.0, false),
+            })
+        );
+        assert_eq!(vec2(&mut Input::new("^ ^")), Err(Error::Coordinate));
+    }
+
+    #[test]
+    fn teleport() {
+        let name = |name: &str| Target::Name(name.to_string());
+        let at = |x, y, z| Vec3 {
+            x: Coordinate::Absolute(x, false),
+            y: Coordinate::Absolute(y, false),
+            z: Coordinate::Absolute(z, false),
+        };
+        assert_eq!(
+            parse_line("tp Bob"),
+            Ok(Command::Teleport(Teleport {
+                targets: None,
+                destination: Destination::Entity(name("Bob"))
+            }))
+        );
+        assert_eq!(
+            parse_line("teleport 1 2 3"),
+            Ok(Command::Teleport(Teleport {
+                targets: None,
+                destination: Destination::Location(at(1.0, 2.0, 3.0), None)
+            }))
+        );
+        assert_eq!(
+            parse_line("tp @a Bob"),
+            Ok(Command::Teleport(Teleport {
+                targets: Some(Target::Selector(Selector::new(SelectorVariable::A))),
+                destination: Destination::Entity(name("Bob"))
+            }))
+        );
+        assert_eq!(
+            parse_line("tp Alice 1 2 3 ~90 -45"),
+            Ok(Command::Teleport(Teleport {
+                targets: Some(name("Alice")),
+                destination: Destination::Location(
+                    at(1.0, 2.0, 3.0),
+                    Some(Orientation::Rotation(Rotation {
+                        yaw: Coordinate::Relative(90.0),
+                        pitch: Coordinate::Absolute(-45.0, false)
+                    }))
+                )
+            }))
+        );
+        assert_eq!(
+            parse_line("tp Alice 1 2 3 facing 4 5 6"),
+            Ok(Command::Teleport(Teleport {
+                targets: Some(name("Alice")),
+                destination: Destination::Location(
+                    at(1.0, 2.0, 3.0),
+                    Some(Orientation::FacingLocation(at(4.0, 5.0, 6.0)))
+                )
+            }))
+        );
+        assert_eq!(
+            parse_line("tp Alice 1 2 3 facing entity Bob eyes"),
+            Ok(Command::Teleport(Teleport {
+                targets: Some(name("Alice")),
+                destination: Destination::Location(
+                    at(1.0, 2.0, 3.0),
+                    Some(Orientation::FacingEntity(name("Bob"), Anchor::Eyes))
+                )
+            }))
+        );
+        assert_eq!(
+            parse_line("tp Alice 1 2 3 facing entity Bob"),
+            Ok(Command::Teleport(Teleport {
+                targets: Some(name("Alice")),
+                destination: Destination::Location(
+                    at(1.0, 2.0, 3.0),
+                    Some(Orientation::FacingEntity(name("Bob"), Anchor::Feet))
+                )
+            }))
+        );
+        assert_eq!(parse_line("tp Alice 1 2 3 ^ ^"), Err(Error::Coordinate));
+    }
+
+    #[test]
+    fn position_selector_arguments() {
+        let mut selector = Selector::new(SelectorVariable::E);
+        selector.arguments = vec![
+            SelectorArgument::X(1.5),
+            SelectorArgument::Y(-2.0),
+            SelectorArgument::Z(0.0),
+            SelectorArgument::Distance(FloatRange {
+                min: None,
+                max: Some(5.5),
+            }),
+            SelectorArgument::Distance(FloatRange {
+                min: Some(1.0),
+                max: None,
+            }),
+            SelectorArgument::Distance(FloatRange {
+                min: Some(2.0),
+                max: Some(2.0),
+            }),
+            SelectorArgument::Dx(3.0),
+            SelectorArgument::Dy(0.0),
+            SelectorArgument::Dz(-1.0),
+            SelectorArgument::Sort(Sort::Furthest),
+        ];
+        assert_eq!(
+            parse_target(
+                "@e[x=1.5,y=-2,z=0,distance=..5.5,distance=1..,distance=2,dx=3,dy=0,dz=-1,sort=furthest]"
+            ),
+            Ok(Target::Selector(selector))
+        );
+        assert_eq!(parse_target("@e[sort=sideways]"), Err(Error::Target));
+        assert_eq!(parse_target("@e[distance=]"), Err(Error::Target));
+    }
+
+    #[test]
+    fn selector_limit_must_be_positive() {
+        assert_eq!(parse_target("@e[limit=0]"), Err(Error::Target));
+        assert_eq!(
+            parse_target("@e[limit=1]"),
+            Ok(Target::Selector(Selector {
+                variable: SelectorVariable::E,
+                arguments: vec![SelectorArgument::Limit(1)],
+            }))
+        );
+    }
+
+    #[test]
+    fn selector_malformed_scores() {
+        assert_eq!(parse_target("@e[scores={a=x}]"), Err(Error::Integer));
+        assert_eq!(parse_line("kill @e[scores={a=x}]"), Err(Error::Integer));
+    }
+
+    #[test]
+    fn blocks() {
+        let at = |x, y, z| BlockPos {
+            x: Coordinate::Absolute(x, false),
+            y: Coordinate::Absolute(y, false),
+            z: Coordinate::Absolute(z, false),
+        };
+        let mut stairs = Block::new("oak_stairs");
+        stairs
+            .states
+            .insert("facing".to_string(), "east".to_string());
+        stairs.nbt = Some(nbt::parse("{a:\"}\"}").unwrap());
+        assert_eq!(
+            parse_line("setblock 1 2 3 oak_stairs[facing=east]{a:\"}\"} keep"),
+            Ok(Command::SetBlock(SetBlock {
+                position: at(1.0, 2.0, 3.0),
+                block: stairs,
+                mode: SetBlockMode::Keep,
+            }))
+        );
+        assert_eq!(
+            parse_line("fill 0 0 0 1 1 1 air replace minecraft:stone"),
+            Ok(Command::Fill(Fill {
+                from: at(0.0, 0.0, 0.0),
+                to: at(1.0, 1.0, 1.0),
+                block: Block::new("air"),
+                mode: FillMode::Replace(Some(Block::new("minecraft:stone"))),
+            }))
+        );
+        assert_eq!(
+            parse_line("clone 0 0 0 1 1 1 2 0 0 masked"),
+            Ok(Command::Clone(CloneBlocks {
+                begin: at(0.0, 0.0, 0.0),
+                end: at(1.0, 1.0, 1.0),
+                destination: at(2.0, 0.0, 0.0),
+                mask: CloneMask::Masked,
+                mode: CloneMode::Normal,
+            }))
+        );
+        assert_eq!(
+            parse_line("execute unless block 0 0 0 air run kill"),
+            Ok(Command::Execute(Execute::Unless(If::Block(
+                BlockCondition {
+                    position: at(0.0, 0.0, 0.0),
+                    block: Block::new("air"),
+                    command: Box::new(Command::Kill(Kill { targets: None })),
+                }
+            ))))
+        );
+        assert_eq!(
+            parse_line("setblock 0 0 0 stone[facing=]"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("fill 0 0 0 1 1 1 stone sideways"),
+            Err(Error::Command)
+        );
+        assert_eq!(
+            parse_line("execute if block 0 0 0 stone say hi"),
+            Err(Error::Command)
+        );
+    }
+
+    #[test]
+    fn place_template() {
+        assert_eq!(
+            parse_line("place template ns:house"),
+            Ok(Command::Place(Place::Template(PlaceTemplate {
+                template: "ns:house".to_string(),
+                position: None,
+                rotation: StructureRotation::None,
+                mirror: Mirror::None,
+            })))
+        );
+        assert_eq!(
+            parse_line("place template house ~ ~ ~ 180 front_back"),
+            Ok(Command::Place(Place::Template(PlaceTemplate {
+                template: "house".to_string(),
+                position: Some(BlockPos {
+                    x: Coordinate::Relative(0.0),
+                    y: Coordinate::Relative(0.0),
+                    z: Coordinate::Relative(0.0),
+                }),
+                rotation: StructureRotation::Clockwise180,
+                mirror: Mirror::FrontBack,
+            })))
+        );
+        assert_eq!(
+            parse_line("place template house 0 0 0 sideways"),
+            Err(Error::Command)
+        );
+        assert_eq!(parse_line("place feature tree"), Err(Error::Command));
+    }
+
+    #[test]
+    fn items() {
+        let mut sword = ItemStack::new("diamond_sword", 1);
+        sword
+            .components
+            .insert("damage".to_string(), nbt::Nbt::Int(5));
+        assert_eq!(
+            parse_line("give @s diamond_sword[damage=5] 2"),
+            Ok(Command::Give(Give {
+                targets: Target::Selector(Selector::new(SelectorVariable::S)),
+                item: sword,
+                count: Some(2),
+            }))
+        );
+        assert_eq!(
+            parse_line("clear Alice *{shop:1b}"),
+            Ok(Command::Clear(Clear {
+                targets: Some(Target::Name("Alice".to_string())),
+                item: Some(ItemPredicate {
+                    id: None,
+                    tests: vec![ComponentTest::Matches(
+                        "minecraft:custom_data".to_string(),
+                        nbt::parse("{shop:1b}").unwrap()
+                    )],
+                }),
+                max_count: None,
+            }))
+        );
+        assert_eq!(parse_line("give @s diamond 0"), Err(Error::Identifier));
+        assert_eq!(parse_line("clear Alice diamond x"), Err(Error::Integer));
+        assert_eq!(
+            parse_line("give Alice stone 99999999999"),
+            Err(Error::Integer)
+        );
+        assert_eq!(
+            parse_line("give @s diamond[damage~5]"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("item replace entity @s pocket.0 with stone"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("item replace block 0 0 0 container.0 with stone"),
+            Err(Error::Command)
+        );
+        assert_eq!(
+            parse_line("item replace entity @s hotbar.* with stone"),
+            Err(Error::Identifier)
+        );
+    }
+
+    #[test]
+    fn criteria() {
+        let criteria =
+            |name: &str| match parse_line(&format!("scoreboard objectives add obj {}", name)) {
+                Ok(Command::Scoreboard(Scoreboard::Objectives(Objectives::Add(add)))) => {
+                    Ok(add.criteria)
+                }
+                other => other.map(|_| Criteria::Dummy),
+            };
+        assert_eq!(criteria("deathCount"), Ok(Criteria::DeathCount));
+        assert_eq!(
+            criteria("teamkill.light_purple"),
+            Ok(Criteria::TeamKill(text::Color::LightPurple))
+        );
+        assert_eq!(
+            criteria("minecraft.killed:minecraft.zombie"),
+            Ok(Criteria::Statistic(
+                StatType::Killed,
+                "minecraft:zombie".to_string()
+            ))
+        );
+        assert_eq!(
+            criteria("mined:stone"),
+            Ok(Criteria::Statistic(
+                StatType::Mined,
+                "minecraft:stone".to_string()
+            ))
+        );
+        assert_eq!(criteria("Health"), Err(Error::Identifier));
+        assert_eq!(criteria("teamkill.#FF0000"), Err(Error::Identifier));
+        assert_eq!(criteria("minecraft.jumped:stone"), Err(Error::Identifier));
+        assert_eq!(criteria("other.used:stone"), Err(Error::Identifier));
+        assert_eq!(criteria("minecraft.used:"), Err(Error::Identifier));
+    }
+
+    #[test]
+    fn random() {
+        assert_eq!(
+            parse_line("random roll 1..6 dice"),
+            Ok(Command::Random(Random::Roll(
+                Interval::Bounded(1, 6),
+                Some("dice".to_string())
+            )))
+        );
+        assert_eq!(
+            parse_line("random value 0.."),
+            Ok(Command::Random(Random::Value(
+                Interval::RightUnbounded(0),
+                None
+            )))
+        );
+        assert_eq!(
+            parse_line("random reset * 5 false"),
+            Ok(Command::Random(Random::Reset(RandomReset {
+                sequence: None,
+                seed: Some(5),
+                include_world_seed: Some(false),
+                include_sequence_id: None,
+            })))
+        );
+        assert_eq!(parse_line("random value abc"), Err(Error::Integer));
+        assert_eq!(
+            parse_line("random value 1..x"),
+            Err(Error::Space(Space::SymbolInstead))
+        );
+        assert_eq!(
+            parse_line("kill @e[sort=random]"),
+            Ok(Command::Kill(Kill {
+                targets: Some(Target::Selector(Selector {
+                    variable: SelectorVariable::E,
+                    arguments: vec![SelectorArgument::Sort(Sort::Random)],
+                }))
+            }))
+        );
+    }
+
+    #[test]
+    fn predicates() {
+        assert_eq!(
+            parse_line("execute unless predicate ns:night run say day"),
+            Ok(Command::Execute(Execute::Unless(If::Predicate(
+                PredicateCondition {
+                    predicate: "ns:night".to_string(),
+                    command: Box::new(Command::Say(Say {
+                        message: "day".to_string()
+                    })),
+                }
+            ))))
+        );
+        assert_eq!(
+            parse_line("kill @e[predicate=!ns:rich/enough]"),
+            Ok(Command::Kill(Kill {
+                targets: Some(Target::Selector(Selector {
+                    variable: SelectorVariable::E,
+                    arguments: vec![SelectorArgument::Predicate {
+                        predicate: "ns:rich/enough".to_string(),
+                        negated: true,
+                    }],
+                }))
+            }))
+        );
+    }
+
+    #[test]
+    fn advancement() {
+        assert_eq!(
+            parse_line("advancement grant @s only ns:story/root joined"),
+            Ok(Command::Advancement(Advancement {
+                action: AdvancementAction::Grant,
+                targets: Target::Selector(Selector::new(SelectorVariable::S)),
+                selection: AdvancementSelection::Only(
+                    "ns:story/root".to_string(),
+                    Some("joined".to_string())
+                ),
+            }))
+        );
+        assert_eq!(
+            parse_line("advancement revoke Alice everything"),
+            Ok(Command::Advancement(Advancement {
+                action: AdvancementAction::Revoke,
+                targets: Target::Name("Alice".to_string()),
+                selection: AdvancementSelection::Everything,
+            }))
+        );
+        assert_eq!(
+            parse_line("advancement give Alice everything"),
+            Err(Error::Command)
+        );
+    }
+
+    #[test]
+    fn time_and_weather() {
+        assert_eq!(
+            parse_line("time set noon"),
+            Ok(Command::Time(Time::Set(6000)))
+        );
+        assert_eq!(
+            parse_line("time add 1.5d"),
+            Ok(Command::Time(Time::Add(36000)))
+        );
+        assert_eq!(
+            parse_line("time query daytime"),
+            Ok(Command::Time(Time::Query(TimeQuery::Daytime)))
+        );
+        assert_eq!(parse_line("time set dusk"), Err(Error::Identifier));
+        assert_eq!(
+            parse_line("weather thunder 30s"),
+            Ok(Command::Weather(Weather {
+                kind: WeatherKind::Thunder,
+                duration: Some(600),
+            }))
+        );
+        assert_eq!(parse_line("weather rain 0"), Err(Error::Identifier));
+        assert_eq!(parse_line("difficulty"), Ok(Command::Difficulty(None)));
+        assert_eq!(
+            parse_line("difficulty hard"),
+            Ok(Command::Difficulty(Some(Difficulty::Hard)))
+        );
+        assert_eq!(parse_line("difficulty extreme"), Err(Error::Identifier));
+    }
+
+    #[test]
+    fn gamerules() {
+        assert_eq!(
+            parse_line("gamerule keepInventory true"),
+            Ok(Command::Gamerule(Gamerule {
+                rule: GameRule::KeepInventory,
+                value: Some(GameRuleValue::Bool(true)),
+            }))
+        );
+        assert_eq!(
+            parse_line("gamerule randomTickSpeed"),
+            Ok(Command::Gamerule(Gamerule {
+                rule: GameRule::RandomTickSpeed,
+                value: None,
+            }))
+        );
+        assert_eq!(
+            parse_line("gamerule keepInventory 1"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("gamerule noSuchRule true"),
+            Err(Error::Identifier)
+        );
+    }
+
+    #[test]
+    fn number_formats() {
+        assert_eq!(
+            parse_line("scoreboard objectives modify obj numberformat styled {\"color\":\"red\"}"),
+            Ok(Command::Scoreboard(Scoreboard::Objectives(
+                Objectives::Modify(ObjectivesModify {
+                    objective: "obj".to_string(),
+                    modification: Modification::NumberFormat(Some(NumberFormat::Styled(
+                        text::Style {
+                            color: Some(text::Color::Red),
+                            ..text::Style::default()
+                        }
+                    ))),
+                })
+            )))
+        );
+        assert_eq!(
+            parse_line("scoreboard players display name Alice obj"),
+            Ok(Command::Scoreboard(Scoreboard::Players(Players::Display(
+                PlayersDisplay {
+                    targets: Target::Name("Alice".to_string()),
+                    objective: "obj".to_string(),
+                    option: ScoreDisplayOption::Name(None),
+                }
+            ))))
+        );
+        assert_eq!(
+            parse_line("scoreboard objectives modify obj numberformat styled \"red\""),
+            Err(Error::Text(text::Error::Content))
+        );
+        assert_eq!(
+            parse_line("scoreboard objectives modify obj numberformat plain"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("scoreboard objectives modify obj displayautoupdate on"),
+            Err(Error::Identifier)
+        );
+    }
+
+    #[test]
+    fn teams() {
+        assert_eq!(
+            parse_line("team join red.1 @s"),
+            Ok(Command::Team(Team::Join(
+                "red.1".to_string(),
+                Some(Target::Selector(Selector::new(SelectorVariable::S)))
+            )))
+        );
+        assert_eq!(
+            parse_line("scoreboard objectives setdisplay sidebar.team.gold obj"),
+            Ok(Command::Scoreboard(Scoreboard::Objectives(
+                Objectives::SetDisplay(ObjectivesSetDisplay {
+                    slot: DisplaySlot::SidebarTeam(text::Color::Gold),
+                    objective: "obj".to_string(),
+                })
+            )))
+        );
+        assert_eq!(
+            parse_line("scoreboard objectives setdisplay sidebar.team.pink obj"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("team modify red color #FF0000"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("team modify red friendlyFire yes"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(
+            parse_line("team modify red collisionRule pushEveryone"),
+            Err(Error::Identifier)
+        );
+        assert_eq!(parse_line("team promote red"), Err(Error::Command));
+    }
 }
@@ -0,0 +1,137 @@
+const GOLDEN_RATIO_64: u64 = 0x9e37_79b9_7f4a_7c15;
+const SILVER_RATIO_64: u64 = 0x6a09_e667_f3bc_c909;
+
+/// Xoroshiro128++, the generator vanilla seeds its random sequences with. The same seed always
+/// gives the same numbers.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct RandomSource {
+    lo: u64,
+    hi: u64,
+}
+
+impl RandomSource {
+    pub fn new(seed: i64) -> RandomSource {
+        let (lo, hi) = unmixed(seed);
+        RandomSource::mixed(lo, hi)
+    }
+
+    /// The generator for a named sequence: the seed, and the id unless it is left out, spread
+    /// over both halves of the state. Vanilla hashes the id with MD5; this uses FNV-1a.
+    pub fn for_sequence(seed: i64, id: Option<&str>) -> RandomSource {
+        let (mut lo, mut hi) = unmixed(seed);
+        if let Some(id) = id {
+            lo ^= fnv1a(id.as_bytes(), 0xcbf2_9ce4_8422_2325);
+            hi ^= fnv1a(id.as_bytes(), 0x8422_2325_cbf2_9ce4);
+        }
+        RandomSource::mixed(lo, hi)
+    }
+
+    fn mixed(lo: u64, hi: u64) -> RandomSource {
+        let (lo, hi) = (stafford13(lo), stafford13(hi));
+        if lo == 0 && hi == 0 {
+            return RandomSource {
+                lo: GOLDEN_RATIO_64,
+                hi: SILVER_RATIO_64,
+            };
+        }
+        RandomSource { lo, hi }
+    }
+
+    pub fn next_long(&mut self) -> u64 {
+        let (lo, hi) = (self.lo, self.hi);
+        let result = lo.wrapping_add(hi).rotate_left(17).wrapping_add(lo);
+        let hi = hi ^ lo;
+        self.lo = lo.rotate_left(49) ^ hi ^ (hi << 21);
+        self.hi = hi.rotate_left(28);
+        result
+    }
+
+    /// A number from 0 up to but not including `bound`, with no bias towards any.
+    pub fn next_int(&mut self, bound: u32) -> u32 {
+        let bound = u64::from(bound);
+        let mut product = (self.next_long() & 0xffff_ffff) * bound;
+        if product & 0xffff_ffff < bound {
+            let threshold = (bound.wrapping_neg() & 0xffff_ffff) % bound;
+            while product & 0xffff_ffff < threshold {
+                product = (self.next_long() & 0xffff_ffff) * bound;
+            }
+        }
+        (product >> 32) as u32
+    }
+
+    /// A number from `min` to `max`, both included. `max` must not be less than `min`.
+    pub fn between(&mut self, min: i32, max: i32) -> i32 {
+        let size = (i64::from(max) - i64::from(min) + 1) as u32;
+        (i64::from(min) + i64::from(self.next_int(size))) as i32
+    }
+
+    /// A number from 0 up to but not including 1.
+    pub fn next_float(&mut self) -> f32 {
+        (self.next_long() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.next_int(i as u32 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+fn unmixed(seed: i64) -> (u64, u64) {
+    let lo = seed as u64 ^ SILVER_RATIO_64;
+    (lo, lo.wrapping_add(GOLDEN_RATIO_64))
+}
+
+fn stafford13(z: u64) -> u64 {
+    let z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn fnv1a(bytes: &[u8], basis: u64) -> u64 {
+    bytes.iter().fold(basis, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let mut a = RandomSource::new(42);
+        let mut b = RandomSource::new(42);
+        let rolls: Vec<i32> = (0..20).map(|_| a.between(1, 6)).collect();
+        assert_eq!(
+            rolls,
+            (0..20).map(|_| b.between(1, 6)).collect::<Vec<i32>>()
+        );
+        assert!(rolls.iter().all(|roll| (1..=6).contains(roll)));
+        assert_ne!(RandomSource::new(42), RandomSource::new(43));
+        assert_ne!(
+            RandomSource::for_sequence(42, Some("minecraft:a")),
+            RandomSource::for_sequence(42, Some("minecraft:b"))
+        );
+        assert_eq!(
+            RandomSource::for_sequence(42, None),
+            RandomSource::for_sequence(42, None)
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let mut random = RandomSource::new(0);
+        for _ in 0..1000 {
+            let value = random.between(i32::MIN + 1, i32::MAX);
+            assert!(value > i32::MIN);
+            let float = random.next_float();
+            assert!((0.0..1.0).contains(&float));
+        }
+        let mut items = [1, 2, 3, 4, 5];
+        random.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+}
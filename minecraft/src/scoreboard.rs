@@ -0,0 +1,185 @@
+use crate::ansi;
+use crate::text::{Color, TextComponent};
+use crate::NumberFormat;
+
+/// The sidebar shows at most this many scores.
+pub const SIDEBAR_LINES: usize = 15;
+
+/// What a client sees of the scoreboard: the sidebar, the player list and the scores under
+/// players' names. Each is there only while an objective is displayed in its slot.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct ScoreboardView {
+    pub sidebar: Option<Panel>,
+    pub list: Option<Panel>,
+    pub below_name: Option<Panel>,
+}
+
+/// A titled column of names and their scores.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Panel {
+    pub title: TextComponent,
+    pub lines: Vec<Line>,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Line {
+    pub name: TextComponent,
+    pub score: TextComponent,
+}
+
+impl Line {
+    pub fn new(name: TextComponent, score: TextComponent) -> Line {
+        Line { name, score }
+    }
+}
+
+impl ScoreboardView {
+    pub fn is_empty(&self) -> bool {
+        self.sidebar.is_none() && self.list.is_none() && self.below_name.is_none()
+    }
+
+    /// Each shown panel boxed in turn, the sidebar first. With `colors` off this is plain text.
+    pub fn render(&self, colors: bool) -> String {
+        [&self.sidebar, &self.list, &self.below_name]
+            .iter()
+            .filter_map(|panel| panel.as_ref())
+            .map(|panel| panel.render(colors))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Panel {
+    /// The panel in a box: the title centered on top, then one line per score with the name on
+    /// the left and the score on the right.
+    pub fn render(&self, colors: bool) -> String {
+        let title_width = self.title.plain().chars().count();
+        let width = self
+            .lines
+            .iter()
+            .map(|line| line.name.plain().chars().count() + 1 + line.score.plain().chars().count())
+            .chain(std::iter::once(title_width + 2))
+            .max()
+            .unwrap_or(0);
+        let left = (width - title_width) / 2;
+        let mut output = format!("┌{}┐\n", "─".repeat(width));
+        output.push_str(&format!(
+            "│{}{}{}│\n",
+            " ".repeat(left),
+            ansi::render(&self.title, colors),
+            " ".repeat(width - title_width - left)
+        ));
+        output.push_str(&format!("├{}┤\n", "─".repeat(width)));
+        for line in &self.lines {
+            let used = line.name.plain().chars().count() + line.score.plain().chars().count();
+            output.push_str(&format!(
+                "│{}{}{}│\n",
+                ansi::render(&line.name, colors),
+                " ".repeat(width - used),
+                ansi::render(&line.score, colors)
+            ));
+        }
+        output.push_str(&format!("└{}┘", "─".repeat(width)));
+        output
+    }
+}
+
+/// A score written in `format`, or as a plain number in `color`: red in the sidebar, yellow in
+/// the player list.
+pub fn format_score(
+    score: i32,
+    format: Option<&NumberFormat>,
+    color: Option<Color>,
+) -> TextComponent {
+    match format {
+        Some(NumberFormat::Blank) => TextComponent::text(""),
+        Some(NumberFormat::Fixed(text)) => text.clone(),
+        Some(NumberFormat::Styled(style)) => {
+            let mut number = TextComponent::text(&score.to_string());
+            number.style = Box::new(style.clone());
+            number
+        }
+        None => {
+            let mut number = TextComponent::text(&score.to_string());
+            number.style.color = color;
+            number
+        }
+    }
+}
+
+/// A score shown as hearts, two points to a heart, the way the player list draws health. Past
+/// ten hearts the client gives up and writes the number of hearts instead.
+pub fn hearts(score: i32) -> TextComponent {
+    let score = score.max(0);
+    if score > 20 {
+        return colored(&format!("{}❤", f64::from(score) / 2.0), Color::Red);
+    }
+    let mut hearts = "❤".repeat(score as usize / 2);
+    if score % 2 == 1 {
+        hearts.push('♡');
+    }
+    colored(&hearts, Color::Red)
+}
+
+fn colored(text: &str, color: Color) -> TextComponent {
+    let mut component = TextComponent::text(text);
+    component.style.color = Some(color);
+    component
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn red(score: i32) -> TextComponent {
+        format_score(score, None, Some(Color::Red))
+    }
+
+    #[test]
+    fn number_formats() {
+        assert_eq!(red(5).style.color, Some(Color::Red));
+        assert_eq!(
+            format_score(5, Some(&NumberFormat::Blank), None).plain(),
+            ""
+        );
+        let fixed = NumberFormat::Fixed(TextComponent::text("-"));
+        assert_eq!(format_score(5, Some(&fixed), None).plain(), "-");
+        let style = crate::text::Style {
+            bold: Some(true),
+            ..Default::default()
+        };
+        let styled = format_score(5, Some(&NumberFormat::Styled(style)), Some(Color::Red));
+        assert_eq!(styled.plain(), "5");
+        assert_eq!(styled.style.color, None);
+        assert_eq!(styled.style.bold, Some(true));
+    }
+
+    #[test]
+    fn panels() {
+        let panel = Panel {
+            title: TextComponent::text("Points"),
+            lines: vec![
+                Line::new(TextComponent::text("Alice"), red(12)),
+                Line::new(TextComponent::text("Bob"), red(3)),
+            ],
+        };
+        assert_eq!(
+            panel.render(false),
+            "┌────────┐\n\
+             │ Points │\n\
+             ├────────┤\n\
+             │Alice 12│\n\
+             │Bob    3│\n\
+             └────────┘"
+        );
+        assert!(panel.render(true).contains("\x1b["));
+    }
+
+    #[test]
+    fn heart_scores() {
+        assert_eq!(hearts(7).plain(), "❤❤❤♡");
+        assert_eq!(hearts(20).plain(), "❤❤❤❤❤❤❤❤❤❤");
+        assert_eq!(hearts(25).plain(), "12.5❤");
+        assert_eq!(hearts(-3).plain(), "");
+    }
+}
@@ -0,0 +1,297 @@
+use crate::block::Block;
+use crate::nbt;
+use crate::nbt::Nbt;
+use crate::{Mirror, StructureRotation};
+use flate2::read::GzDecoder;
+use std::io::Read;
+
+/// A structure template, as saved by structure blocks into `.nbt` files.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Structure {
+    pub size: [i32; 3],
+    pub blocks: Vec<([i32; 3], Block)>,
+    /// Entity positions within the template and their data, including their `id`.
+    pub entities: Vec<([f64; 3], Nbt)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Gzip,
+    Nbt(nbt::Error),
+    /// The NBT is missing a tag a structure needs, or it has the wrong type.
+    Missing(&'static str),
+}
+
+impl Structure {
+    /// Reads a structure file, which vanilla gzips.
+    pub fn load(bytes: &[u8]) -> Result<Structure, Error> {
+        let tag = if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .map_err(|_| Error::Gzip)?;
+            nbt::read(&decompressed)
+        } else {
+            nbt::read(bytes)
+        };
+        Structure::from_nbt(&tag.map_err(Error::Nbt)?)
+    }
+
+    /// Builds a structure from its NBT. Templates with several palettes use the first.
+    pub fn from_nbt(tag: &Nbt) -> Result<Structure, Error> {
+        let size = int_triple(tag.get("size")).ok_or(Error::Missing("size"))?;
+        let palette = match (tag.get("palette"), tag.get("palettes")) {
+            (Some(Nbt::List(palette)), _) => palette,
+            (None, Some(Nbt::List(palettes))) => match palettes.first() {
+                Some(Nbt::List(palette)) => palette,
+                _ => return Err(Error::Missing("palette")),
+            },
+            _ => return Err(Error::Missing("palette")),
+        };
+        let palette = palette
+            .iter()
+            .map(palette_block)
+            .collect::<Option<Vec<Block>>>()
+            .ok_or(Error::Missing("Name"))?;
+
+        let mut blocks = Vec::new();
+        if let Some(Nbt::List(entries)) = tag.get("blocks") {
+            for entry in entries {
+                let position = int_triple(entry.get("pos")).ok_or(Error::Missing("pos"))?;
+                let mut block = match entry.get("state") {
+                    Some(Nbt::Int(state)) => palette.get(*state as usize).cloned(),
+                    _ => None,
+                }
+                .ok_or(Error::Missing("state"))?;
+                block.nbt = entry.get("nbt").cloned();
+                blocks.push((position, block));
+            }
+        }
+
+        let mut entities = Vec::new();
+        if let Some(Nbt::List(entries)) = tag.get("entities") {
+            for entry in entries {
+                let position = match entry.get("pos") {
+                    Some(Nbt::List(pos)) => match pos.as_slice() {
+                        [x, y, z] => x.as_f64().and_then(|x| Some([x, y.as_f64()?, z.as_f64()?])),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+                .ok_or(Error::Missing("pos"))?;
+                let data = entry.get("nbt").cloned().ok_or(Error::Missing("nbt"))?;
+                entities.push((position, data));
+            }
+        }
+
+        Ok(Structure {
+            size,
+            blocks,
+            entities,
+        })
+    }
+}
+
+fn int_triple(tag: Option<&Nbt>) -> Option<[i32; 3]> {
+    match tag {
+        Some(Nbt::List(values)) => match values.as_slice() {
+            [Nbt::Int(x), Nbt::Int(y), Nbt::Int(z)] => Some([*x, *y, *z]),
+            _ => None,
+        },
+        Some(Nbt::IntArray(values)) => match values.as_slice() {
+            [x, y, z] => Some([*x, *y, *z]),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn palette_block(entry: &Nbt) -> Option<Block> {
+    let mut block = Block::new(entry.get("Name")?.as_str()?);
+    if let Some(Nbt::Compound(properties)) = entry.get("Properties") {
+        for (state, value) in properties {
+            block.states.insert(state.clone(), value.as_text());
+        }
+    }
+    Some(block)
+}
+
+/// Where a block of the template ends up relative to the placement position, mirrored first
+/// and then rotated about the template's origin, as vanilla does.
+pub fn transform(position: [i32; 3], mirror: Mirror, rotation: StructureRotation) -> [i32; 3] {
+    let [mut x, y, mut z] = position;
+    match mirror {
+        Mirror::LeftRight => z = -z,
+        Mirror::FrontBack => x = -x,
+        Mirror::None => {}
+    }
+    match rotation {
+        StructureRotation::None => [x, y, z],
+        StructureRotation::Clockwise90 => [-z, y, x],
+        StructureRotation::Clockwise180 => [-x, y, -z],
+        StructureRotation::Counterclockwise90 => [z, y, -x],
+    }
+}
+
+/// Like `transform`, for entities, which sit within their block rather than at its corner.
+pub fn transform_exact(
+    position: [f64; 3],
+    mirror: Mirror,
+    rotation: StructureRotation,
+) -> [f64; 3] {
+    let [mut x, y, mut z] = position;
+    match mirror {
+        Mirror::LeftRight => z = 1.0 - z,
+        Mirror::FrontBack => x = 1.0 - x,
+        Mirror::None => {}
+    }
+    match rotation {
+        StructureRotation::None => [x, y, z],
+        StructureRotation::Clockwise90 => [1.0 - z, y, x],
+        StructureRotation::Clockwise180 => [1.0 - x, y, 1.0 - z],
+        StructureRotation::Counterclockwise90 => [z, y, 1.0 - x],
+    }
+}
+
+/// Horizontal directions in clockwise order.
+const HORIZONTAL: [&str; 4] = ["north", "east", "south", "west"];
+
+/// Turns the block to match the template's mirror and rotation. Only the common directional
+/// states are known: `facing`, `axis` and the sixteen-step `rotation` of signs and banners.
+pub fn transform_block(block: &Block, mirror: Mirror, rotation: StructureRotation) -> Block {
+    let mut block = block.clone();
+    if let Some(facing) = block.states.get_mut("facing") {
+        let mirrored = match (mirror, facing.as_str()) {
+            (Mirror::LeftRight, "north") => "south",
+            (Mirror::LeftRight, "south") => "north",
+            (Mirror::FrontBack, "east") => "west",
+            (Mirror::FrontBack, "west") => "east",
+            (_, other) => other,
+        };
+        *facing = match HORIZONTAL.iter().position(|d| *d == mirrored) {
+            Some(index) => String::from(HORIZONTAL[(index + quarter_turns(rotation)) % 4]),
+            None => String::from(mirrored),
+        };
+    }
+    if let Some(axis) = block.states.get_mut("axis") {
+        if quarter_turns(rotation) % 2 == 1 {
+            *axis = match axis.as_str() {
+                "x" => String::from("z"),
+                "z" => String::from("x"),
+                other => String::from(other),
+            };
+        }
+    }
+    if let Some(step) = block.states.get_mut("rotation") {
+        if let Ok(value) = step.parse::<i32>() {
+            let signed = if value > 8 { value - 16 } else { value };
+            let mirrored = match mirror {
+                Mirror::LeftRight => (8 - signed).rem_euclid(16),
+                Mirror::FrontBack => (16 - signed) % 16,
+                Mirror::None => value,
+            };
+            *step = ((mirrored + 4 * quarter_turns(rotation) as i32) % 16).to_string();
+        }
+    }
+    block
+}
+
+fn quarter_turns(rotation: StructureRotation) -> usize {
+    match rotation {
+        StructureRotation::None => 0,
+        StructureRotation::Clockwise90 => 1,
+        StructureRotation::Clockwise180 => 2,
+        StructureRotation::Counterclockwise90 => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn template() -> Nbt {
+        nbt::parse(
+            "{size:[2,1,1],palette:[{Name:\"minecraft:stone\"},\
+             {Name:\"minecraft:furnace\",Properties:{facing:\"north\",lit:\"false\"}}],\
+             blocks:[{pos:[0,0,0],state:0},{pos:[1,0,0],state:1,nbt:{Lock:\"key\"}}],\
+             entities:[{pos:[0.5d,0.0d,0.5d],blockPos:[0,0,0],nbt:{id:\"minecraft:marker\"}}]}",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn load_gzipped() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&nbt::write(&template())).unwrap();
+        let structure = Structure::load(&encoder.finish().unwrap()).unwrap();
+        assert_eq!(structure.size, [2, 1, 1]);
+        assert_eq!(
+            structure
+                .blocks
+                .iter()
+                .map(|(position, block)| (*position, block.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                ([0, 0, 0], "minecraft:stone".to_string()),
+                (
+                    [1, 0, 0],
+                    "minecraft:furnace[facing=north,lit=false]{Lock:\"key\"}".to_string()
+                )
+            ]
+        );
+        assert_eq!(structure.entities[0].0, [0.5, 0.0, 0.5]);
+        assert_eq!(
+            Structure::load(&nbt::write(&template())),
+            Ok(structure.clone())
+        );
+        assert_eq!(
+            Structure::load(&nbt::write(&Nbt::compound())),
+            Err(Error::Missing("size"))
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        use Mirror as M;
+        use StructureRotation as R;
+        assert_eq!(transform([1, 2, 3], M::None, R::Clockwise90), [-3, 2, 1]);
+        assert_eq!(transform([1, 2, 3], M::None, R::Clockwise180), [-1, 2, -3]);
+        assert_eq!(
+            transform([1, 2, 3], M::None, R::Counterclockwise90),
+            [3, 2, -1]
+        );
+        assert_eq!(transform([1, 2, 3], M::LeftRight, R::None), [1, 2, -3]);
+        assert_eq!(
+            transform([1, 2, 3], M::FrontBack, R::Clockwise90),
+            [-3, 2, -1]
+        );
+        assert_eq!(
+            transform_exact([0.5, 0.0, 0.25], M::None, R::Clockwise90),
+            [0.75, 0.0, 0.5]
+        );
+
+        let state = |block: &Block, state: &str, value: &str| {
+            let mut block = block.clone();
+            block.states.insert(state.to_string(), value.to_string());
+            block
+        };
+        let turned = |block: &Block, mirror, rotation, name: &str| {
+            transform_block(block, mirror, rotation).states[name].clone()
+        };
+        let furnace = state(&Block::new("furnace"), "facing", "north");
+        assert_eq!(turned(&furnace, M::None, R::Clockwise90, "facing"), "east");
+        assert_eq!(turned(&furnace, M::LeftRight, R::None, "facing"), "south");
+        assert_eq!(
+            turned(&furnace, M::LeftRight, R::Counterclockwise90, "facing"),
+            "east"
+        );
+        let log = state(&Block::new("oak_log"), "axis", "x");
+        assert_eq!(turned(&log, M::None, R::Clockwise90, "axis"), "z");
+        let sign = state(&Block::new("oak_sign"), "rotation", "1");
+        assert_eq!(turned(&sign, M::None, R::Clockwise90, "rotation"), "5");
+        assert_eq!(turned(&sign, M::LeftRight, R::None, "rotation"), "7");
+    }
+}
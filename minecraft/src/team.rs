@@ -0,0 +1,66 @@
+use crate::entity::ScoreHolder;
+use crate::text::{Color, TextComponent};
+use crate::{CollisionRule, Visibility};
+use std::collections::BTreeSet;
+
+/// A team made with `team add`, holding its options and the score holders on it. A holder is on
+/// at most one team.
+#[derive(PartialEq, Clone, Debug)]
+pub struct PlayerTeam {
+    pub display_name: TextComponent,
+    pub color: Option<Color>,
+    pub prefix: TextComponent,
+    pub suffix: TextComponent,
+    pub friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub nametag_visibility: Visibility,
+    pub death_message_visibility: Visibility,
+    pub collision_rule: CollisionRule,
+    pub members: BTreeSet<ScoreHolder>,
+}
+
+impl PlayerTeam {
+    pub fn new(display_name: TextComponent) -> PlayerTeam {
+        PlayerTeam {
+            display_name,
+            color: None,
+            prefix: TextComponent::text(""),
+            suffix: TextComponent::text(""),
+            friendly_fire: true,
+            see_friendly_invisibles: true,
+            nametag_visibility: Visibility::Always,
+            death_message_visibility: Visibility::Always,
+            collision_rule: CollisionRule::Always,
+            members: BTreeSet::new(),
+        }
+    }
+
+    /// A member's name the way chat and the sidebar show it: in the team's color, between its
+    /// prefix and suffix.
+    pub fn format_name(&self, name: TextComponent) -> TextComponent {
+        let mut formatted = TextComponent::text("");
+        formatted.style.color = self.color;
+        formatted.extra = vec![self.prefix.clone(), name, self.suffix.clone()];
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatted_names() {
+        let mut team = PlayerTeam::new(TextComponent::text("Red"));
+        assert_eq!(
+            team.format_name(TextComponent::text("Alice")).plain(),
+            "Alice"
+        );
+        team.prefix = TextComponent::text("[R] ");
+        team.suffix = TextComponent::text("!");
+        team.color = Some(Color::Red);
+        let name = team.format_name(TextComponent::text("Alice"));
+        assert_eq!(name.plain(), "[R] Alice!");
+        assert_eq!(name.style.color, Some(Color::Red));
+    }
+}
@@ -0,0 +1,930 @@
+use crate::json::Json;
+use crate::{json, lower, parse, Target};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A raw JSON text component, as accepted by `tellraw`, objective display names and titles.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextComponent {
+    pub content: Content,
+    pub style: Box<Style>,
+    pub extra: Vec<TextComponent>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Content {
+    Text(String),
+    Translatable {
+        key: String,
+        fallback: Option<String>,
+        with: Vec<TextComponent>,
+    },
+    Score {
+        name: Target,
+        objective: String,
+        value: Option<String>,
+    },
+    Selector {
+        selector: Target,
+        separator: Option<Box<TextComponent>>,
+    },
+    Keybind(String),
+    Nbt {
+        path: String,
+        source: NbtSource,
+        interpret: bool,
+        separator: Option<Box<TextComponent>>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum NbtSource {
+    Block(String),
+    Entity(Target),
+    Storage(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub font: Option<String>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underlined: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub obfuscated: Option<bool>,
+    pub insertion: Option<String>,
+    pub click_event: Option<ClickEvent>,
+    pub hover_event: Option<HoverEvent>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Color {
+    Black,
+    DarkBlue,
+    DarkGreen,
+    DarkAqua,
+    DarkRed,
+    DarkPurple,
+    Gold,
+    Gray,
+    DarkGray,
+    Blue,
+    Green,
+    Aqua,
+    Red,
+    LightPurple,
+    Yellow,
+    White,
+    Hex(u32),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClickEvent {
+    pub action: ClickAction,
+    pub value: String,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ClickAction {
+    OpenUrl,
+    RunCommand,
+    SuggestCommand,
+    ChangePage,
+    CopyToClipboard,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum HoverEvent {
+    ShowText(Box<TextComponent>),
+    ShowItem {
+        id: String,
+        count: Option<i32>,
+        tag: Option<String>,
+    },
+    ShowEntity {
+        kind: String,
+        id: String,
+        name: Option<Box<TextComponent>>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    Json(json::Error),
+    Empty,
+    Content,
+    Color(String),
+    ClickEvent,
+    HoverEvent,
+    Selector(String),
+}
+
+const NAMED_COLORS: [(Color, &str); 16] = [
+    (Color::Black, "black"),
+    (Color::DarkBlue, "dark_blue"),
+    (Color::DarkGreen, "dark_green"),
+    (Color::DarkAqua, "dark_aqua"),
+    (Color::DarkRed, "dark_red"),
+    (Color::DarkPurple, "dark_purple"),
+    (Color::Gold, "gold"),
+    (Color::Gray, "gray"),
+    (Color::DarkGray, "dark_gray"),
+    (Color::Blue, "blue"),
+    (Color::Green, "green"),
+    (Color::Aqua, "aqua"),
+    (Color::Red, "red"),
+    (Color::LightPurple, "light_purple"),
+    (Color::Yellow, "yellow"),
+    (Color::White, "white"),
+];
+
+impl Color {
+    pub fn from_name(name: &str) -> Option<Color> {
+        match name.strip_prefix('#') {
+            Some(hex) if hex.len() == 6 => u32::from_str_radix(hex, 16).ok().map(Color::Hex),
+            Some(_) => None,
+            None => NAMED_COLORS
+                .iter()
+                .find(|(_, n)| *n == name)
+                .map(|(c, _)| *c),
+        }
+    }
+
+    /// The 24-bit value the client renders this color as.
+    pub fn rgb(self) -> u32 {
+        match self {
+            Color::Black => 0x000000,
+            Color::DarkBlue => 0x0000AA,
+            Color::DarkGreen => 0x00AA00,
+            Color::DarkAqua => 0x00AAAA,
+            Color::DarkRed => 0xAA0000,
+            Color::DarkPurple => 0xAA00AA,
+            Color::Gold => 0xFFAA00,
+            Color::Gray => 0xAAAAAA,
+            Color::DarkGray => 0x555555,
+            Color::Blue => 0x5555FF,
+            Color::Green => 0x55FF55,
+            Color::Aqua => 0x55FFFF,
+            Color::Red => 0xFF5555,
+            Color::LightPurple => 0xFF55FF,
+            Color::Yellow => 0xFFFF55,
+            Color::White => 0xFFFFFF,
+            Color::Hex(rgb) => rgb,
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Hex(rgb) => write!(f, "#{:06X}", rgb),
+            named => write!(
+                f,
+                "{}",
+                NAMED_COLORS.iter().find(|(c, _)| c == named).unwrap().1
+            ),
+        }
+    }
+}
+
+impl ClickAction {
+    fn from_name(name: &str) -> Option<ClickAction> {
+        match name {
+            "open_url" => Some(ClickAction::OpenUrl),
+            "run_command" => Some(ClickAction::RunCommand),
+            "suggest_command" => Some(ClickAction::SuggestCommand),
+            "change_page" => Some(ClickAction::ChangePage),
+            "copy_to_clipboard" => Some(ClickAction::CopyToClipboard),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ClickAction::OpenUrl => "open_url",
+            ClickAction::RunCommand => "run_command",
+            ClickAction::SuggestCommand => "suggest_command",
+            ClickAction::ChangePage => "change_page",
+            ClickAction::CopyToClipboard => "copy_to_clipboard",
+        }
+    }
+}
+
+impl Content {
+    /// The text this content alone contributes, without any children.
+    pub fn plain(&self) -> String {
+        match self {
+            Content::Text(text) => text.clone(),
+            Content::Translatable {
+                key,
+                fallback,
+                with,
+            } => translate(
+                fallback.as_ref().unwrap_or(key),
+                &with.iter().map(TextComponent::plain).collect::<Vec<_>>(),
+            ),
+            Content::Score { value, .. } => value.clone().unwrap_or_default(),
+            Content::Selector { selector, .. } => lower::target(selector.clone()),
+            Content::Keybind(key) => key.clone(),
+            Content::Nbt { .. } => String::new(),
+        }
+    }
+}
+
+impl Style {
+    pub fn is_empty(&self) -> bool {
+        *self == Style::default()
+    }
+
+    /// Fills in anything this style leaves unset from `parent`, the way children inherit
+    /// formatting from the component that contains them.
+    pub fn inherit(&self, parent: &Style) -> Style {
+        Style {
+            color: self.color.or(parent.color),
+            font: self.font.clone().or_else(|| parent.font.clone()),
+            bold: self.bold.or(parent.bold),
+            italic: self.italic.or(parent.italic),
+            underlined: self.underlined.or(parent.underlined),
+            strikethrough: self.strikethrough.or(parent.strikethrough),
+            obfuscated: self.obfuscated.or(parent.obfuscated),
+            insertion: self.insertion.clone().or_else(|| parent.insertion.clone()),
+            click_event: self
+                .click_event
+                .clone()
+                .or_else(|| parent.click_event.clone()),
+            hover_event: self
+                .hover_event
+                .clone()
+                .or_else(|| parent.hover_event.clone()),
+        }
+    }
+}
+
+impl TextComponent {
+    pub fn text(text: &str) -> TextComponent {
+        TextComponent {
+            content: Content::Text(String::from(text)),
+            style: Box::default(),
+            extra: vec![],
+        }
+    }
+
+    /// A translatable component carrying its English pattern as the fallback, since there are no
+    /// language files to look `key` up in.
+    pub fn translatable(key: &str, pattern: &str, with: Vec<TextComponent>) -> TextComponent {
+        TextComponent {
+            content: Content::Translatable {
+                key: String::from(key),
+                fallback: Some(String::from(pattern)),
+                with,
+            },
+            style: Box::default(),
+            extra: vec![],
+        }
+    }
+
+    /// The component as unformatted text, the way it reads with all styling stripped.
+    pub fn plain(&self) -> String {
+        let mut output = self.content.plain();
+        for child in &self.extra {
+            output.push_str(&child.plain());
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> Json {
+        if self.style.is_empty() && self.extra.is_empty() {
+            if let Content::Text(text) = &self.content {
+                return Json::String(text.clone());
+            }
+        }
+        let mut members = Vec::new();
+        match &self.content {
+            Content::Text(text) => members.push(member("text", Json::String(text.clone()))),
+            Content::Translatable {
+                key,
+                fallback,
+                with,
+            } => {
+                members.push(member("translate", Json::String(key.clone())));
+                if let Some(fallback) = fallback {
+                    members.push(member("fallback", Json::String(fallback.clone())));
+                }
+                if !with.is_empty() {
+                    members.push(member(
+                        "with",
+                        Json::Array(with.iter().map(TextComponent::to_json).collect()),
+                    ));
+                }
+            }
+            Content::Score {
+                name,
+                objective,
+                value,
+            } => {
+                let mut score = vec![
+                    member("name", Json::String(lower::target(name.clone()))),
+                    member("objective", Json::String(objective.clone())),
+                ];
+                if let Some(value) = value {
+                    score.push(member("value", Json::String(value.clone())));
+                }
+                members.push(member("score", Json::Object(score)));
+            }
+            Content::Selector {
+                selector,
+                separator,
+            } => {
+                members.push(member(
+                    "selector",
+                    Json::String(lower::target(selector.clone())),
+                ));
+                if let Some(separator) = separator {
+                    members.push(member("separator", separator.to_json()));
+                }
+            }
+            Content::Keybind(key) => members.push(member("keybind", Json::String(key.clone()))),
+            Content::Nbt {
+                path,
+                source,
+                interpret,
+                separator,
+            } => {
+                members.push(member("nbt", Json::String(path.clone())));
+                members.push(match source {
+                    NbtSource::Block(pos) => member("block", Json::String(pos.clone())),
+                    NbtSource::Entity(target) => {
+                        member("entity", Json::String(lower::target(target.clone())))
+                    }
+                    NbtSource::Storage(id) => member("storage", Json::String(id.clone())),
+                });
+                if *interpret {
+                    members.push(member("interpret", Json::Bool(true)));
+                }
+                if let Some(separator) = separator {
+                    members.push(member("separator", separator.to_json()));
+                }
+            }
+        }
+        style_members(&self.style, &mut members);
+        if !self.extra.is_empty() {
+            members.push(member(
+                "extra",
+                Json::Array(self.extra.iter().map(TextComponent::to_json).collect()),
+            ));
+        }
+        Json::Object(members)
+    }
+}
+
+impl Style {
+    pub fn to_json(&self) -> Json {
+        let mut members = vec![];
+        style_members(self, &mut members);
+        Json::Object(members)
+    }
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+impl Display for TextComponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TranslationPart {
+    Literal(String),
+    Argument(usize),
+}
+
+/// Splits a translation pattern into literal text and the `%s`/`%<n>$s` argument slots
+/// between it, numbering arguments from zero.
+pub fn translation_parts(pattern: &str) -> Vec<TranslationPart> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut next = 0;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+        let argument = match chars.peek().copied() {
+            Some('%') => {
+                chars.next();
+                literal.push('%');
+                None
+            }
+            Some('s') => {
+                chars.next();
+                next += 1;
+                Some(next - 1)
+            }
+            Some(d) if d.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(d) = chars.peek().copied().filter(char::is_ascii_digit) {
+                    digits.push(d);
+                    chars.next();
+                }
+                if chars.next() == Some('$') && chars.next() == Some('s') {
+                    let index: usize = digits.parse().unwrap_or(1);
+                    Some(index.saturating_sub(1))
+                } else {
+                    None
+                }
+            }
+            _ => {
+                literal.push('%');
+                None
+            }
+        };
+        if let Some(index) = argument {
+            if !literal.is_empty() {
+                parts.push(TranslationPart::Literal(literal.clone()));
+                literal.clear();
+            }
+            parts.push(TranslationPart::Argument(index));
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(TranslationPart::Literal(literal));
+    }
+    parts
+}
+
+/// Substitutes `%s` and `%<n>$s` placeholders in a translation pattern.
+pub fn translate(pattern: &str, args: &[String]) -> String {
+    translation_parts(pattern)
+        .into_iter()
+        .map(|part| match part {
+            TranslationPart::Literal(text) => text,
+            TranslationPart::Argument(index) => args.get(index).cloned().unwrap_or_default(),
+        })
+        .collect()
+}
+
+pub fn parse(text: &str) -> Result<TextComponent, Error> {
+    json::parse(text)
+        .map_err(Error::Json)
+        .and_then(|json| from_json(&json))
+}
+
+/// A style on its own, written as the JSON object a component would carry it in.
+pub fn parse_style(text: &str) -> Result<Style, Error> {
+    match json::parse(text).map_err(Error::Json)? {
+        json @ Json::Object(_) => style(&json),
+        _ => Err(Error::Content),
+    }
+}
+
+pub fn from_json(json: &Json) -> Result<TextComponent, Error> {
+    match json {
+        Json::String(text) => Ok(TextComponent::text(text)),
+        Json::Number(n) => Ok(TextComponent::text(&n.to_string())),
+        Json::Bool(b) => Ok(TextComponent::text(&b.to_string())),
+        Json::Array(elements) => {
+            let mut elements = elements.iter();
+            let mut first = from_json(elements.next().ok_or(Error::Empty)?)?;
+            for element in elements {
+                first.extra.push(from_json(element)?);
+            }
+            Ok(first)
+        }
+        Json::Object(_) => {
+            let content = content(json)?;
+            let style = style(json)?;
+            let extra = match json.get("extra") {
+                Some(Json::Array(children)) => children
+                    .iter()
+                    .map(from_json)
+                    .collect::<Result<Vec<_>, _>>()?,
+                Some(_) => return Err(Error::Content),
+                None => vec![],
+            };
+            Ok(TextComponent {
+                content,
+                style: Box::new(style),
+                extra,
+            })
+        }
+        Json::Null => Err(Error::Content),
+    }
+}
+
+fn content(json: &Json) -> Result<Content, Error> {
+    if let Some(text) = json.get("text") {
+        return match text {
+            Json::String(s) => Ok(Content::Text(s.clone())),
+            Json::Number(n) => Ok(Content::Text(n.to_string())),
+            Json::Bool(b) => Ok(Content::Text(b.to_string())),
+            _ => Err(Error::Content),
+        };
+    }
+    if let Some(key) = json.get("translate").and_then(Json::as_str) {
+        let with = match json.get("with") {
+            Some(Json::Array(args)) => args.iter().map(from_json).collect::<Result<_, _>>()?,
+            Some(_) => return Err(Error::Content),
+            None => vec![],
+        };
+        return Ok(Content::Translatable {
+            key: String::from(key),
+            fallback: json
+                .get("fallback")
+                .and_then(Json::as_str)
+                .map(String::from),
+            with,
+        });
+    }
+    if let Some(score) = json.get("score") {
+        let name = score
+            .get("name")
+            .and_then(Json::as_str)
+            .ok_or(Error::Content)?;
+        let objective = score
+            .get("objective")
+            .and_then(Json::as_str)
+            .ok_or(Error::Content)?;
+        return Ok(Content::Score {
+            name: score_name(name)?,
+            objective: String::from(objective),
+            value: score.get("value").and_then(Json::as_str).map(String::from),
+        });
+    }
+    if let Some(selector) = json.get("selector").and_then(Json::as_str) {
+        return Ok(Content::Selector {
+            selector: target(selector)?,
+            separator: separator(json)?,
+        });
+    }
+    if let Some(key) = json.get("keybind").and_then(Json::as_str) {
+        return Ok(Content::Keybind(String::from(key)));
+    }
+    if let Some(path) = json.get("nbt").and_then(Json::as_str) {
+        let source = if let Some(pos) = json.get("block").and_then(Json::as_str) {
+            NbtSource::Block(String::from(pos))
+        } else if let Some(entity) = json.get("entity").and_then(Json::as_str) {
+            NbtSource::Entity(target(entity)?)
+        } else if let Some(storage) = json.get("storage").and_then(Json::as_str) {
+            NbtSource::Storage(String::from(storage))
+        } else {
+            return Err(Error::Content);
+        };
+        return Ok(Content::Nbt {
+            path: String::from(path),
+            source,
+            interpret: json
+                .get("interpret")
+                .and_then(Json::as_bool)
+                .unwrap_or(false),
+            separator: separator(json)?,
+        });
+    }
+    Err(Error::Content)
+}
+
+fn score_name(name: &str) -> Result<Target, Error> {
+    match name {
+        "*" => Ok(Target::Name(String::from("*"))),
+        _ => target(name),
+    }
+}
+
+fn target(text: &str) -> Result<Target, Error> {
+    parse::parse_target(text).map_err(|_| Error::Selector(String::from(text)))
+}
+
+fn separator(json: &Json) -> Result<Option<Box<TextComponent>>, Error> {
+    json.get("separator")
+        .map(|s| from_json(s).map(Box::new))
+        .transpose()
+}
+
+fn style(json: &Json) -> Result<Style, Error> {
+    let color = match json.get("color").and_then(Json::as_str) {
+        Some("reset") | None => None,
+        Some(name) => Some(Color::from_name(name).ok_or_else(|| Error::Color(name.to_string()))?),
+    };
+    let flag = |key: &str| json.get(key).and_then(Json::as_bool);
+    let click_event = match json.get("clickEvent").or_else(|| json.get("click_event")) {
+        Some(event) => Some(click_event(event)?),
+        None => None,
+    };
+    let hover_event = match json.get("hoverEvent").or_else(|| json.get("hover_event")) {
+        Some(event) => Some(hover_event(event)?),
+        None => None,
+    };
+    Ok(Style {
+        color,
+        font: json.get("font").and_then(Json::as_str).map(String::from),
+        bold: flag("bold"),
+        italic: flag("italic"),
+        underlined: flag("underlined"),
+        strikethrough: flag("strikethrough"),
+        obfuscated: flag("obfuscated"),
+        insertion: json
+            .get("insertion")
+            .and_then(Json::as_str)
+            .map(String::from),
+        click_event,
+        hover_event,
+    })
+}
+
+fn click_event(json: &Json) -> Result<ClickEvent, Error> {
+    let action = json
+        .get("action")
+        .and_then(Json::as_str)
+        .and_then(ClickAction::from_name)
+        .ok_or(Error::ClickEvent)?;
+    let value = match json.get("value") {
+        Some(Json::String(s)) => s.clone(),
+        Some(Json::Number(n)) => n.to_string(),
+        _ => return Err(Error::ClickEvent),
+    };
+    Ok(ClickEvent { action, value })
+}
+
+fn hover_event(json: &Json) -> Result<HoverEvent, Error> {
+    let contents = json
+        .get("contents")
+        .or_else(|| json.get("value"))
+        .ok_or(Error::HoverEvent)?;
+    match json.get("action").and_then(Json::as_str) {
+        Some("show_text") => Ok(HoverEvent::ShowText(Box::new(from_json(contents)?))),
+        Some("show_item") => match contents {
+            Json::String(id) => Ok(HoverEvent::ShowItem {
+                id: id.clone(),
+                count: None,
+                tag: None,
+            }),
+            Json::Object(_) => Ok(HoverEvent::ShowItem {
+                id: contents
+                    .get("id")
+                    .and_then(Json::as_str)
+                    .map(String::from)
+                    .ok_or(Error::HoverEvent)?,
+                count: contents.get("count").and_then(Json::as_i32),
+                tag: contents.get("tag").and_then(Json::as_str).map(String::from),
+            }),
+            _ => Err(Error::HoverEvent),
+        },
+        Some("show_entity") => Ok(HoverEvent::ShowEntity {
+            kind: contents
+                .get("type")
+                .and_then(Json::as_str)
+                .map(String::from)
+                .ok_or(Error::HoverEvent)?,
+            id: contents
+                .get("id")
+                .and_then(Json::as_str)
+                .map(String::from)
+                .ok_or(Error::HoverEvent)?,
+            name: contents
+                .get("name")
+                .map(|name| from_json(name).map(Box::new))
+                .transpose()?,
+        }),
+        _ => Err(Error::HoverEvent),
+    }
+}
+
+fn member(key: &str, value: Json) -> (String, Json) {
+    (String::from(key), value)
+}
+
+fn style_members(style: &Style, members: &mut Vec<(String, Json)>) {
+    if let Some(color) = style.color {
+        members.push(member("color", Json::String(color.to_string())));
+    }
+    if let Some(font) = &style.font {
+        members.push(member("font", Json::String(font.clone())));
+    }
+    let flags = [
+        ("bold", style.bold),
+        ("italic", style.italic),
+        ("underlined", style.underlined),
+        ("strikethrough", style.strikethrough),
+        ("obfuscated", style.obfuscated),
+    ];
+    for (key, value) in flags.iter() {
+        if let Some(value) = value {
+            members.push(member(key, Json::Bool(*value)));
+        }
+    }
+    if let Some(insertion) = &style.insertion {
+        members.push(member("insertion", Json::String(insertion.clone())));
+    }
+    if let Some(event) = &style.click_event {
+        members.push(member(
+            "clickEvent",
+            Json::Object(vec![
+                member("action", Json::String(String::from(event.action.name()))),
+                member("value", Json::String(event.value.clone())),
+            ]),
+        ));
+    }
+    if let Some(event) = &style.hover_event {
+        let (action, contents) = match event {
+            HoverEvent::ShowText(text) => ("show_text", text.to_json()),
+            HoverEvent::ShowItem { id, count, tag } => {
+                let mut item = vec![member("id", Json::String(id.clone()))];
+                if let Some(count) = count {
+                    item.push(member("count", Json::Number(f64::from(*count))));
+                }
+                if let Some(tag) = tag {
+                    item.push(member("tag", Json::String(tag.clone())));
+                }
+                ("show_item", Json::Object(item))
+            }
+            HoverEvent::ShowEntity { kind, id, name } => {
+                let mut entity = vec![
+                    member("type", Json::String(kind.clone())),
+                    member("id", Json::String(id.clone())),
+                ];
+                if let Some(name) = name {
+                    entity.push(member("name", name.to_json()));
+                }
+                ("show_entity", Json::Object(entity))
+            }
+        };
+        members.push(member(
+            "hoverEvent",
+            Json::Object(vec![
+                member("action", Json::String(String::from(action))),
+                member("contents", contents),
+            ]),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Selector, SelectorVariable};
+
+    #[test]
+    fn plain_string() {
+        assert_eq!(parse("\"hi\""), Ok(TextComponent::text("hi")));
+        assert_eq!(parse("12"), Ok(TextComponent::text("12")));
+    }
+
+    #[test]
+    fn styled_text() {
+        let component = parse("{\"text\":\"hi\",\"color\":\"red\",\"bold\":true}").unwrap();
+        assert_eq!(component.content, Content::Text("hi".to_string()));
+        assert_eq!(component.style.color, Some(Color::Red));
+        assert_eq!(component.style.bold, Some(true));
+        assert_eq!(component.style.italic, None);
+    }
+
+    #[test]
+    fn hex_color() {
+        let component = parse("{\"text\":\"x\",\"color\":\"#12aB34\"}").unwrap();
+        assert_eq!(component.style.color, Some(Color::Hex(0x12AB34)));
+        assert_eq!(
+            parse("{\"text\":\"x\",\"color\":\"mauve\"}"),
+            Err(Error::Color("mauve".to_string()))
+        );
+    }
+
+    #[test]
+    fn reset_color() {
+        let component = parse("{\"text\":\"x\",\"color\":\"reset\"}").unwrap();
+        assert_eq!(component.style.color, None);
+    }
+
+    #[test]
+    fn array_becomes_extra() {
+        let component = parse("[\"\",{\"text\":\"a\"},\"b\"]").unwrap();
+        assert_eq!(component.content, Content::Text("".to_string()));
+        assert_eq!(
+            component.extra,
+            vec![TextComponent::text("a"), TextComponent::text("b")]
+        );
+        assert_eq!(component.plain(), "ab");
+        assert_eq!(parse("[]"), Err(Error::Empty));
+    }
+
+    #[test]
+    fn score() {
+        assert_eq!(
+            parse("{\"score\":{\"name\":\"@s\",\"objective\":\"obj\"}}")
+                .unwrap()
+                .content,
+            Content::Score {
+                name: Target::Selector(Selector::new(SelectorVariable::S)),
+                objective: "obj".to_string(),
+                value: None,
+            }
+        );
+        assert_eq!(
+            parse("{\"score\":{\"name\":\"*\",\"objective\":\"obj\"}}")
+                .unwrap()
+                .content,
+            Content::Score {
+                name: Target::Name("*".to_string()),
+                objective: "obj".to_string(),
+                value: None,
+            }
+        );
+    }
+
+    #[test]
+    fn selector_with_separator() {
+        let component = parse("{\"selector\":\"@a\",\"separator\":\" | \"}").unwrap();
+        assert_eq!(
+            component.content,
+            Content::Selector {
+                selector: Target::Selector(Selector::new(SelectorVariable::A)),
+                separator: Some(Box::new(TextComponent::text(" | "))),
+            }
+        );
+    }
+
+    #[test]
+    fn translate_with() {
+        let component =
+            parse("{\"translate\":\"%s likes %s\",\"with\":[\"Alice\",{\"text\":\"cake\"}]}")
+                .unwrap();
+        assert_eq!(component.plain(), "Alice likes cake");
+        assert_eq!(
+            translate(
+                "%2$s before %1$s, 100%%",
+                &["a".to_string(), "b".to_string()]
+            ),
+            "b before a, 100%"
+        );
+    }
+
+    #[test]
+    fn nbt_sources() {
+        assert_eq!(
+            parse("{\"nbt\":\"Items[0]\",\"storage\":\"ns:store\",\"interpret\":true}")
+                .unwrap()
+                .content,
+            Content::Nbt {
+                path: "Items[0]".to_string(),
+                source: NbtSource::Storage("ns:store".to_string()),
+                interpret: true,
+                separator: None,
+            }
+        );
+        assert_eq!(parse("{\"nbt\":\"Items\"}"), Err(Error::Content));
+    }
+
+    #[test]
+    fn events() {
+        let component = parse(
+            "{\"text\":\"click\",\"clickEvent\":{\"action\":\"run_command\",\"value\":\"/say hi\"},\
+             \"hoverEvent\":{\"action\":\"show_text\",\"contents\":\"tip\"}}",
+        )
+        .unwrap();
+        assert_eq!(
+            component.style.click_event,
+            Some(ClickEvent {
+                action: ClickAction::RunCommand,
+                value: "/say hi".to_string(),
+            })
+        );
+        assert_eq!(
+            component.style.hover_event,
+            Some(HoverEvent::ShowText(Box::new(TextComponent::text("tip"))))
+        );
+    }
+
+    #[test]
+    fn keybind() {
+        assert_eq!(
+            parse("{\"keybind\":\"key.jump\"}").unwrap().content,
+            Content::Keybind("key.jump".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_content() {
+        assert_eq!(parse("{\"color\":\"red\"}"), Err(Error::Content));
+        assert!(matches!(parse("{"), Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn round_trip() {
+        for text in &[
+            "\"plain\"",
+            "{\"text\":\"hi\",\"color\":\"red\",\"bold\":true,\"extra\":[\"a\"]}",
+            "{\"translate\":\"chat.type.text\",\"with\":[\"a\",\"b\"]}",
+            "{\"score\":{\"name\":\"*\",\"objective\":\"obj\"}}",
+            "{\"selector\":\"@e\",\"separator\":\", \"}",
+            "{\"nbt\":\"a.b\",\"entity\":\"@s\",\"interpret\":true}",
+            "{\"text\":\"x\",\"color\":\"#00FF00\",\"clickEvent\":{\"action\":\"open_url\",\"value\":\"https://example.com\"},\"hoverEvent\":{\"action\":\"show_item\",\"contents\":{\"id\":\"minecraft:stone\",\"count\":2}}}",
+        ] {
+            assert_eq!(&parse(text).unwrap().to_string(), text);
+        }
+    }
+}
@@ -1,13 +1,26 @@
 use colored::*;
 use minecraft::*;
+use std::env;
 use std::io;
 use std::io::Write;
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let colors = flags::colors_enabled(&args);
+    colored::control::set_override(colors);
     let mut logger = Logger {};
-    let mut chat = Chat {};
+    let mut chat = Chat { colors };
     let mut game = execute::Game::new(&mut logger, &mut chat);
+    game.set_verbose(flags::verbose(&args));
+    if let Some(directory) = flags::structure_directory(&args) {
+        game.set_structure_directory(directory.into());
+    }
+    if let Some(seed) = flags::seed(&args) {
+        game.set_seed(seed);
+    }
 
+    let mut bossbars = vec![];
+    let mut scoreboard = scoreboard::ScoreboardView::default();
     loop {
         let mut input = String::new();
         print!("> ");
@@ -15,13 +28,47 @@ fn main() {
         io::stdin()
             .read_line(&mut input)
             .expect("Failed to read line");
-        match parse::parse_line(&input.trim()) {
-            Ok(command) => game.execute(&command),
-            _ => {}
+        if let Ok(command) = parse::parse_line(input.trim()) {
+            game.execute(&command)
+        }
+        let lines = bossbar_lines(&game);
+        if lines != bossbars {
+            for line in &lines {
+                println!("{}", line);
+            }
+            bossbars = lines;
+        }
+        let view = game.scoreboard_view(None);
+        if view != scoreboard {
+            if !view.is_empty() {
+                println!("{}", view.render(colors));
+            }
+            scoreboard = view;
         }
     }
 }
 
+/// The visible bossbars as progress lines in their colors.
+fn bossbar_lines<T: execute::Log, S: execute::Chat>(game: &execute::Game<T, S>) -> Vec<String> {
+    game.bossbars()
+        .values()
+        .filter(|bossbar| bossbar.visible)
+        .map(|bossbar| {
+            let line = bossbar.progress_line(20);
+            match bossbar.color {
+                BossbarColor::Blue => line.blue(),
+                BossbarColor::Green => line.green(),
+                BossbarColor::Pink => line.bright_magenta(),
+                BossbarColor::Purple => line.magenta(),
+                BossbarColor::Red => line.red(),
+                BossbarColor::White => line.white(),
+                BossbarColor::Yellow => line.yellow(),
+            }
+            .to_string()
+        })
+        .collect()
+}
+
 struct Logger {}
 
 impl execute::Log for Logger {
@@ -31,12 +78,30 @@ impl execute::Log for Logger {
             execute::Level::Fail => println!("{}", message.red()),
         }
     }
+
+    fn log_admin(&mut self, sender: &str, message: &str) {
+        println!(
+            "{}",
+            format!("[{}: {}]", sender, message).bright_black().italic()
+        );
+    }
 }
 
-struct Chat {}
+struct Chat {
+    colors: bool,
+}
 
 impl execute::Chat for Chat {
-    fn tell(&mut self, players: Vec<String>, message: &str) {
-        println!("{}", message.white())
+    fn tell(
+        &mut self,
+        _players: Vec<String>,
+        channel: execute::Channel,
+        message: &execute::Message,
+    ) {
+        let text = ansi::render(&message.component, self.colors);
+        match channel {
+            execute::Channel::Chat => println!("{}", text),
+            channel => println!("[{}] {}", channel, text),
+        }
     }
 }
@@ -1,10 +1,15 @@
 use colored::*;
+use minecraft::advancement::AdvancementNode;
 use minecraft::execute::{Datapack, Game};
+use minecraft::json;
+use minecraft::json::Json;
+use minecraft::structure::Structure;
 use minecraft::Function;
-use minecraft::{execute, parse, Command, FunctionIdentifier};
+use minecraft::{ansi, execute, parse, Command, FunctionIdentifier};
 use regex::Regex;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{stdin, stdout, Read, Write};
+use std::io::{stdin, Read, Write};
 use std::path::Path;
 use std::{env, io};
 use zip::read::ZipArchive;
@@ -13,14 +18,17 @@ use zip::read::ZipArchive;
 extern crate lazy_static;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let datapack_path = match args.len() {
-        1 => {
+    let (flags, paths): (Vec<String>, Vec<String>) =
+        env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    let colors = minecraft::flags::colors_enabled(&flags);
+    colored::control::set_override(colors);
+    let datapack_path = match paths.first() {
+        None => {
             print!("Enter datapack path: ");
             io::stdout().flush().unwrap();
             read_line()
         }
-        _ => args[1].clone(),
+        Some(path) => path.clone(),
     };
     let datapack_path = datapack_path.trim();
 
@@ -28,14 +36,34 @@ fn main() {
         Ok(mut zip_archive) => {
             let functions = get_function_files(&mut zip_archive);
             let functions = parse_functions(functions);
+            let structures = get_structures(&mut zip_archive);
+            let item_modifiers =
+                get_json_files(&mut zip_archive, &ITEM_MODIFIER_PATTERN, "item modifier");
+            let predicates = get_json_files(&mut zip_archive, &PREDICATE_PATTERN, "predicate");
+            let advancements =
+                get_json_files(&mut zip_archive, &ADVANCEMENT_PATTERN, "advancement")
+                    .into_iter()
+                    .map(|(id, json)| (id, AdvancementNode::from_json(&json)))
+                    .collect();
             let datapack = Datapack {
                 name: "test".to_string(),
                 functions,
+                structures,
+                item_modifiers,
+                predicates,
+                advancements,
             };
             let mut logger = Logger {};
-            let mut chat = Chat {};
+            let mut chat = Chat { colors };
             let datapack = Some(datapack);
             let mut game = Game::from(&mut logger, &mut chat, &datapack);
+            if let Some(directory) = minecraft::flags::structure_directory(&flags) {
+                game.set_structure_directory(directory.into());
+            }
+            game.set_verbose(minecraft::flags::verbose(&flags));
+            if let Some(seed) = minecraft::flags::seed(&flags) {
+                game.set_seed(seed);
+            }
             game.execute(&Command::Function(FunctionIdentifier {
                 namespace: Some(
                     Path::new(datapack_path)
@@ -93,11 +121,22 @@ impl execute::Log for Logger {
     }
 }
 
-struct Chat {}
+struct Chat {
+    colors: bool,
+}
 
 impl execute::Chat for Chat {
-    fn tell(&mut self, players: Vec<String>, message: &str) {
-        println!("{}", message);
+    fn tell(
+        &mut self,
+        _players: Vec<String>,
+        channel: execute::Channel,
+        message: &execute::Message,
+    ) {
+        let text = ansi::render(&message.component, self.colors);
+        match channel {
+            execute::Channel::Chat => println!("{}", text),
+            channel => println!("[{}] {}", channel, text),
+        }
     }
 }
 
@@ -105,34 +144,84 @@ fn get_function_files(archive: &mut ZipArchive<File>) -> Vec<McFunction> {
     let mut functions = Vec::new();
     for i in 0..archive.len() {
         let mut entry = archive.by_index(i).unwrap();
-        match parse_name(entry.name()) {
-            Some((namespace, name)) => {
-                let mut content = String::new();
-                entry.read_to_string(&mut content).unwrap();
-                functions.push(McFunction {
-                    namespace,
-                    name,
-                    content,
-                })
-            }
-            None => {}
+        if let Some((namespace, name)) = parse_name(entry.name()) {
+            let mut content = String::new();
+            entry.read_to_string(&mut content).unwrap();
+            functions.push(McFunction {
+                namespace,
+                name,
+                content,
+            })
         }
     }
     functions
 }
 
+/// Structure templates stored in the datapack archive, keyed by namespaced id.
+fn get_structures(archive: &mut ZipArchive<File>) -> HashMap<String, Structure> {
+    let mut structures = HashMap::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).unwrap();
+        let id = match STRUCTURE_PATTERN.captures(entry.name()) {
+            Some(captures) => format!("{}:{}", &captures[1], &captures[2]),
+            None => continue,
+        };
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).unwrap();
+        match Structure::load(&bytes) {
+            Ok(structure) => {
+                structures.insert(id, structure);
+            }
+            Err(e) => eprintln!("Failed to load structure {}: {:?}", id, e),
+        }
+    }
+    structures
+}
+
+/// The JSON files whose paths match `pattern`, keyed by the namespaced id it captures.
+fn get_json_files(
+    archive: &mut ZipArchive<File>,
+    pattern: &Regex,
+    kind: &str,
+) -> HashMap<String, Json> {
+    let mut files = HashMap::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).unwrap();
+        let id = match pattern.captures(entry.name()) {
+            Some(captures) => format!("{}:{}", &captures[1], &captures[2]),
+            None => continue,
+        };
+        let mut content = String::new();
+        entry.read_to_string(&mut content).unwrap();
+        match json::parse(&content) {
+            Ok(json) => {
+                files.insert(id, json);
+            }
+            Err(e) => eprintln!("Failed to load {} {}: {:?}", kind, id, e),
+        }
+    }
+    files
+}
+
 lazy_static! {
     static ref PATH_PATTERN: Regex =
         Regex::new(r"^data/(\w+)/functions/(\w+).mcfunction$").unwrap();
+    static ref STRUCTURE_PATTERN: Regex =
+        Regex::new(r"^data/(\w+)/structures?/([\w/.-]+)\.nbt$").unwrap();
+    static ref ITEM_MODIFIER_PATTERN: Regex =
+        Regex::new(r"^data/(\w+)/item_modifiers?/([\w/.-]+)\.json$").unwrap();
+    static ref PREDICATE_PATTERN: Regex =
+        Regex::new(r"^data/(\w+)/predicates?/([\w/.-]+)\.json$").unwrap();
+    static ref ADVANCEMENT_PATTERN: Regex =
+        Regex::new(r"^data/(\w+)/advancements?/([\w/.-]+)\.json$").unwrap();
 }
 
 fn parse_name(path: &str) -> Option<(String, String)> {
     match PATH_PATTERN.captures(path) {
         Some(captures) => match captures.get(1) {
-            Some(namespace) => match captures.get(2) {
-                Some(name) => Some((namespace.as_str().to_string(), name.as_str().to_string())),
-                None => None,
-            },
+            Some(namespace) => captures
+                .get(2)
+                .map(|name| (namespace.as_str().to_string(), name.as_str().to_string())),
             None => None,
         },
         None => None,
@@ -157,7 +246,7 @@ fn parse_functions(mcfunctions: Vec<McFunction>) -> Vec<Function> {
                     commands.push(match parse::parse_line(text.trim()) {
                         Ok(cmd) => cmd,
                         Err(_) => {
-                            eprintln!("{}", text.to_string());
+                            eprintln!("{}", text);
                             panic!();
                         }
                     });